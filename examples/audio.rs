@@ -6,18 +6,81 @@ async fn main() {
 
     let sound1 = audio::load_sound("sound.wav").await.unwrap();
     let sound2 = audio::load_sound("sound2.wav").await.unwrap();
+    // decoded a bit at a time while it plays, like a long music track should be
+    let music = audio::load_sound_streamed("sound2.wav").await.unwrap();
+
+    let sfx = audio::create_group("sfx");
+    let mut sfx_volume = 1.0;
+    let mut master_volume = 1.0;
+
+    let mut looped: Option<audio::Playback> = None;
+    let mut volume = 1.0;
 
     loop {
         clear_background(LIGHTGRAY);
 
         if ui::root_ui().button(None, "Play sound 1") {
             warn!("play 1!");
-            audio::play_sound_once(&sound1);
+            audio::play_sound_in_group(&sound1, sfx, Default::default());
         }
         if ui::root_ui().button(None, "Play sound 2") {
             warn!("play 2!");
-            audio::play_sound_once(&sound2);
+            // a bit off every time, so that it does not sound the same over and over
+            let params = audio::PlaySoundParams {
+                speed: rand::gen_range(0.8, 1.25),
+                ..Default::default()
+            };
+            audio::play_sound(&sound2, params);
+        }
+
+        let paused = audio::is_group_paused(sfx);
+        if ui::root_ui().button(
+            None,
+            if paused {
+                "Resume sound 1"
+            } else {
+                "Pause sound 1"
+            },
+        ) {
+            if paused {
+                audio::resume_group(sfx);
+            } else {
+                audio::pause_group(sfx);
+            }
+        }
+
+        let (old_sfx_volume, old_master_volume) = (sfx_volume, master_volume);
+        ui::root_ui().slider(ui::hash!(), "sound 1", 0. ..1., &mut sfx_volume);
+        ui::root_ui().slider(ui::hash!(), "master", 0. ..1., &mut master_volume);
+        if sfx_volume != old_sfx_volume {
+            audio::set_group_volume(sfx, sfx_volume);
         }
+        if master_volume != old_master_volume {
+            audio::set_master_volume(master_volume);
+        }
+
+        if let Some(playback) = looped.take() {
+            // fade the loop in and out, the one-shots of sound 2 keep their volume
+            volume = (get_time() as f32).sin() * 0.5 + 0.5;
+            audio::set_playback_volume(&playback, volume);
+
+            if ui::root_ui().button(None, "Restart the loop") {
+                audio::set_sound_position(&music, 0.);
+            }
+            if ui::root_ui().button(None, "Stop the loop") {
+                audio::stop_playback(playback);
+            } else {
+                looped = Some(playback);
+            }
+        } else if ui::root_ui().button(None, "Loop sound 2") {
+            let params = audio::PlaySoundParams {
+                looped: true,
+                volume,
+                ..Default::default()
+            };
+            looped = Some(audio::play_sound(&music, params));
+        }
+
         next_frame().await
     }
 }
@@ -8,6 +8,14 @@ async fn main() {
         draw_line(40.0, 40.0, 100.0, 200.0, 15.0, BLUE);
         draw_rectangle(screen_width() / 2.0 - 60.0, 100.0, 120.0, 60.0, GREEN);
         draw_circle(screen_width() - 30.0, screen_height() - 30.0, 15.0, YELLOW);
+        draw_bezier(
+            vec2(40.0, 300.0),
+            vec2(120.0, 200.0),
+            vec2(200.0, 400.0),
+            vec2(280.0, 300.0),
+            4.0,
+            RED,
+        );
 
         draw_text("HELLO", 20.0, 20.0, 30.0, DARKGRAY);
 
@@ -0,0 +1,32 @@
+use macroquad::prelude::*;
+
+#[macroquad::main("Blend modes")]
+async fn main() {
+    let modes = [
+        (BlendMode::Alpha, "Alpha"),
+        (BlendMode::Additive, "Additive"),
+        (BlendMode::Multiply, "Multiply"),
+        (BlendMode::Replace, "Replace"),
+    ];
+
+    loop {
+        clear_background(Color::new(0.2, 0.2, 0.3, 1.));
+
+        let width = screen_width() / modes.len() as f32;
+        for (i, (mode, name)) in modes.iter().enumerate() {
+            let x = i as f32 * width;
+            draw_rectangle(x + 20., 80., width - 40., 200., LIGHTGRAY);
+            draw_text(name, x + 20., 60., 30., WHITE);
+
+            gl_push_blend_mode(*mode);
+            for (j, color) in [RED, GREEN, BLUE].into_iter().enumerate() {
+                let angle = j as f32 * std::f32::consts::TAU / 3. + get_time() as f32;
+                let center = vec2(x + width / 2., 180.) + Vec2::from_angle(angle) * 30.;
+                draw_circle(center.x, center.y, 50., Color { a: 0.7, ..color });
+            }
+            gl_pop_blend_mode();
+        }
+
+        next_frame().await
+    }
+}
@@ -2,13 +2,31 @@ use macroquad::prelude::*;
 
 #[macroquad::main("Camera")]
 async fn main() {
+    let mut zoom = 1.0;
+    let mut target = vec2(0., 0.);
+
     loop {
+        // pinch to zoom and two finger pan on touch screens
+        for gesture in gestures() {
+            match gesture {
+                Gesture::Pinch { scale_delta, .. } => zoom = (zoom * scale_delta).clamp(0.2, 10.),
+                Gesture::Pan { delta } => {
+                    // touches are in physical pixels, the camera space is
+                    // 2 units across the screen width at zoom 1
+                    let width = screen_width() * dpi_scale();
+                    target -= delta * 2. / (width * zoom);
+                }
+                _ => {}
+            }
+        }
+
         clear_background(LIGHTGRAY);
 
         // Render some primitives in camera space
 
         set_camera(&Camera2D {
-            zoom: vec2(1., screen_width() / screen_height()),
+            zoom: vec2(1., screen_width() / screen_height()) * zoom,
+            target,
             ..Default::default()
         });
         draw_line(-0.4, 0.4, -0.8, 0.9, 0.05, BLUE);
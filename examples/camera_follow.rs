@@ -0,0 +1,87 @@
+use macroquad::prelude::*;
+
+const TILE: f32 = 32.;
+const MAP_W: usize = 100;
+const MAP_H: usize = 60;
+
+#[macroquad::main("Camera follow")]
+async fn main() {
+    // a large tilemap with walls around and some bushes
+    let map: Vec<bool> = (0..MAP_W * MAP_H)
+        .map(|i| {
+            let (x, y) = (i % MAP_W, i / MAP_W);
+            x == 0 || y == 0 || x == MAP_W - 1 || y == MAP_H - 1 || rand::gen_range(0, 10) == 0
+        })
+        .collect();
+    let level = Rect::new(0., 0., MAP_W as f32 * TILE, MAP_H as f32 * TILE);
+
+    let mut player = vec2(level.w / 2., level.h / 2.);
+
+    let mut zoom = 1.;
+    let mut controller = CameraController::new(Camera2D {
+        target: player,
+        ..Default::default()
+    });
+    controller.set_bounds(level);
+    controller.set_deadzone(Rect::new(-60., -40., 120., 80.));
+
+    loop {
+        let mut dir = Vec2::ZERO;
+        if is_key_down(KeyCode::Left) {
+            dir.x -= 1.;
+        }
+        if is_key_down(KeyCode::Right) {
+            dir.x += 1.;
+        }
+        if is_key_down(KeyCode::Up) {
+            dir.y -= 1.;
+        }
+        if is_key_down(KeyCode::Down) {
+            dir.y += 1.;
+        }
+        player += dir.normalize_or_zero() * 400. * get_frame_time();
+        player = player.clamp(vec2(TILE, TILE), vec2(level.w - TILE, level.h - TILE));
+
+        // mouse wheel zooms, the bounds stay exact at any zoom
+        let wheel = mouse_wheel().1;
+        if wheel != 0. {
+            zoom = (zoom * (1. + wheel.signum() * 0.1)).clamp(0.25, 4.);
+        }
+        controller.camera.zoom = vec2(zoom * 2. / screen_width(), zoom * 2. / screen_height());
+
+        if is_key_pressed(KeyCode::Space) {
+            controller.add_trauma(0.5);
+        }
+
+        controller.follow(player, 5., get_frame_time());
+        controller.update_shake(get_frame_time());
+
+        clear_background(DARKGRAY);
+        // the controller applies the shake on top of the followed position
+        set_camera(&controller);
+
+        for (i, &solid) in map.iter().enumerate() {
+            let (x, y) = ((i % MAP_W) as f32 * TILE, (i / MAP_W) as f32 * TILE);
+            let color = if solid {
+                BROWN
+            } else if (i + i / MAP_W) % 2 == 0 {
+                DARKGREEN
+            } else {
+                GREEN
+            };
+            draw_rectangle(x, y, TILE, TILE, color);
+        }
+        draw_circle(player.x, player.y, TILE / 2., YELLOW);
+
+        set_default_camera();
+        draw_text(
+            "arrows to move, wheel to zoom, space to shake",
+            10.,
+            30.,
+            30.,
+            WHITE,
+        );
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,53 @@
+use macroquad::prelude::*;
+
+#[macroquad::main("Cursor")]
+async fn main() {
+    // a small crosshair, its center is the hotspot
+    let mut crosshair = Image::gen_image_color(15, 15, BLANK);
+    for i in 0..15 {
+        crosshair.set_pixel(i, 7, RED);
+        crosshair.set_pixel(7, i, RED);
+    }
+
+    let icons = [
+        CursorIcon::Default,
+        CursorIcon::Pointer,
+        CursorIcon::Crosshair,
+        CursorIcon::Text,
+        CursorIcon::Move,
+        CursorIcon::NotAllowed,
+    ];
+    let mut current = 0;
+
+    loop {
+        if is_mouse_button_pressed(MouseButton::Left) {
+            current = (current + 1) % (icons.len() + 1);
+            match icons.get(current) {
+                Some(icon) => set_mouse_cursor(*icon),
+                None => set_mouse_cursor_image(&crosshair, 7., 7.),
+            }
+        }
+
+        clear_background(LIGHTGRAY);
+
+        let name = icons
+            .get(current)
+            .map_or("image".to_string(), |icon| format!("{icon:?}"));
+        draw_text(
+            &format!("cursor: {name}, click to change"),
+            20.,
+            40.,
+            30.,
+            DARKGRAY,
+        );
+        draw_text(
+            &format!("{:?}", cursor_capabilities()),
+            20.,
+            80.,
+            20.,
+            DARKGRAY,
+        );
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,52 @@
+use macroquad::prelude::*;
+
+const ICONS: [(CursorIcon, &str); 12] = [
+    (CursorIcon::Default, "Default"),
+    (CursorIcon::Help, "Help"),
+    (CursorIcon::Pointer, "Pointer"),
+    (CursorIcon::Wait, "Wait"),
+    (CursorIcon::Crosshair, "Crosshair"),
+    (CursorIcon::Text, "Text"),
+    (CursorIcon::Move, "Move"),
+    (CursorIcon::NotAllowed, "NotAllowed"),
+    (CursorIcon::EWResize, "EWResize"),
+    (CursorIcon::NSResize, "NSResize"),
+    (CursorIcon::NESWResize, "NESWResize"),
+    (CursorIcon::NWSEResize, "NWSEResize"),
+];
+
+#[macroquad::main("Cursors")]
+async fn main() {
+    let mut current = CursorIcon::Default;
+
+    loop {
+        clear_background(LIGHTGRAY);
+
+        let mouse: Vec2 = mouse_position().into();
+        let size = vec2(screen_width() / 4., screen_height() / 3.);
+        let mut hovered = CursorIcon::Default;
+
+        for (i, (icon, name)) in ICONS.iter().enumerate() {
+            let rect = Rect::new(
+                (i % 4) as f32 * size.x,
+                (i / 4) as f32 * size.y,
+                size.x,
+                size.y,
+            );
+            if rect.contains(mouse) {
+                hovered = *icon;
+                draw_rectangle(rect.x, rect.y, rect.w, rect.h, WHITE);
+            }
+            draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2., DARKGRAY);
+            draw_text(name, rect.x + 10., rect.y + 30., 30., BLACK);
+        }
+
+        // only when it changes, not to reset the system cursor every frame
+        if hovered != current {
+            current = hovered;
+            set_mouse_cursor(current);
+        }
+
+        next_frame().await
+    }
+}
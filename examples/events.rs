@@ -4,6 +4,13 @@ use macroquad::prelude::*;
 
 #[macroquad::main("Events")]
 async fn main() {
+    let mut notes = "Arrows, Home and End move the cursor, hold Shift to select.\n\
+        Ctrl+X, Ctrl+C and Ctrl+V cut, copy and paste, Ctrl+Z undoes.\n\n\
+        Keep typing past the bottom of the box and it scrolls along with the cursor. \
+        With the word wrap on, the long lines are broken between the words to fit the width."
+        .to_string();
+    let mut word_wrap = true;
+
     loop {
         clear_background(WHITE);
         root_ui().window(hash!(), Vec2::new(20., 20.), Vec2::new(450., 200.), |ui| {
@@ -40,6 +47,19 @@ async fn main() {
                     }
                 });
         });
+
+        root_ui().window(hash!(), Vec2::new(20., 240.), Vec2::new(450., 250.), |ui| {
+            ui.checkbox(hash!(), "Word wrap", &mut word_wrap);
+            widgets::Editbox::new(hash!(), Vec2::new(440., 200.))
+                .word_wrap(word_wrap)
+                .ui(ui, &mut notes);
+        });
+
+        // edited outside of the editbox, its cursor stays within the text
+        if is_key_pressed(KeyCode::F1) {
+            notes.clear();
+        }
+
         next_frame().await;
     }
 }
@@ -0,0 +1,49 @@
+use macroquad::prelude::*;
+
+// cargo run --example gamepad --features gamepad
+#[macroquad::main("Gamepad")]
+async fn main() {
+    let mut position = vec2(screen_width() / 2., screen_height() / 2.);
+    let mut color = RED;
+    let colors = [RED, GREEN, BLUE, YELLOW];
+
+    loop {
+        for event in get_gamepad_events() {
+            info!("{:?}", event);
+        }
+
+        let pads: Vec<GamepadId> = gamepads().collect();
+        if let Some(&pad) = pads.first() {
+            let stick = vec2(
+                gamepad_axis(pad, GamepadAxis::LeftStickX),
+                -gamepad_axis(pad, GamepadAxis::LeftStickY),
+            );
+            position += stick * 300. * get_frame_time();
+
+            if is_gamepad_button_pressed(pad, GamepadButton::South) {
+                let next = colors.iter().position(|c| *c == color).unwrap() + 1;
+                color = colors[next % colors.len()];
+            }
+        }
+
+        clear_background(LIGHTGRAY);
+
+        draw_circle(position.x, position.y, 30., color);
+        draw_text(
+            &format!("{} gamepad(s) connected", pads.len()),
+            20.,
+            30.,
+            30.,
+            DARKGRAY,
+        );
+        draw_text(
+            "left stick to move, South button to change color",
+            20.,
+            60.,
+            30.,
+            DARKGRAY,
+        );
+
+        next_frame().await
+    }
+}
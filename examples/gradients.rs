@@ -0,0 +1,26 @@
+use macroquad::prelude::*;
+
+#[macroquad::main("Gradients")]
+async fn main() {
+    let sky_top = Color::from_rgba(20, 24, 82, 255);
+    let sky_bottom = Color::from_rgba(240, 140, 90, 255);
+
+    loop {
+        clear_background(BLACK);
+
+        draw_rectangle_gradient_v(0., 0., screen_width(), screen_height(), sky_top, sky_bottom);
+
+        let (x, y) = (screen_width() * 0.7, screen_height() * 0.75);
+        let glow = Color::new(1.0, 0.9, 0.5, 0.0);
+        draw_circle_gradient(x, y, 160., Color::new(1.0, 0.9, 0.5, 0.6), glow);
+        draw_circle(x, y, 40., Color::from_rgba(255, 240, 200, 255));
+
+        let health = (get_time() as f32 * 0.5).sin() * 0.5 + 0.5;
+        draw_rectangle(20., 20., 204., 24., DARKGRAY);
+        draw_rectangle_gradient_h(22., 22., 200. * health, 20., RED, GREEN);
+
+        draw_rectangle_gradient(20., 60., 100., 100., [RED, YELLOW, BLUE, GREEN]);
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,44 @@
+use macroquad::prelude::*;
+
+fn window_conf() -> macroquad::conf::Conf {
+    macroquad::conf::Conf {
+        miniquad_conf: Conf {
+            window_title: "High DPI".to_owned(),
+            window_resizable: true,
+            ..Default::default()
+        },
+        // screen coordinates are physical pixels
+        high_dpi: true,
+        ..Default::default()
+    }
+}
+
+#[macroquad::main(window_conf)]
+async fn main() {
+    loop {
+        clear_background(DARKGRAY);
+
+        // fills the window exactly, with a one pixel outline
+        draw_rectangle(0., 0., screen_width(), screen_height(), DARKBLUE);
+        draw_rectangle_lines(0., 0., screen_width(), screen_height(), 2., YELLOW);
+
+        let (x, y) = mouse_position();
+        draw_circle(x, y, 8., RED);
+
+        draw_text(
+            &format!(
+                "{} x {}, display scale {}, dpi_scale {}",
+                screen_width(),
+                screen_height(),
+                screen_dpi_scale(),
+                dpi_scale(),
+            ),
+            20.,
+            40.,
+            30.,
+            WHITE,
+        );
+
+        next_frame().await
+    }
+}
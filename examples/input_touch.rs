@@ -2,6 +2,9 @@ use macroquad::prelude::*;
 
 #[macroquad::main("InputTouch")]
 async fn main() {
+    // the left mouse button counts as a finger on desktop
+    simulate_touch_with_mouse(true);
+
     loop {
         clear_background(LIGHTGRAY);
 
@@ -14,6 +17,13 @@ async fn main() {
                 TouchPhase::Cancelled => (BLACK, 80.0),
             };
             draw_circle(touch.position.x, touch.position.y, size, fill_color);
+            draw_text(
+                &touch.id.to_string(),
+                touch.position.x - size,
+                touch.position.y - size,
+                20.0,
+                DARKGRAY,
+            );
         }
 
         draw_text("touch the screen!", 20.0, 20.0, 20.0, DARKGRAY);
@@ -0,0 +1,128 @@
+use macroquad::prelude::*;
+
+const SIZE: usize = 128;
+
+const VERTEX: &str = r#"#version 100
+attribute vec3 position;
+attribute vec4 color0;
+attribute vec4 normal;
+
+varying lowp vec4 color;
+varying mediump vec3 world_normal;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    world_normal = normal.xyz;
+}"#;
+
+const FRAGMENT: &str = r#"#version 100
+precision mediump float;
+
+varying lowp vec4 color;
+varying mediump vec3 world_normal;
+
+uniform vec3 light_direction;
+
+void main() {
+    float diffuse = max(dot(normalize(world_normal), -light_direction), 0.0);
+    gl_FragColor = vec4(color.rgb * (0.25 + 0.75 * diffuse), color.a);
+}"#;
+
+fn height(x: f32, z: f32) -> f32 {
+    (x * 0.15).sin() * (z * 0.1).cos() * 3. + (x * 0.5 + z * 0.3).sin() * 0.5
+}
+
+fn terrain() -> Mesh {
+    let mut vertices = Vec::with_capacity(SIZE * SIZE);
+    for z in 0..SIZE {
+        for x in 0..SIZE {
+            let (x, z) = (x as f32 - SIZE as f32 / 2., z as f32 - SIZE as f32 / 2.);
+            let y = height(x, z);
+            let color = if y > 1.5 {
+                WHITE
+            } else if y > -1. {
+                DARKGREEN
+            } else {
+                BEIGE
+            };
+            vertices.push(Vertex::new(x, y, z, 0., 0., color));
+        }
+    }
+
+    let mut indices = Vec::with_capacity((SIZE - 1) * (SIZE - 1) * 6);
+    for z in 0..SIZE - 1 {
+        for x in 0..SIZE - 1 {
+            let i = (z * SIZE + x) as u16;
+            let below = i + SIZE as u16;
+            // counter-clockwise seen from above
+            indices.extend([i, below, i + 1, i + 1, below, below + 1]);
+        }
+    }
+
+    let mut mesh = Mesh {
+        vertices,
+        indices,
+        texture: None,
+    };
+    mesh.compute_normals();
+    mesh
+}
+
+#[macroquad::main("Lit terrain")]
+async fn main() {
+    let material = load_material(
+        ShaderSource::Glsl {
+            vertex: VERTEX,
+            fragment: FRAGMENT,
+        },
+        MaterialParams {
+            uniforms: vec![UniformDesc::new("light_direction", UniformType::Float3)],
+            pipeline_params: PipelineParams {
+                depth_write: true,
+                depth_test: Comparison::LessOrEqual,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let terrain = terrain();
+    // the terrain doesn't change, so it goes to the GPU once
+    let gpu_terrain = terrain.upload().unwrap();
+
+    loop {
+        clear_background(SKYBLUE);
+
+        let time = get_time() as f32;
+        set_camera(&Camera3D {
+            position: vec3(time.cos() * 60., 30., time.sin() * 60.),
+            up: vec3(0., 1., 0.),
+            target: vec3(0., 0., 0.),
+            ..Default::default()
+        });
+
+        // the sun goes around too
+        let light = vec3((time * 0.3).cos(), -0.7, (time * 0.3).sin()).normalize();
+        material.set_uniform("light_direction", light);
+
+        gl_use_material(&material);
+        draw_gpu_mesh(&gpu_terrain);
+        gl_use_default_material();
+
+        set_default_camera();
+        draw_text(
+            &format!("{} triangles", terrain.indices.len() / 3),
+            20.,
+            40.,
+            30.,
+            BLACK,
+        );
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,72 @@
+use macroquad::experimental::coroutines::start_coroutine;
+use macroquad::prelude::*;
+
+const TEXTURES: [&str; 4] = [
+    "examples/chess.png",
+    "examples/ferris.png",
+    "examples/rust.png",
+    "examples/smoke_fire.png",
+];
+
+#[macroquad::main("Loading screen")]
+async fn main() {
+    // one coroutine per texture, they are loaded side by side;
+    // decoding happens off the main thread, so the spinner keeps spinning while loading
+    let loading: Vec<_> = TEXTURES
+        .into_iter()
+        .map(|path| {
+            start_coroutine(async move {
+                load_texture_with_format(path, Some(ImageFormat::Png))
+                    .await
+                    .unwrap()
+            })
+        })
+        .collect();
+
+    while !loading.iter().all(|coroutine| coroutine.is_done()) {
+        clear_background(BLACK);
+
+        let (x, y) = (screen_width() / 2., screen_height() / 2.);
+        let angle = get_time() as f32 * 360.;
+        draw_arc(x, y, 32, 40., angle, 8., 90., WHITE);
+        let done = loading
+            .iter()
+            .filter(|coroutine| coroutine.is_done())
+            .count();
+        draw_text(
+            &format!("loading {}/{}...", done, loading.len()),
+            x - 90.,
+            y + 80.,
+            30.,
+            WHITE,
+        );
+
+        next_frame().await
+    }
+
+    let textures: Vec<Texture2D> = loading
+        .iter()
+        .map(|coroutine| coroutine.retrieve().unwrap())
+        .collect();
+
+    loop {
+        clear_background(LIGHTGRAY);
+
+        let mut x = 10.;
+        for texture in &textures {
+            draw_texture_ex(
+                texture,
+                x,
+                10.,
+                WHITE,
+                DrawTextureParams {
+                    dest_size: Some(vec2(150., 150.)),
+                    ..Default::default()
+                },
+            );
+            x += 160.;
+        }
+
+        next_frame().await
+    }
+}
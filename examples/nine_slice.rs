@@ -0,0 +1,73 @@
+use macroquad::prelude::*;
+
+/// 32x32 panel with a 2px dark frame and rounded corners
+fn panel_image() -> Image {
+    let mut image = Image::gen_image_color(32, 32, Color::new(0.3, 0.5, 0.8, 1.0));
+    for y in 0..32 {
+        for x in 0..32 {
+            let corner_x = (x as i32 - 7).min(0).abs().max((x as i32 - 24).max(0));
+            let corner_y = (y as i32 - 7).min(0).abs().max((y as i32 - 24).max(0));
+            let distance = ((corner_x * corner_x + corner_y * corner_y) as f32).sqrt();
+
+            if distance > 7.5 {
+                image.set_pixel(x, y, BLANK);
+            } else if distance > 5.5 {
+                image.set_pixel(x, y, DARKBLUE);
+            }
+        }
+    }
+    image
+}
+
+#[macroquad::main("Nine slice")]
+async fn main() {
+    let texture = Texture2D::from_image(&panel_image());
+    texture.set_filter(FilterMode::Nearest);
+
+    let margins = RectOffset::new(8., 8., 8., 8.);
+
+    loop {
+        clear_background(LIGHTGRAY);
+
+        draw_text("stretched", 20., 30., 20., BLACK);
+        draw_texture_ex(
+            &texture,
+            20.,
+            40.,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(vec2(300., 80.)),
+                ..Default::default()
+            },
+        );
+
+        draw_text("nine slice", 20., 160., 20., BLACK);
+        draw_texture_nine_slice(
+            &texture,
+            20.,
+            170.,
+            WHITE,
+            margins,
+            DrawTextureParams {
+                dest_size: Some(vec2(300., 80.)),
+                ..Default::default()
+            },
+        );
+
+        // same thing through draw_texture_ex
+        draw_texture_ex(
+            &texture,
+            400.,
+            100.,
+            ORANGE,
+            DrawTextureParams {
+                dest_size: Some(vec2(200., 120.)),
+                rotation: get_time() as f32,
+                slice: Some(margins),
+                ..Default::default()
+            },
+        );
+
+        next_frame().await
+    }
+}
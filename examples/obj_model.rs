@@ -0,0 +1,46 @@
+use macroquad::prelude::*;
+
+#[macroquad::main("OBJ model")]
+async fn main() {
+    let model = match load_model("examples/cube.obj").await {
+        Ok(model) => model,
+        Err(err) => panic!("{err}"),
+    };
+
+    loop {
+        clear_background(LIGHTGRAY);
+
+        set_camera(&Camera3D {
+            position: vec3(-6., 4., 0.),
+            up: vec3(0., 1., 0.),
+            target: vec3(0., 0., 0.),
+            ..Default::default()
+        });
+
+        draw_grid(20, 1., BLACK, GRAY);
+
+        let time = get_time() as f32;
+        let transform = Mat4::from_translation(vec3(0., 1.5, 0.))
+            * Mat4::from_rotation_y(time)
+            * Mat4::from_scale(vec3(2., 2., 2.));
+        draw_model(&model, transform);
+
+        set_default_camera();
+        for (i, (mesh, material)) in model.meshes.iter().enumerate() {
+            draw_text(
+                &format!(
+                    "{}: {} triangles, {:?}",
+                    material.name,
+                    mesh.indices.len() / 3,
+                    material.diffuse
+                ),
+                10.,
+                30. + i as f32 * 25.,
+                24.,
+                BLACK,
+            );
+        }
+
+        next_frame().await
+    }
+}
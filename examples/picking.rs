@@ -0,0 +1,64 @@
+use macroquad::prelude::*;
+
+/// Distance along the ray to the axis aligned box, if it is hit.
+fn ray_box(origin: Vec3, dir: Vec3, center: Vec3, size: Vec3) -> Option<f32> {
+    let (min, max) = (center - size / 2., center + size / 2.);
+    let (t0, t1) = ((min - origin) / dir, (max - origin) / dir);
+    let near = t0.min(t1).max_element();
+    let far = t0.max(t1).min_element();
+
+    (near <= far && far >= 0.).then_some(near.max(0.))
+}
+
+#[macroquad::main("Picking")]
+async fn main() {
+    let cubes = [
+        vec3(-4., 1., 0.),
+        vec3(0., 1., -4.),
+        vec3(0., 1., 4.),
+        vec3(3., 1., 0.),
+    ];
+    let size = vec3(2., 2., 2.);
+
+    loop {
+        clear_background(LIGHTGRAY);
+
+        // the 3d view takes only the right half of the window,
+        // viewports are in physical pixels with the origin at the bottom left
+        let dpi = dpi_scale();
+        let (w, h) = (screen_width() * dpi, screen_height() * dpi);
+        let camera = Camera3D {
+            position: vec3(-12., 10., 6.),
+            up: vec3(0., 1., 0.),
+            target: vec3(0., 0., 0.),
+            viewport: Some(((w / 2.) as i32, 0, (w / 2.) as i32, h as i32)),
+            ..Default::default()
+        };
+
+        let (origin, dir) = camera.screen_to_ray(mouse_position().into());
+        let hovered = cubes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &cube)| ray_box(origin, dir, cube, size).map(|t| (i, t)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i);
+
+        set_camera(&camera);
+        draw_grid(20, 1., BLACK, GRAY);
+        for (i, &cube) in cubes.iter().enumerate() {
+            let color = if hovered == Some(i) { RED } else { DARKBLUE };
+            draw_cube(cube, size, None, color);
+            draw_cube_wires(cube, size, BLACK);
+        }
+
+        set_default_camera();
+        draw_text("hover a cube", 10., 30., 30., BLACK);
+        for &cube in &cubes {
+            if let Some(label) = camera.world_to_screen(cube + vec3(0., 1.5, 0.)) {
+                draw_circle(label.x, label.y, 4., YELLOW);
+            }
+        }
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,122 @@
+use macroquad::prelude::*;
+
+use macroquad::window::miniquad::*;
+
+const MAX_LIGHTS: usize = 8;
+
+const VERTEX: &str = r#"#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec4 color;
+varying highp vec2 pixel;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    pixel = position.xy;
+}"#;
+
+const FRAGMENT: &str = r#"#version 100
+precision highp float;
+
+varying lowp vec4 color;
+varying highp vec2 pixel;
+
+// xy is the position, z the radius; unused lights are zeroed
+uniform vec4 lights[8];
+uniform vec4 light_colors[8];
+// from the light positions to the screen
+uniform mat4 lights_transform;
+uniform vec4 ambient;
+
+void main() {
+    vec3 light = ambient.rgb;
+    for (int i = 0; i < 8; i++) {
+        if (lights[i].z > 0.0) {
+            vec2 position = (lights_transform * vec4(lights[i].xy, 0, 1)).xy;
+            float falloff = 1.0 - clamp(distance(pixel, position) / lights[i].z, 0.0, 1.0);
+            light += light_colors[i].rgb * falloff * falloff;
+        }
+    }
+    gl_FragColor = vec4(color.rgb * light, color.a);
+}"#;
+
+#[macroquad::main("Point lights")]
+async fn main() {
+    let material = load_material(
+        ShaderSource::Glsl {
+            vertex: VERTEX,
+            fragment: FRAGMENT,
+        },
+        MaterialParams {
+            uniforms: vec![
+                UniformDesc::new("lights", UniformType::Float4).array(MAX_LIGHTS),
+                UniformDesc::new("light_colors", UniformType::Float4).array(MAX_LIGHTS),
+                UniformDesc::new("lights_transform", UniformType::Mat4),
+                UniformDesc::new("ambient", UniformType::Float4),
+            ],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let colors = [RED, GREEN, BLUE, YELLOW, MAGENTA, SKYBLUE, ORANGE, WHITE];
+    let mut count = MAX_LIGHTS;
+
+    loop {
+        clear_background(BLACK);
+
+        if is_key_pressed(KeyCode::Up) {
+            count = (count + 1).min(MAX_LIGHTS);
+        }
+        if is_key_pressed(KeyCode::Down) {
+            count = count.saturating_sub(1);
+        }
+
+        let time = get_time() as f32;
+        // the lights go around the origin, the transform puts them around the center
+        let lights: Vec<Vec4> = (0..count)
+            .map(|i| {
+                let angle = i as f32 / MAX_LIGHTS as f32 * std::f32::consts::TAU + time * 0.5;
+                let distance = 150. + (time * 2. + i as f32).sin() * 50.;
+                let position = Vec2::from_angle(angle) * distance;
+                vec4(position.x, position.y, 200., 0.)
+            })
+            .collect();
+        let light_colors: Vec<Vec4> = colors[..count].iter().map(Color::to_vec).collect();
+
+        // the arrays are shorter with less lights, the rest of them is zeroed
+        material.set_uniform_array("lights", &lights[..]);
+        material.set_uniform_array("light_colors", &light_colors[..]);
+        material.set_uniform(
+            "lights_transform",
+            Mat4::from_translation(vec3(screen_width() / 2., screen_height() / 2., 0.)),
+        );
+        material.set_uniform("ambient", vec4(0.1, 0.1, 0.15, 1.));
+
+        gl_use_material(&material);
+        draw_rectangle(0., 0., screen_width(), screen_height(), GRAY);
+        for x in 0..8 {
+            for y in 0..6 {
+                let (x, y) = (x as f32 * 100. + 30., y as f32 * 100. + 30.);
+                draw_rectangle(x, y, 40., 40., WHITE);
+            }
+        }
+        gl_use_default_material();
+
+        draw_text(
+            &format!("{count} lights, up and down to change"),
+            20.,
+            30.,
+            30.,
+            WHITE,
+        );
+
+        next_frame().await
+    }
+}
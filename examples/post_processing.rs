@@ -2,10 +2,7 @@ use macroquad::prelude::*;
 
 #[macroquad::main("Post processing")]
 async fn main() {
-    let render_target = render_target(320, 150);
-    render_target.texture.set_filter(FilterMode::Nearest);
-
-    let material = load_material(
+    let crt = load_material(
         ShaderSource::Glsl {
             vertex: CRT_VERTEX_SHADER,
             fragment: CRT_FRAGMENT_SHADER,
@@ -13,45 +10,55 @@ async fn main() {
         Default::default(),
     )
     .unwrap();
+    let pixelate = load_material(
+        ShaderSource::Glsl {
+            vertex: CRT_VERTEX_SHADER,
+            fragment: PIXELATE_FRAGMENT_SHADER,
+        },
+        MaterialParams {
+            uniforms: vec![UniformDesc::new("Resolution", UniformType::Float2)],
+            ..Default::default()
+        },
+    )
+    .unwrap();
 
-    loop {
-        // drawing to the texture
+    // the scene is pixelated first, then goes through the crt, resizing the window keeps both right
+    let mut crt = PostProcessing::new(crt);
+    let mut pixelate = PostProcessing::new(pixelate);
 
-        // 0..100, 0..100 camera
-        set_camera(&Camera2D {
-            zoom: vec2(0.01, 0.01),
-            target: vec2(0.0, 0.0),
-            render_target: Some(render_target.clone()),
-            ..Default::default()
-        });
+    loop {
+        crt.begin();
+        pixelate.begin();
 
         clear_background(LIGHTGRAY);
-        draw_line(-30.0, 45.0, 30.0, 45.0, 3.0, BLUE);
-        draw_circle(-45.0, -35.0, 20.0, YELLOW);
-        draw_circle(45.0, -35.0, 20.0, GREEN);
-
-        // drawing to the screen
-
-        set_default_camera();
-
-        clear_background(WHITE);
-        gl_use_material(&material);
-        draw_texture_ex(
-            &render_target.texture,
-            0.,
-            0.,
-            WHITE,
-            DrawTextureParams {
-                dest_size: Some(vec2(screen_width(), screen_height())),
-                ..Default::default()
-            },
-        );
-        gl_use_default_material();
+        let (w, h) = (screen_width(), screen_height());
+        draw_line(w * 0.35, h * 0.75, w * 0.65, h * 0.75, 10.0, BLUE);
+        draw_circle(w * 0.3, h * 0.3, h * 0.1, YELLOW);
+        draw_circle(w * 0.7, h * 0.3, h * 0.1, GREEN);
+        draw_text("top left", 20., 40., 40., DARKGRAY);
+
+        pixelate.end_and_draw();
+        crt.end_and_draw();
 
         next_frame().await;
     }
 }
 
+const PIXELATE_FRAGMENT_SHADER: &str = r#"#version 100
+precision lowp float;
+
+varying vec4 color;
+varying vec2 uv;
+
+uniform sampler2D Texture;
+uniform vec2 Resolution;
+
+void main() {
+    vec2 pixels = Resolution / 4.0;
+    gl_FragColor = texture2D(Texture, (floor(uv * pixels) + 0.5) / pixels) * color;
+}
+"#;
+
 const CRT_FRAGMENT_SHADER: &'static str = r#"#version 100
 precision lowp float;
 
@@ -0,0 +1,55 @@
+use macroquad::prelude::*;
+
+#[macroquad::main("Render target with depth")]
+async fn main() {
+    // without `depth: true` the cubes inside of the target would overlap in draw order
+    let target = render_target_ex(
+        512,
+        512,
+        RenderTargetParams {
+            depth: true,
+            sample_count: 4,
+            ..Default::default()
+        },
+    );
+
+    loop {
+        let t = get_time() as f32;
+
+        // "CCTV" view into the off-screen target
+        set_camera(&Camera3D {
+            position: vec3(t.cos() * 8., 5., t.sin() * 8.),
+            target: vec3(0., 0., 0.),
+            up: vec3(0., 1., 0.),
+            render_target: Some(target.clone()),
+            ..Default::default()
+        });
+        clear_background(DARKGRAY);
+        draw_grid(10, 1., WHITE, GRAY);
+        draw_cube(vec3(0., 1., 0.), vec3(2., 2., 2.), None, RED);
+        draw_cube(vec3(1.5, 0.5, 1.5), vec3(1., 1., 1.), None, BLUE);
+        draw_cube(vec3(-1.5, 0.75, -1.), vec3(1.5, 1.5, 1.5), None, GREEN);
+
+        // main scene, the target is shown on a plane
+        set_camera(&Camera3D {
+            position: vec3(-12., 8., 0.),
+            target: vec3(0., 0., 0.),
+            up: vec3(0., 1., 0.),
+            ..Default::default()
+        });
+        clear_background(LIGHTGRAY);
+        draw_grid(20, 1., BLACK, GRAY);
+        draw_plane(
+            vec3(0., 0.01, 0.),
+            vec2(5., 5.),
+            Some(&target.texture),
+            WHITE,
+        );
+        draw_cube(vec3(0., 1., 6.), vec3(2., 2., 2.), None, YELLOW);
+
+        set_default_camera();
+        draw_text("render target with a depth buffer", 10., 20., 30., BLACK);
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,109 @@
+//! A turret, child of a tank: it turns with the tank and aims at the mouse on top of that.
+
+use macroquad::experimental::scene::{self, Handle, Node, RefMut, Transform};
+use macroquad::prelude::*;
+
+struct Tank {
+    speed: f32,
+}
+
+impl Node for Tank {
+    fn update(node: RefMut<Self>) {
+        let handle = node.handle();
+        let mut transform = scene::transform(handle);
+
+        if is_key_down(KeyCode::Left) {
+            transform.rotation -= 2. * get_frame_time();
+        }
+        if is_key_down(KeyCode::Right) {
+            transform.rotation += 2. * get_frame_time();
+        }
+        let forward = Vec2::from_angle(transform.rotation);
+        if is_key_down(KeyCode::Up) {
+            transform.position += forward * node.speed * get_frame_time();
+        }
+        if is_key_down(KeyCode::Down) {
+            transform.position -= forward * node.speed * get_frame_time();
+        }
+
+        scene::set_transform(handle, transform);
+    }
+
+    fn draw(node: RefMut<Self>) {
+        let transform = scene::world_transform(node.handle());
+        let corners = [
+            vec2(-30., -20.),
+            vec2(30., -20.),
+            vec2(30., 20.),
+            vec2(-30., 20.),
+        ]
+        .map(|corner| transform.transform_point(corner));
+
+        draw_triangle(corners[0], corners[1], corners[2], DARKGREEN);
+        draw_triangle(corners[0], corners[2], corners[3], DARKGREEN);
+    }
+}
+
+struct Turret {
+    tank: Handle<Tank>,
+}
+
+impl Node for Turret {
+    fn update(node: RefMut<Self>) {
+        // the tank was updated first, its transform is the one of this frame
+        let tank = scene::world_transform(node.tank);
+        let to_mouse = Vec2::from(mouse_position()) - tank.position;
+
+        scene::set_transform(
+            node.handle(),
+            Transform {
+                rotation: to_mouse.y.atan2(to_mouse.x) - tank.rotation,
+                ..Transform::IDENTITY
+            },
+        );
+    }
+
+    fn draw(node: RefMut<Self>) {
+        let transform = scene::world_transform(node.handle());
+        let muzzle = transform.transform_point(vec2(40., 0.));
+
+        draw_line(
+            transform.position.x,
+            transform.position.y,
+            muzzle.x,
+            muzzle.y,
+            6.,
+            GREEN,
+        );
+        draw_circle(transform.position.x, transform.position.y, 12., GREEN);
+    }
+}
+
+#[macroquad::main("Scene hierarchy")]
+async fn main() {
+    // added before its parent, still updated after it
+    let turret = scene::add_node(Turret {
+        tank: Handle::null(),
+    });
+    let tank = scene::add_node(Tank { speed: 150. });
+    scene::get_node(turret).tank = tank;
+
+    scene::set_parent(turret, tank);
+    scene::set_transform(
+        tank,
+        Transform::from_position(vec2(screen_width() / 2., screen_height() / 2.)),
+    );
+
+    loop {
+        clear_background(LIGHTGRAY);
+        draw_text(
+            "arrows to drive, the turret aims at the mouse",
+            10.,
+            20.,
+            20.,
+            BLACK,
+        );
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,131 @@
+//! Bullets hit anything tagged "enemy", whatever the type of the node is.
+
+use macroquad::experimental::scene::{self, Node, RefMut};
+use macroquad::prelude::*;
+
+struct Walker {
+    rect: Rect,
+    speed: f32,
+}
+
+impl Node for Walker {
+    fn update(mut node: RefMut<Self>) {
+        node.rect.x += node.speed * get_frame_time();
+        if node.rect.x < 0. || node.rect.right() > screen_width() {
+            node.speed = -node.speed;
+        }
+    }
+
+    fn draw(node: RefMut<Self>) {
+        let Rect { x, y, w, h } = node.rect;
+        draw_rectangle(x, y, w, h, RED);
+    }
+}
+
+struct Crate {
+    rect: Rect,
+    hits: u32,
+}
+
+impl Node for Crate {
+    fn draw(node: RefMut<Self>) {
+        let Rect { x, y, w, h } = node.rect;
+        draw_rectangle(x, y, w, h, BROWN);
+        draw_text(&node.hits.to_string(), x + 4., y + 18., 20., WHITE);
+    }
+}
+
+struct Bullet {
+    pos: Vec2,
+}
+
+impl Node for Bullet {
+    fn update(mut node: RefMut<Self>) {
+        node.pos.y -= 400. * get_frame_time();
+        if node.pos.y < 0. {
+            node.delete();
+            return;
+        }
+
+        let pos = node.pos;
+        let hit = scene::find_nodes_by_tag("enemy").find(|enemy| {
+            let rect = if let Some(walker) = enemy.downcast_ref::<Walker>() {
+                walker.rect
+            } else if let Some(crate_) = enemy.downcast_ref::<Crate>() {
+                crate_.rect
+            } else {
+                return false;
+            };
+            rect.contains(pos)
+        });
+
+        if let Some(mut enemy) = hit {
+            // crates take a few hits
+            let survived = enemy.downcast_mut::<Crate>().is_some_and(|crate_| {
+                crate_.hits -= 1;
+                crate_.hits > 0
+            });
+            if !survived {
+                enemy.delete();
+            }
+            node.delete();
+        }
+    }
+
+    fn draw(node: RefMut<Self>) {
+        draw_circle(node.pos.x, node.pos.y, 3., YELLOW);
+    }
+}
+
+struct Player {
+    x: f32,
+}
+
+impl Node for Player {
+    fn update(mut node: RefMut<Self>) {
+        if is_key_down(KeyCode::Left) {
+            node.x -= 300. * get_frame_time();
+        }
+        if is_key_down(KeyCode::Right) {
+            node.x += 300. * get_frame_time();
+        }
+        if is_key_pressed(KeyCode::Space) {
+            scene::add_node(Bullet {
+                pos: vec2(node.x, screen_height() - 40.),
+            });
+        }
+    }
+
+    fn draw(node: RefMut<Self>) {
+        draw_rectangle(node.x - 15., screen_height() - 30., 30., 20., GREEN);
+    }
+}
+
+#[macroquad::main("Scene tags")]
+async fn main() {
+    scene::add_node(Player {
+        x: screen_width() / 2.,
+    });
+
+    for n in 0..3 {
+        let walker = scene::add_node(Walker {
+            rect: Rect::new(100. + n as f32 * 150., 80. + n as f32 * 60., 40., 30.),
+            speed: 100. + n as f32 * 50.,
+        });
+        scene::add_tag(walker, "enemy");
+    }
+    for n in 0..4 {
+        let crate_ = scene::add_node(Crate {
+            rect: Rect::new(80. + n as f32 * 120., 300., 30., 30.),
+            hits: 3,
+        });
+        scene::add_tag(crate_, "enemy");
+    }
+
+    loop {
+        clear_background(DARKGRAY);
+        draw_text("left/right to move, space to shoot", 10., 20., 20., WHITE);
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,50 @@
+use macroquad::prelude::*;
+
+#[macroquad::main("Scissor")]
+async fn main() {
+    let mut scroll = 0.;
+
+    loop {
+        clear_background(LIGHTGRAY);
+
+        scroll += mouse_wheel().1 * 10. + get_frame_time() * 20.;
+        let dpi = dpi_scale();
+
+        let (x, y, w, h) = (50., 50., 300., 200.);
+        draw_rectangle(x, y, w, h, DARKGRAY);
+
+        // scissor works in physical pixels
+        push_scissor(
+            (x * dpi) as i32,
+            (y * dpi) as i32,
+            (w * dpi) as i32,
+            (h * dpi) as i32,
+        );
+        for i in 0..50 {
+            let item_y = y + i as f32 * 30. - scroll % (50. * 30.);
+            draw_rectangle(
+                x + 10.,
+                item_y,
+                w - 20.,
+                26.,
+                if i % 2 == 0 { BEIGE } else { BROWN },
+            );
+            draw_text(&format!("item {i}"), x + 20., item_y + 20., 24., BLACK);
+
+            // nested scissor is intersected with the panel
+            push_scissor(
+                ((x + w - 60.) * dpi) as i32,
+                ((item_y + 4.) * dpi) as i32,
+                (100. * dpi) as i32,
+                (18. * dpi) as i32,
+            );
+            draw_circle(x + w - 40., item_y + 13., 20., RED);
+            pop_scissor();
+        }
+        pop_scissor();
+
+        draw_text("drawn without clipping", x, y + h + 30., 30., BLACK);
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,12 @@
+#version 100
+precision lowp float;
+
+varying vec2 uv;
+
+uniform vec4 _Time;
+
+// edit and save this file while the shader_reload example is running
+void main() {
+    vec3 color = 0.5 + 0.5 * cos(_Time.x + uv.xyx * 3.0 + vec3(0.0, 2.0, 4.0));
+    gl_FragColor = vec4(color, 1.0);
+}
@@ -0,0 +1,42 @@
+//! Edit examples/shader_reload.frag while this is running, the shader gets recompiled on save.
+//! Compilation errors are shown on the screen and the last working shader keeps drawing.
+//! `cargo run --example shader_reload --features shader-watch`
+
+use macroquad::prelude::*;
+
+const VERTEX_PATH: &str = "examples/shader_reload.vert";
+const FRAGMENT_PATH: &str = "examples/shader_reload.frag";
+
+#[macroquad::main("Shader reload")]
+async fn main() {
+    let material = load_material_from_files(VERTEX_PATH, FRAGMENT_PATH, Default::default())
+        .await
+        .unwrap();
+    material.watch().unwrap();
+
+    loop {
+        // saving the files is enough, R reloads by hand
+        if is_key_pressed(KeyCode::R) {
+            if let Err(err) = material.reload().await {
+                warn!("{}", err);
+            }
+        }
+
+        clear_background(BLACK);
+
+        gl_use_material(&material);
+        draw_rectangle(0., 0., screen_width(), screen_height(), WHITE);
+        gl_use_default_material();
+
+        match material.last_error() {
+            Some(error) => {
+                for (i, line) in error.lines().enumerate() {
+                    draw_text(line, 20., 40. + i as f32 * 20., 20., RED);
+                }
+            }
+            None => draw_text("edit examples/shader_reload.frag", 20., 40., 20., WHITE),
+        }
+
+        next_frame().await
+    }
+}
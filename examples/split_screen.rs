@@ -0,0 +1,58 @@
+use macroquad::prelude::*;
+
+fn draw_world(players: &[Vec2; 2]) {
+    for x in -20..20 {
+        for y in -20..20 {
+            let color = if (x + y) % 2 == 0 { DARKGREEN } else { GREEN };
+            draw_rectangle(x as f32 * 50., y as f32 * 50., 50., 50., color);
+        }
+    }
+    draw_circle(players[0].x, players[0].y, 20., RED);
+    draw_circle(players[1].x, players[1].y, 20., BLUE);
+}
+
+#[macroquad::main("Split screen")]
+async fn main() {
+    let mut players = [vec2(-100., 0.), vec2(100., 0.)];
+    let controls = [
+        [KeyCode::A, KeyCode::D, KeyCode::W, KeyCode::S],
+        [KeyCode::Left, KeyCode::Right, KeyCode::Up, KeyCode::Down],
+    ];
+
+    loop {
+        for (player, [left, right, up, down]) in players.iter_mut().zip(controls) {
+            let dir = vec2(
+                is_key_down(right) as i32 as f32 - is_key_down(left) as i32 as f32,
+                is_key_down(down) as i32 as f32 - is_key_down(up) as i32 as f32,
+            );
+            *player += dir.normalize_or_zero() * 300. * get_frame_time();
+        }
+
+        clear_background(BLACK);
+
+        // viewports are in physical pixels, the left half and the right half of the window
+        let dpi = dpi_scale();
+        let (w, h) = (screen_width() * dpi, screen_height() * dpi);
+        let half = (w / 2.) as i32;
+
+        for (i, player) in players.iter().enumerate() {
+            set_camera(&Camera2D {
+                target: *player,
+                // the view is half as wide as the window
+                zoom: vec2(2. / (screen_width() / 2.), 2. / screen_height()),
+                viewport: Some((half * i as i32, 0, half, h as i32)),
+                ..Default::default()
+            });
+            // anything outside of the viewport is clipped away
+            draw_world(&players);
+        }
+
+        set_default_camera();
+        let middle = screen_width() / 2.;
+        draw_line(middle, 0., middle, screen_height(), 4., WHITE);
+        draw_text("WASD", 10., 30., 30., WHITE);
+        draw_text("arrows", middle + 10., 30., 30., WHITE);
+
+        next_frame().await
+    }
+}
@@ -0,0 +1,58 @@
+use macroquad::prelude::*;
+
+static DIALOG: &str = "Welcome, traveler! The road ahead is long and the nights are cold.
+Take this lantern, and mind the wolves past the old bridge.";
+
+#[macroquad::main("Text box")]
+async fn main() {
+    let font_size = 24;
+    let mut align = TextAlign::Left;
+
+    loop {
+        if is_key_pressed(KeyCode::Space) {
+            align = match align {
+                TextAlign::Left => TextAlign::Center,
+                TextAlign::Center => TextAlign::Right,
+                TextAlign::Right => TextAlign::Left,
+            };
+        }
+
+        clear_background(DARKGRAY);
+
+        let box_rect = Rect::new(50., screen_height() - 200., screen_width() - 100., 150.);
+        draw_rectangle(box_rect.x, box_rect.y, box_rect.w, box_rect.h, BLACK);
+        draw_rectangle_lines(box_rect.x, box_rect.y, box_rect.w, box_rect.h, 2., WHITE);
+
+        // center the text vertically in the box
+        let inner_width = box_rect.w - 40.;
+        let wrapped = wrap_text(DIALOG, None, font_size, 1.0, inner_width);
+        let height = measure_multiline_text(&wrapped, None, font_size, 1.0, None).height;
+        let text_rect = Rect::new(
+            box_rect.x + 20.,
+            box_rect.y + (box_rect.h - height) / 2.,
+            inner_width,
+            height,
+        );
+
+        draw_text_wrapped(
+            DIALOG,
+            text_rect,
+            align,
+            TextParams {
+                font_size,
+                color: WHITE,
+                ..Default::default()
+            },
+        );
+
+        draw_text(
+            &format!("{align:?} aligned, space to change"),
+            20.,
+            30.,
+            30.,
+            WHITE,
+        );
+
+        next_frame().await
+    }
+}
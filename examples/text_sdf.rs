@@ -0,0 +1,91 @@
+use macroquad::prelude::*;
+
+#[macroquad::main("Text SDF")]
+async fn main() {
+    let bitmap = load_ttf_font("./examples/DancingScriptRegular.ttf")
+        .await
+        .unwrap();
+    let sdf = load_ttf_font_from_bytes_ex(
+        &load_file("./examples/DancingScriptRegular.ttf")
+            .await
+            .unwrap(),
+        FontParams {
+            sdf: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let mut zoom = 1.0f32;
+
+    loop {
+        let (_, wheel) = mouse_wheel();
+        if wheel != 0. {
+            zoom = (zoom * if wheel > 0. { 1.1 } else { 1. / 1.1 }).clamp(0.5, 20.);
+        }
+
+        clear_background(DARKBLUE);
+
+        // both fonts are rasterized at 30px and scaled up by the camera
+        set_camera(&Camera2D {
+            zoom: vec2(2. / screen_width(), 2. / screen_height()) * zoom,
+            target: vec2(130., 20.),
+            ..Default::default()
+        });
+        draw_text_ex(
+            "Bitmap text",
+            0.,
+            0.,
+            TextParams {
+                font: Some(&bitmap),
+                font_size: 30,
+                ..Default::default()
+            },
+        );
+        draw_text_ex(
+            "SDF text",
+            0.,
+            40.,
+            TextParams {
+                font: Some(&sdf),
+                font_size: 30,
+                ..Default::default()
+            },
+        );
+        draw_text_ex(
+            "SDF outline",
+            150.,
+            40.,
+            TextParams {
+                font: Some(&sdf),
+                font_size: 30,
+                color: YELLOW,
+                outline: 0.2,
+                outline_color: BLACK,
+                ..Default::default()
+            },
+        );
+        draw_text_ex(
+            "SDF soft",
+            150.,
+            0.,
+            TextParams {
+                font: Some(&sdf),
+                font_size: 30,
+                softness: 0.1,
+                ..Default::default()
+            },
+        );
+
+        set_default_camera();
+        draw_text(
+            &format!("zoom {zoom:.1}, mouse wheel to change"),
+            20.,
+            30.,
+            30.,
+            WHITE,
+        );
+
+        next_frame().await
+    }
+}
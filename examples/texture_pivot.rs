@@ -0,0 +1,50 @@
+use macroquad::prelude::*;
+
+#[macroquad::main("Texture pivot")]
+async fn main() {
+    let texture: Texture2D = load_texture("examples/ferris.png").await.unwrap();
+
+    loop {
+        clear_background(LIGHTGRAY);
+
+        let rotation = get_time() as f32;
+        let center = vec2(screen_width() / 2., screen_height() / 2.);
+        let size = vec2(120., 80.);
+
+        // orbit around the center of the screen: the sprite is placed to the right
+        // of the pivot, so rotating around the pivot moves it along a circle
+        draw_circle(center.x, center.y, 4., RED);
+        draw_texture_ex(
+            &texture,
+            center.x + 100.,
+            center.y - size.y / 2.,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(size),
+                rotation,
+                pivot: Some(center),
+                ..Default::default()
+            },
+        );
+
+        // flipped and cropped sprite swinging around its bottom center
+        let base = vec2(150., screen_height() - 50.);
+        draw_circle(base.x, base.y, 4., RED);
+        draw_texture_ex(
+            &texture,
+            base.x - size.x / 2.,
+            base.y - size.y,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(size),
+                source: Some(Rect::new(0., 0., texture.width() / 2., texture.height())),
+                rotation: rotation.sin() * 0.5,
+                pivot: Some(base),
+                flip_x: true,
+                ..Default::default()
+            },
+        );
+
+        next_frame().await
+    }
+}
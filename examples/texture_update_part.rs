@@ -0,0 +1,70 @@
+use macroquad::prelude::*;
+
+const SIZE: u16 = 2048;
+const BRUSH: u16 = 64;
+
+#[macroquad::main("Texture update_part")]
+async fn main() {
+    let mut canvas = Image::gen_image_color(SIZE, SIZE, DARKGRAY);
+    let texture = Texture2D::from_image(&canvas);
+    let mut brush = Image::gen_image_color(BRUSH, BRUSH, WHITE);
+    let mut full_update = false;
+
+    loop {
+        if is_key_pressed(KeyCode::Space) {
+            full_update = !full_update;
+        }
+
+        let t = get_time() as f32;
+        let color = Color::new(t.sin() * 0.5 + 0.5, t.cos() * 0.5 + 0.5, 0.5, 1.);
+        for pixel in brush.get_image_data_mut() {
+            *pixel = color.into();
+        }
+
+        // move the brush over the whole texture, one 64x64 window per frame
+        let x = ((t * 300.) as u32 % (SIZE - BRUSH) as u32) as i32;
+        let y = (((t * 0.37).sin() * 0.5 + 0.5) * (SIZE - BRUSH) as f32) as i32;
+
+        if full_update {
+            let row = BRUSH as usize * 4;
+            for by in 0..BRUSH as usize {
+                let start = ((y as usize + by) * SIZE as usize + x as usize) * 4;
+                canvas.bytes[start..start + row]
+                    .copy_from_slice(&brush.bytes[by * row..(by + 1) * row]);
+            }
+            texture.update(&canvas);
+        } else {
+            texture.update_part(&brush, x, y, BRUSH as i32, BRUSH as i32);
+        }
+
+        clear_background(BLACK);
+        draw_texture_ex(
+            &texture,
+            0.,
+            0.,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(vec2(screen_width(), screen_height())),
+                ..Default::default()
+            },
+        );
+
+        let mode = if full_update {
+            "full update"
+        } else {
+            "update_part"
+        };
+        draw_text(
+            &format!(
+                "{mode}: {:.2}ms (space to switch)",
+                get_frame_time() * 1000.
+            ),
+            20.,
+            30.,
+            30.,
+            WHITE,
+        );
+
+        next_frame().await
+    }
+}
@@ -4,7 +4,7 @@ use macroquad::ui::widgets::{ProgressBar, Slider};
 use macroquad::ui::{
     hash, root_ui,
     widgets::{self, Group},
-    Drag, Ui,
+    Ui,
 };
 
 pub struct Slot {
@@ -28,7 +28,6 @@ pub enum FittingCommand {
 
 pub struct Data {
     inventory: Vec<String>,
-    item_dragging: bool,
     slots: Vec<(&'static str, Slot)>,
     fit_command: Option<FittingCommand>,
 }
@@ -36,7 +35,6 @@ impl Data {
     pub fn new() -> Data {
         Data {
             inventory: vec![],
-            item_dragging: false,
             slots: vec![
                 ("Left Mouse Button", Slot::new(hash!())),
                 ("Right Mouse Button", Slot::new(hash!())),
@@ -51,46 +49,49 @@ impl Data {
     }
 
     fn slots(&mut self, ui: &mut Ui) {
-        let item_dragging = &mut self.item_dragging;
+        let slot_ids: Vec<u64> = self.slots.iter().map(|(_, slot)| slot.id).collect();
 
-        let fit_command = &mut self.fit_command;
-        for (label, slot) in self.slots.iter_mut() {
+        for (label, slot) in self.slots.iter() {
             Group::new(hash!("grp", slot.id, &label), Vec2::new(210., 55.)).ui(ui, |ui| {
-                let drag = Group::new(slot.id, Vec2::new(50., 50.))
+                // highlighted with other color when some item is dragging
+                let highlight = ui.drag_payload().is_some();
+                let item = |ui: &mut Ui| {
+                    Group::new(slot.id, Vec2::new(50., 50.))
+                        .highlight(highlight)
+                        .ui(ui, |ui| {
+                            if let Some(ref item) = slot.item {
+                                ui.label(Vec2::new(5., 10.), item);
+                            }
+                        });
+                };
+
+                let dropped = ui.drop_target(hash!("slot target", slot.id), |ui| {
                     // slot without item is not draggable
-                    .draggable(slot.item.is_some())
-                    // but could be a target of drag
-                    .hoverable(*item_dragging)
-                    // and is highlighted with other color when some item is dragging
-                    .highlight(*item_dragging)
-                    .ui(ui, |ui| {
-                        if let Some(ref item) = slot.item {
-                            ui.label(Vec2::new(5., 10.), &item);
-                        }
-                    });
+                    if slot.item.is_some() {
+                        ui.drag_source(hash!("slot item", slot.id), slot.id, item);
+                    } else {
+                        item(ui);
+                    }
+                });
 
-                match drag {
-                    // there is some item in this slot and it was dragged to another slot
-                    Drag::Dropped(_, Some(id)) if slot.item.is_some() => {
-                        *fit_command = Some(FittingCommand::Refit {
-                            target_slot: id,
-                            origin_slot: slot.id,
+                match dropped {
+                    // dropped back where it was taken from
+                    Some(origin_slot) if origin_slot == slot.id => {}
+                    // an item was dragged here from another slot
+                    Some(origin_slot) if slot_ids.contains(&origin_slot) => {
+                        self.fit_command = Some(FittingCommand::Refit {
+                            target_slot: slot.id,
+                            origin_slot,
                         });
                     }
-                    // there is some item in this slot and it was dragged out - unfit it
-                    Drag::Dropped(_, None) if slot.item.is_some() => {
-                        *fit_command = Some(FittingCommand::Unfit {
+                    // an item was dragged here from the inventory
+                    Some(n) => {
+                        self.fit_command = Some(FittingCommand::Fit {
                             target_slot: slot.id,
+                            item: self.inventory[n as usize].clone(),
                         });
                     }
-                    // there is no item in this slot
-                    // this is impossible - slots without items are non-draggable
-                    Drag::Dropped(_, _) => unreachable!(),
-                    Drag::Dragging(pos, id) => {
-                        debug!("slots: pos: {:?}, id {:?}", pos, id);
-                        *item_dragging = true;
-                    }
-                    Drag::No => {}
+                    None => {}
                 }
                 ui.label(Vec2::new(60., 20.), label);
             });
@@ -98,34 +99,31 @@ impl Data {
     }
 
     fn inventory(&mut self, ui: &mut Ui) {
-        let item_dragging = &mut self.item_dragging;
-        for (n, item) in self.inventory.iter().enumerate() {
-            let drag = Group::new(hash!("inventory", n), Vec2::new(50., 50.))
-                .draggable(true)
-                .ui(ui, |ui| {
-                    ui.label(Vec2::new(5., 10.), &item);
-                });
-
-            match drag {
-                Drag::Dropped(_, Some(id)) => {
-                    self.fit_command = Some(FittingCommand::Fit {
-                        target_slot: id,
-                        item: item.clone(),
+        let dropped = ui.drop_target(hash!("inventory target"), |ui| {
+            Group::new(hash!(), Vec2::new(280., 400.)).ui(ui, |ui| {
+                for (n, item) in self.inventory.iter().enumerate() {
+                    ui.drag_source(hash!("inventory item", n), n as u64, |ui| {
+                        Group::new(hash!("inventory", n), Vec2::new(50., 50.)).ui(ui, |ui| {
+                            ui.label(Vec2::new(5., 10.), item);
+                        });
                     });
-                    *item_dragging = false;
-                }
-                Drag::Dropped(_, _) => {
-                    *item_dragging = false;
                 }
-                Drag::Dragging(pos, id) => {
-                    debug!("inventory: pos: {:?}, id {:?}", pos, id);
-                    *item_dragging = true;
-                }
-                _ => {}
-            }
+            });
+        });
+
+        // an item was dragged out of a slot back to the inventory - unfit it
+        if let Some(target_slot) = dropped.filter(|id| self.slot_item(*id).is_some()) {
+            self.fit_command = Some(FittingCommand::Unfit { target_slot });
         }
     }
 
+    fn slot_item(&self, id: u64) -> Option<String> {
+        self.slots
+            .iter()
+            .find(|(_, slot)| slot.id == id)
+            .and_then(|(_, slot)| slot.item.clone())
+    }
+
     fn set_item(&mut self, id: u64, item: Option<String>) {
         if let Some(slot) = self.slots.iter_mut().find(|(_, slot)| slot.id == id) {
             slot.1.item = item;
@@ -147,6 +145,9 @@ async fn main() {
     let mut number1 = 0.;
     let mut number2 = 0.;
 
+    let mut settings_tab = 0;
+    let mut picked_color = SKYBLUE;
+
     let texture: Texture2D = load_texture("examples/ferris.png").await.unwrap();
 
     loop {
@@ -164,6 +165,7 @@ async fn main() {
                         if ui.button(Vec2::new(260., 55.), "buy") {
                             data.inventory.push(format!("Item {i}"));
                         }
+                        ui.tooltip(&format!("Adds item N {i} to the inventory"));
                     });
                 }
             });
@@ -175,9 +177,7 @@ async fn main() {
                 Group::new(hash!(), Vec2::new(230., 400.)).ui(ui, |ui| {
                     data.slots(ui);
                 });
-                Group::new(hash!(), Vec2::new(280., 400.)).ui(ui, |ui| {
-                    data.inventory(ui);
-                });
+                data.inventory(ui);
             });
 
         widgets::Window::new(hash!(), vec2(470., 50.), vec2(300., 300.))
@@ -188,6 +188,7 @@ async fn main() {
                     if ui.button(None, "click me") {
                         println!("hi");
                     }
+                    ui.tooltip("Prints \"hi\"\nto the console");
 
                     ui.separator();
 
@@ -215,6 +216,40 @@ async fn main() {
                     widgets::Button::new(texture.clone())
                         .size(vec2(120., 70.))
                         .ui(ui);
+
+                    // quarters of the texture, like icons from an atlas
+                    let (w, h) = (texture.width() / 2., texture.height() / 2.);
+                    for (i, (x, y)) in [(0., 0.), (w, 0.), (0., h), (w, h)].into_iter().enumerate()
+                    {
+                        if i % 2 == 1 {
+                            ui.same_line(0.);
+                        }
+                        let source = Some(Rect::new(x, y, w, h));
+                        if ui.texture_button(
+                            hash!("quarter", i),
+                            texture.clone(),
+                            vec2(60., 35.),
+                            source,
+                        ) {
+                            println!("quarter {i}");
+                        }
+                    }
+                });
+                ui.tree_node(hash!(), "tabs", |ui| {
+                    widgets::Tabs::new(
+                        hash!(),
+                        vec2(260., 120.),
+                        &["Video", "Audio", "Controls", "Online"],
+                    )
+                    .disabled(&[3])
+                    .ui(ui, &mut settings_tab, |ui, tab| {
+                        for i in 0..10 {
+                            ui.label(None, &format!("Tab {tab} setting {i}"));
+                        }
+                    });
+                });
+                ui.tree_node(hash!(), "color picker", |ui| {
+                    ui.color_picker(hash!(), &mut picked_color);
                 });
                 ui.tree_node(hash!(), "sliders and bars", |ui| {
                     let range0 = -10f32..10f32;
@@ -265,17 +300,7 @@ async fn main() {
                 target_slot,
                 origin_slot,
             }) => {
-                let origin_item = data
-                    .slots
-                    .iter()
-                    .find_map(|(_, slot)| {
-                        if slot.id == origin_slot {
-                            Some(slot.item.clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .flatten();
+                let origin_item = data.slot_item(origin_slot);
                 data.set_item(target_slot, origin_item);
                 data.set_item(origin_slot, None);
             }
@@ -0,0 +1,43 @@
+use macroquad::prelude::*;
+
+use macroquad::ui::{hash, root_ui, widgets};
+
+const ROWS: usize = 100_000;
+
+#[macroquad::main("UI list")]
+async fn main() {
+    let mut selected = None;
+    let mut scroll_to = None;
+
+    loop {
+        clear_background(WHITE);
+
+        root_ui().window(hash!(), vec2(20., 20.), vec2(400., 500.), |ui| {
+            ui.label(None, &format!("{ROWS} rows, {} fps", get_fps()));
+            if ui.button(None, "Scroll to the middle") {
+                scroll_to = Some(ROWS / 2);
+            }
+            ui.same_line(0.);
+            if ui.button(None, "Scroll to the end") {
+                scroll_to = Some(ROWS - 1);
+            }
+
+            // only the ~20 visible rows are laid out and drawn each frame
+            let mut list = widgets::List::new(hash!(), vec2(390., 440.)).row_height(22.);
+            if let Some(row) = scroll_to.take() {
+                list = list.scroll_to(row);
+            }
+            list.ui(ui, ROWS, |ui, row| {
+                let label = format!("Row {row}");
+                if widgets::Button::new(label.as_str())
+                    .selected(selected == Some(row))
+                    .ui(ui)
+                {
+                    selected = Some(row);
+                }
+            });
+        });
+
+        next_frame().await;
+    }
+}
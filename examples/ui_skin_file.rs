@@ -0,0 +1,40 @@
+//! The first skin of the `ui_skins` example, loaded from `ui_assets/skin1.json`.
+//!
+//! Run with `cargo run --example ui_skin_file --features serde`.
+
+use macroquad::prelude::*;
+
+use macroquad::ui::{hash, root_ui, widgets, Skin};
+
+#[macroquad::main("UI skin file")]
+async fn main() {
+    let skin = Skin::from_file("examples/ui_assets/skin1.json")
+        .await
+        .unwrap();
+
+    let mut text = String::new();
+
+    loop {
+        clear_background(GRAY);
+
+        root_ui().push_skin(&skin);
+        root_ui().window(hash!(), vec2(20., 20.), vec2(300., 340.), |ui| {
+            ui.label(None, "Main menu");
+            widgets::Button::new("Play")
+                .position(vec2(65.0, 55.0))
+                .ui(ui);
+            widgets::Button::new("Options")
+                .position(vec2(40.0, 115.0))
+                .ui(ui);
+            widgets::Editbox::new(hash!(), vec2(240., 60.))
+                .position(vec2(10.0, 175.0))
+                .ui(ui, &mut text);
+            widgets::Button::new("Quit")
+                .position(vec2(65.0, 245.0))
+                .ui(ui);
+        });
+        root_ui().pop_skin();
+
+        next_frame().await;
+    }
+}
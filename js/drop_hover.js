@@ -0,0 +1,47 @@
+// Files dragged over the canvas, for macroquad's "drop-hover" feature.
+// Load after mq_js_bundle.js:
+// <script src="mq_js_bundle.js"></script>
+// <script src="drop_hover.js"></script>
+(function () {
+    "use strict";
+
+    var hovered = 0;
+
+    function on_canvas(event) {
+        return event.target == document.getElementById("glcanvas");
+    }
+
+    function has_files(event) {
+        var types = event.dataTransfer ? event.dataTransfer.types : [];
+        return Array.prototype.indexOf.call(types, "Files") != -1;
+    }
+
+    function enter(event) {
+        if (on_canvas(event) && has_files(event)) {
+            hovered = 1;
+        }
+    }
+
+    function leave(event) {
+        if (on_canvas(event)) {
+            hovered = 0;
+        }
+    }
+
+    window.addEventListener("dragenter", enter);
+    window.addEventListener("dragover", enter);
+    window.addEventListener("dragleave", leave);
+    window.addEventListener("drop", leave);
+
+    function register_plugin(importObject) {
+        importObject.env.macroquad_drop_hovered = function () {
+            return hovered;
+        };
+    }
+
+    miniquad_add_plugin({
+        register_plugin: register_plugin,
+        version: 1,
+        name: "macroquad_drop_hover",
+    });
+})();
@@ -0,0 +1,39 @@
+// Browser Gamepad API for macroquad's "gamepad" feature.
+// Load after mq_js_bundle.js:
+// <script src="mq_js_bundle.js"></script>
+// <script src="gamepad.js"></script>
+(function () {
+    "use strict";
+
+    function gamepads() {
+        return navigator.getGamepads ? navigator.getGamepads() : [];
+    }
+
+    function gamepad(index) {
+        var pad = gamepads()[index];
+        return pad && pad.connected && pad.mapping == "standard" ? pad : null;
+    }
+
+    function register_plugin(importObject) {
+        importObject.env.macroquad_gamepad_slots = function () {
+            return gamepads().length;
+        };
+        importObject.env.macroquad_gamepad_connected = function (index) {
+            return gamepad(index) != null ? 1 : 0;
+        };
+        importObject.env.macroquad_gamepad_button = function (index, button) {
+            var pad = gamepad(index);
+            return pad && button < pad.buttons.length ? pad.buttons[button].value : 0;
+        };
+        importObject.env.macroquad_gamepad_axis = function (index, axis) {
+            var pad = gamepad(index);
+            return pad && axis < pad.axes.length ? pad.axes[axis] : 0;
+        };
+    }
+
+    miniquad_add_plugin({
+        register_plugin: register_plugin,
+        version: 1,
+        name: "macroquad_gamepad",
+    });
+})();
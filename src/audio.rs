@@ -3,13 +3,16 @@
 #![allow(dead_code)]
 
 use crate::{file::load_file, get_context, Error};
-use std::sync::Arc;
+use decode::{sound_duration, Voice};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex, Weak,
+};
 
 #[cfg(feature = "audio")]
-use quad_snd::{AudioContext as QuadSndContext, Sound as QuadSndSound};
-
-#[cfg(feature = "audio")]
-pub use quad_snd::PlaySoundParams;
+use quad_snd::{
+    AudioContext as QuadSndContext, Playback as QuadSndPlayback, Sound as QuadSndSound,
+};
 
 #[cfg(not(feature = "audio"))]
 mod dummy_audio {
@@ -36,8 +39,9 @@ mod dummy_audio {
             Sound {}
         }
 
-        pub fn play(&self, _ctx: &mut AudioContext, _params: PlaySoundParams) {
+        pub fn play(&self, _ctx: &mut AudioContext, _params: PlaySoundParams) -> Playback {
             eprintln!("warn: macroquad's \"audio\" feature disabled.");
+            Playback {}
         }
 
         pub fn stop(&self, _ctx: &mut AudioContext) {}
@@ -51,19 +55,107 @@ mod dummy_audio {
 
         pub fn delete(&self, _ctx: &AudioContext) {}
     }
+
+    pub struct Playback {}
+
+    impl Playback {
+        pub fn stop(self, _ctx: &mut AudioContext) {}
+
+        pub fn set_volume(&self, _ctx: &mut AudioContext, _volume: f32) {}
+    }
 }
 
 #[cfg(not(feature = "audio"))]
-use dummy_audio::{AudioContext as QuadSndContext, Sound as QuadSndSound};
+use dummy_audio::{
+    AudioContext as QuadSndContext, Playback as QuadSndPlayback, Sound as QuadSndSound,
+};
 
-#[cfg(not(feature = "audio"))]
+mod decode;
+mod stream;
+
+use stream::Stream;
+
+#[derive(Debug, Clone, Copy)]
 pub struct PlaySoundParams {
     pub looped: bool,
     pub volume: f32,
+    /// 1.0 plays the sound as it is, 2.0 twice as fast and an octave higher, see [set_sound_speed].
+    /// Default is 1.0
+    pub speed: f32,
+}
+
+impl Default for PlaySoundParams {
+    fn default() -> PlaySoundParams {
+        PlaySoundParams {
+            looped: false,
+            volume: 1.0,
+            speed: 1.0,
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+impl From<PlaySoundParams> for quad_snd::PlaySoundParams {
+    fn from(params: PlaySoundParams) -> quad_snd::PlaySoundParams {
+        quad_snd::PlaySoundParams {
+            looped: params.looped,
+            volume: params.volume,
+        }
+    }
+}
+
+/// A set of playbacks sharing a volume, like the music or the sound effects, see [create_group].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SoundGroup(usize);
+
+/// Seconds a group or master volume takes to go from 0 to 1,
+/// a sudden jump of the volume would be heard as a click.
+const VOLUME_RAMP: f32 = 0.1;
+
+/// A volume moving towards `target` once a frame, see [VOLUME_RAMP].
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Ramp {
+    current: f32,
+    target: f32,
+}
+
+impl Ramp {
+    const fn new(volume: f32) -> Ramp {
+        Ramp {
+            current: volume,
+            target: volume,
+        }
+    }
+
+    /// Move `dt` seconds closer to the target, true when the volume changed.
+    fn update(&mut self, dt: f32) -> bool {
+        if self.current == self.target {
+            return false;
+        }
+
+        let step = dt / VOLUME_RAMP;
+        let delta = self.target - self.current;
+        self.current = if delta.abs() <= step {
+            self.target
+        } else {
+            self.current + step.copysign(delta)
+        };
+        true
+    }
+}
+
+struct Group {
+    name: String,
+    volume: Ramp,
+    paused: bool,
 }
 
 pub struct AudioContext {
     native_ctx: QuadSndContext,
+    master_volume: Ramp,
+    groups: Vec<Group>,
+    /// The playbacks not over yet, for the group and master volumes to reach them.
+    playbacks: Vec<Arc<PlaybackState>>,
 }
 
 impl Default for AudioContext {
@@ -72,11 +164,60 @@ impl Default for AudioContext {
     }
 }
 
+/// Playbacks and sounds that are done with, to be dropped once the context
+/// is no longer borrowed: a [QuadSndSoundGuarded] deletes itself from it.
+type Garbage = (Vec<Arc<PlaybackState>>, Vec<QuadSndSoundGuarded>);
+
 impl AudioContext {
     pub fn new() -> AudioContext {
         AudioContext {
             native_ctx: QuadSndContext::new(),
+            master_volume: Ramp::new(1.),
+            groups: vec![],
+            playbacks: vec![],
+        }
+    }
+
+    /// Drop the playbacks that are over from the list, see [Garbage] for the returned ones.
+    fn forget_over(&mut self) -> Vec<Arc<PlaybackState>> {
+        let (playing, over) = std::mem::take(&mut self.playbacks)
+            .into_iter()
+            .partition(|playback| !playback.is_over());
+        self.playbacks = playing;
+        over
+    }
+
+    /// What the master and group volumes make of a playback at `volume`.
+    fn mixed_volume(&self, volume: f32, group: Option<SoundGroup>) -> f32 {
+        let group = group.map_or(1., |SoundGroup(group)| self.groups[group].volume.current);
+        volume * group * self.master_volume.current
+    }
+
+    /// Set the volume of the playbacks again after a group or master volume change.
+    fn remix(&mut self) {
+        for playback in &self.playbacks {
+            let volume = self.mixed_volume(*playback.volume.lock().unwrap(), playback.group);
+            playback.set_native_volume(&mut self.native_ctx, volume);
+        }
+    }
+
+    /// Once a frame: ramp the volumes, stream the playbacks played a part
+    /// at a time and forget the ones that are over.
+    fn update(&mut self, dt: f32) -> Garbage {
+        let mut ramped = self.master_volume.update(dt);
+        for group in &mut self.groups {
+            ramped |= group.volume.update(dt);
+        }
+        if ramped {
+            self.remix();
+        }
+
+        let mut garbage = vec![];
+        for playback in &self.playbacks {
+            playback.advance(&mut self.native_ctx, dt as f64, &mut garbage);
         }
+
+        (self.forget_over(), garbage)
     }
 
     #[cfg(target_os = "android")]
@@ -90,6 +231,14 @@ impl AudioContext {
     }
 }
 
+/// Ramps the volumes and streams the next parts of the sounds played a part at a time,
+/// called at the end of every frame.
+pub(crate) fn update() {
+    let dt = crate::time::get_unscaled_frame_time();
+    let garbage = get_context().audio_context.update(dt);
+    drop(garbage);
+}
+
 struct QuadSndSoundGuarded(QuadSndSound);
 
 impl Drop for QuadSndSoundGuarded {
@@ -99,8 +248,79 @@ impl Drop for QuadSndSoundGuarded {
     }
 }
 
+// only on wasm the sounds are not ready right away
+#[cfg(target_arch = "wasm32")]
+fn is_loaded(sound: &QuadSndSound) -> bool {
+    sound.is_loaded()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_loaded(_: &QuadSndSound) -> bool {
+    true
+}
+
+/// A playback started with [play_sound], tracked on macroquad's side
+/// since the audio backend does not report when it ends.
+#[derive(Clone, Copy)]
+struct Timing {
+    /// When the sound started, moved by a pause as if it had kept playing since.
+    started: f64,
+    looped: bool,
+}
+
+impl Timing {
+    /// Whether a one-shot reached its end, looped ones play until stopped.
+    ///
+    /// A one-shot of unknown length is taken as over right away, macroquad can't
+    /// tell when it ends and would keep track of it forever otherwise.
+    fn is_over(&self, duration: Option<f64>) -> bool {
+        match duration {
+            _ if self.looped => false,
+            Some(duration) => miniquad::date::now() - self.started >= duration,
+            None => true,
+        }
+    }
+
+    /// Seconds since the start, wrapped for the looped ones.
+    /// None once it is over, and when the length is unknown.
+    fn position(&self, duration: Option<f64>) -> Option<f64> {
+        let duration = duration.filter(|&duration| duration > 0.)?;
+        if self.is_over(Some(duration)) {
+            return None;
+        }
+
+        let elapsed = miniquad::date::now() - self.started;
+        Some(if self.looped {
+            elapsed % duration
+        } else {
+            elapsed
+        })
+    }
+}
+
+struct SoundState {
+    /// None for the [streamed](load_sound_streamed) ones, not loaded in the backend.
+    sound: Option<QuadSndSoundGuarded>,
+    /// The file, shared with the decoders of the playbacks played a part at a time,
+    /// see [Stream]. Kept besides the backend's decoded copy: as big as it for WAV,
+    /// about a tenth of it for Ogg Vorbis.
+    data: Arc<[u8]>,
+    /// Length in seconds, when the format header allows to tell.
+    duration: Option<f64>,
+    /// The last playback, the one [is_sound_playing] tells about.
+    last: Mutex<Weak<PlaybackState>>,
+    /// Bumped by [stop_sound], which ends the playbacks started before.
+    stops: AtomicUsize,
+}
+
 #[derive(Clone)]
-pub struct Sound(Arc<QuadSndSoundGuarded>);
+pub struct Sound(Arc<SoundState>);
+
+impl Sound {
+    fn last(&self) -> Option<Arc<PlaybackState>> {
+        self.0.last.lock().unwrap().upgrade()
+    }
+}
 
 impl std::fmt::Debug for Sound {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -120,44 +340,613 @@ pub async fn load_sound(path: &str) -> Result<Sound, Error> {
 /// Load audio data.
 ///
 /// Attempts to automatically detect the format of the source of data.
+///
+/// The data is kept with the sound, besides the copy the audio backend decodes:
+/// macroquad decodes it again to play the sound from the middle, at another speed
+/// or pan. For the long music tracks use [load_sound_streamed], which keeps the file only.
 pub async fn load_sound_from_bytes(data: &[u8]) -> Result<Sound, Error> {
     let sound = {
         let ctx = &mut get_context().audio_context;
         QuadSndSound::load(&mut ctx.native_ctx, data)
     };
 
-    // only on wasm the sound is not ready right away
-    #[cfg(target_arch = "wasm32")]
-    while !sound.is_loaded() {
+    while !is_loaded(&sound) {
         crate::window::next_frame().await;
     }
 
-    Ok(Sound(Arc::new(QuadSndSoundGuarded(sound))))
+    Ok(Sound(Arc::new(SoundState {
+        sound: Some(QuadSndSoundGuarded(sound)),
+        data: data.into(),
+        duration: sound_duration(data),
+        last: Mutex::new(Weak::new()),
+        stops: AtomicUsize::new(0),
+    })))
+}
+
+/// Load an audio file to play it a couple of seconds at a time, instead of having the audio
+/// backend decode all of it up front. For the long music tracks: only the file and the parts
+/// decoded ahead stay in memory, for Ogg Vorbis that is a tenth of the decoded sound or less.
+///
+/// The [Sound] plays, loops, seeks and stops like the other ones. The looped ones go on
+/// from the start without a gap, and [stop_sound] then [play_sound] starts over from the start.
+///
+/// The limitations:
+/// - only WAV and, with the "audio" feature, Ogg Vorbis files can be streamed,
+///   the other formats are an error
+/// - a playback starts a frame or two after [play_sound], once its first part is decoded
+/// - each playback decodes the file on its own, off the main thread or a bit every
+///   frame on the web: many playbacks at once of a streamed sound cost as many decoders
+/// - seeking into Ogg Vorbis decodes the file from the start up to there, far into
+///   a long track that takes a moment
+/// - the parts are timed once a frame and crossfade over a few hundredths of a second
+///   to hide it, a frame longer than the two seconds decoded ahead leaves a gap
+/// - a WAV file is as big as its decoded sound, streaming it spares the decoding
+///   up front but not the memory
+pub async fn load_sound_streamed(path: &str) -> Result<Sound, Error> {
+    let data: Arc<[u8]> = load_file(path).await?.into();
+    if decode::Decoder::new(data.clone()).is_none() {
+        return Err(Error::UnknownError(
+            "load_sound_streamed: only WAV and Ogg Vorbis files can be streamed",
+        ));
+    }
+
+    Ok(Sound(Arc::new(SoundState {
+        sound: None,
+        duration: sound_duration(&data),
+        data,
+        last: Mutex::new(Weak::new()),
+        stops: AtomicUsize::new(0),
+    })))
+}
+
+/// Another name for [load_sound_streamed].
+pub async fn load_sound_streaming(path: &str) -> Result<Sound, Error> {
+    load_sound_streamed(path).await
+}
+
+/// What the audio backend is doing with a playback.
+enum Native {
+    /// The sound itself, from its start.
+    Playing(QuadSndPlayback),
+    /// In a paused group, `position` seconds into the sound.
+    Paused {
+        position: f64,
+    },
+    /// Played a part at a time: at another speed or pan, seeked, resumed or streamed.
+    Streaming(Box<Stream>),
+    Stopped,
+}
+
+struct PlaybackState {
+    native: Mutex<Native>,
+    sound: Sound,
+    timing: Mutex<Timing>,
+    /// [SoundState::stops] when it started.
+    stops: usize,
+    group: Option<SoundGroup>,
+    /// Its own volume, before the group and master ones.
+    volume: Mutex<f32>,
+    voice: Mutex<Voice>,
+}
+
+impl PlaybackState {
+    fn is_over(&self) -> bool {
+        if self.sound.0.stops.load(Ordering::Relaxed) != self.stops {
+            return true;
+        }
+
+        match &*self.native.lock().unwrap() {
+            Native::Playing(_) => self.timing.lock().unwrap().is_over(self.sound.0.duration),
+            Native::Streaming(stream) => stream.is_over(miniquad::date::now()),
+            Native::Paused { .. } => false,
+            Native::Stopped => true,
+        }
+    }
+
+    fn is_playing(&self) -> bool {
+        let playing = matches!(
+            *self.native.lock().unwrap(),
+            Native::Playing(_) | Native::Streaming(_)
+        );
+        playing && !self.is_over()
+    }
+
+    /// Seconds into the sound, None once over or when the length is unknown.
+    fn position(&self) -> Option<f64> {
+        if self.is_over() {
+            return None;
+        }
+
+        let duration = self.sound.0.duration;
+        match &*self.native.lock().unwrap() {
+            Native::Playing(_) => self.timing.lock().unwrap().position(duration),
+            Native::Streaming(stream) => Some(stream.position(miniquad::date::now())),
+            Native::Paused { position } => duration.map(|_| *position),
+            Native::Stopped => None,
+        }
+    }
+
+    /// A [Stream] of the sound from `position`, with the voice of the playback.
+    fn stream(&self, position: f64, volume: f32, fade_in: bool) -> Option<Stream> {
+        let voice = *self.voice.lock().unwrap();
+        let looped = self.timing.lock().unwrap().looped;
+        let data = self.sound.0.data.clone();
+        Stream::new(data, position, voice, looped, volume, fade_in)
+    }
+
+    /// Stop it in the backend and remember where it was, see [Garbage] for the sounds.
+    fn pause(&self, ctx: &mut QuadSndContext, garbage: &mut Vec<QuadSndSoundGuarded>) {
+        let mut native = self.native.lock().unwrap();
+        let position = match std::mem::replace(&mut *native, Native::Stopped) {
+            Native::Playing(playback) => {
+                playback.stop(ctx);
+                let timing = *self.timing.lock().unwrap();
+                timing.position(self.sound.0.duration).unwrap_or(0.)
+            }
+            Native::Streaming(mut stream) => {
+                let position = stream.position(miniquad::date::now());
+                stream.stop(ctx, garbage);
+                position
+            }
+            other => {
+                *native = other;
+                return;
+            }
+        };
+
+        *native = Native::Paused { position };
+    }
+
+    /// Go on from where the pause left it, decoding the sound from there off the main
+    /// thread. The sound itself plays instead when that is not needed or not possible.
+    fn resume(&self, ctx: &mut QuadSndContext, volume: f32) {
+        let mut native = self.native.lock().unwrap();
+        let Native::Paused { position } = *native else {
+            return;
+        };
+
+        let plain = self.voice.lock().unwrap().is_plain() && self.sound.0.sound.is_some();
+        if !plain || position > 0. {
+            if let Some(stream) = self.stream(position, volume, position > 0.) {
+                *native = Native::Streaming(Box::new(stream));
+                return;
+            }
+            crate::logging::warn!("resume_group: can't seek in the sound, it starts over");
+        }
+
+        let mut timing = self.timing.lock().unwrap();
+        let params = PlaySoundParams {
+            looped: timing.looped,
+            volume,
+            ..Default::default()
+        };
+        let sound = self.sound.0.sound.as_ref();
+        let sound = sound.expect("streamed sounds are checked on load");
+        timing.started = miniquad::date::now();
+        *native = Native::Playing(sound.0.play(ctx, params.into()));
+    }
+
+    /// Once a frame: stream the next part of a [Stream]. See [Garbage] for the sounds.
+    fn advance(&self, ctx: &mut QuadSndContext, dt: f64, garbage: &mut Vec<QuadSndSoundGuarded>) {
+        if let Native::Streaming(stream) = &mut *self.native.lock().unwrap() {
+            stream.update(ctx, miniquad::date::now(), dt, garbage);
+        }
+    }
+
+    /// See [Garbage] for the sounds.
+    fn stop(&self, ctx: &mut QuadSndContext, garbage: &mut Vec<QuadSndSoundGuarded>) {
+        match std::mem::replace(&mut *self.native.lock().unwrap(), Native::Stopped) {
+            Native::Playing(playback) => playback.stop(ctx),
+            Native::Streaming(mut stream) => stream.stop(ctx, garbage),
+            _ => {}
+        }
+    }
+
+    /// `volume` is the mixed one, see [AudioContext::mixed_volume].
+    fn set_native_volume(&self, ctx: &mut QuadSndContext, volume: f32) {
+        match &mut *self.native.lock().unwrap() {
+            Native::Playing(playback) => playback.set_volume(ctx, volume),
+            Native::Streaming(stream) => stream.set_volume(ctx, volume),
+            _ => {}
+        }
+    }
+
+    /// Play it with `voice` from now on, from where it is. See [Garbage] for the sounds.
+    fn set_voice(&self, voice: Voice, volume: f32, garbage: &mut Vec<QuadSndSoundGuarded>) {
+        *self.voice.lock().unwrap() = voice;
+        let now = miniquad::date::now();
+
+        let mut native = self.native.lock().unwrap();
+        let position = match &mut *native {
+            Native::Streaming(stream) => {
+                stream.set_voice(voice, now, garbage);
+                return;
+            }
+            Native::Playing(_) if !voice.is_plain() => {
+                let timing = *self.timing.lock().unwrap();
+                timing.position(self.sound.0.duration).unwrap_or(0.)
+            }
+            // the paused ones get the voice on resume
+            _ => return,
+        };
+
+        let Some(mut stream) = self.stream(position, volume, true) else {
+            crate::logging::warn!("only WAV and Ogg Vorbis sounds change speed or pan");
+            return;
+        };
+        let Native::Playing(playback) = std::mem::replace(&mut *native, Native::Stopped) else {
+            unreachable!()
+        };
+        stream.replace(playback, now);
+        *native = Native::Streaming(Box::new(stream));
+    }
+
+    /// Go on from `position` seconds into the sound. See [Garbage] for the sounds.
+    fn seek(&self, position: f64, volume: f32, garbage: &mut Vec<QuadSndSoundGuarded>) {
+        let mut native = self.native.lock().unwrap();
+        match &mut *native {
+            Native::Paused { position: paused } => {
+                *paused = position;
+                return;
+            }
+            Native::Stopped => return,
+            _ => {}
+        }
+
+        let now = miniquad::date::now();
+        let Some(mut stream) = self.stream(position, volume, true) else {
+            crate::logging::warn!("set_sound_position: can only seek in WAV and Ogg Vorbis sounds");
+            return;
+        };
+        match std::mem::replace(&mut *native, Native::Stopped) {
+            Native::Playing(playback) => stream.replace(playback, now),
+            Native::Streaming(old) => stream.take_over(*old, now, garbage),
+            _ => unreachable!(),
+        }
+        *native = Native::Streaming(Box::new(stream));
+    }
+}
+
+/// One playback of a [Sound], returned by [play_sound].
+///
+/// Unlike the functions taking the [Sound], the ones taking a playback only
+/// change this instance of the sound. Once the playback is over, by reaching
+/// the end, [stop_playback] or [stop_sound], they do nothing.
+pub struct Playback(Arc<PlaybackState>);
+
+impl Playback {
+    fn is_over(&self) -> bool {
+        self.0.is_over()
+    }
+}
+
+impl std::fmt::Debug for Playback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Playback").finish()
+    }
 }
 
 pub fn play_sound_once(sound: &Sound) {
-    let ctx = &mut get_context().audio_context;
+    play_sound(sound, PlaySoundParams::default());
+}
 
-    sound.0 .0.play(
-        &mut ctx.native_ctx,
-        PlaySoundParams {
-            looped: false,
-            volume: 1.0,
-        },
-    );
+/// Start a new playback of the sound, on top of the ones already playing.
+///
+/// The returned [Playback] controls just this one, it can be ignored
+/// when the sound is only ever played and stopped as a whole.
+pub fn play_sound(sound: &Sound, params: PlaySoundParams) -> Playback {
+    play(sound, None, params)
 }
 
-pub fn play_sound(sound: &Sound, params: PlaySoundParams) {
+/// [play_sound] in a [group](create_group), which scales its volume on top of the master one.
+///
+/// In a [paused](pause_group) group, the playback starts once the group is resumed.
+pub fn play_sound_in_group(sound: &Sound, group: SoundGroup, params: PlaySoundParams) -> Playback {
+    play(sound, Some(group), params)
+}
+
+fn play(sound: &Sound, group: Option<SoundGroup>, params: PlaySoundParams) -> Playback {
     let ctx = &mut get_context().audio_context;
-    sound.0 .0.play(&mut ctx.native_ctx, params);
+
+    let paused = group.is_some_and(|SoundGroup(group)| ctx.groups[group].paused);
+    let voice = Voice::PLAIN.with_speed(params.speed);
+    let volume = ctx.mixed_volume(params.volume, group);
+    let streamed = sound.0.sound.is_none();
+    let stream = if paused || (voice.is_plain() && !streamed) {
+        None
+    } else {
+        let stream = Stream::new(
+            sound.0.data.clone(),
+            0.,
+            voice,
+            params.looped,
+            volume,
+            false,
+        );
+        if stream.is_none() {
+            crate::logging::warn!(
+                "play_sound: only WAV and Ogg Vorbis sounds play at another speed"
+            );
+        }
+        stream
+    };
+    let native = match (stream, &sound.0.sound) {
+        _ if paused => Native::Paused { position: 0. },
+        (Some(stream), _) => Native::Streaming(Box::new(stream)),
+        (None, Some(native_sound)) => {
+            let native_params = PlaySoundParams { volume, ..params };
+            Native::Playing(
+                native_sound
+                    .0
+                    .play(&mut ctx.native_ctx, native_params.into()),
+            )
+        }
+        // the streamed ones were checked on load
+        (None, None) => unreachable!(),
+    };
+    let playback = Arc::new(PlaybackState {
+        native: Mutex::new(native),
+        sound: sound.clone(),
+        timing: Mutex::new(Timing {
+            started: miniquad::date::now(),
+            looped: params.looped,
+        }),
+        stops: sound.0.stops.load(Ordering::Relaxed),
+        group,
+        volume: Mutex::new(params.volume),
+        voice: Mutex::new(voice),
+    });
+    *sound.0.last.lock().unwrap() = Arc::downgrade(&playback);
+
+    let over = ctx.forget_over();
+    ctx.playbacks.push(playback.clone());
+    drop(over);
+
+    Playback(playback)
 }
 
+/// Stop every playback of the sound.
 pub fn stop_sound(sound: &Sound) {
     let ctx = &mut get_context().audio_context;
-    sound.0 .0.stop(&mut ctx.native_ctx);
+    if let Some(native_sound) = &sound.0.sound {
+        native_sound.0.stop(&mut ctx.native_ctx);
+    }
+
+    // the ones played a part at a time play other sounds made of parts of this one
+    let mut garbage = vec![];
+    for playback in &ctx.playbacks {
+        if Arc::ptr_eq(&playback.sound.0, &sound.0) {
+            playback.stop(&mut ctx.native_ctx, &mut garbage);
+        }
+    }
+    sound.0.stops.fetch_add(1, Ordering::Relaxed);
+
+    let over = ctx.forget_over();
+    drop((over, garbage));
+}
+
+/// Stop this playback only, the other ones of the same sound go on.
+pub fn stop_playback(playback: Playback) {
+    if playback.is_over() {
+        return;
+    }
+
+    let ctx = &mut get_context().audio_context;
+    let mut garbage = vec![];
+    playback.0.stop(&mut ctx.native_ctx, &mut garbage);
+    drop(garbage);
+}
+
+/// Whether the playback did not reach its end and was not stopped, see [is_sound_playing].
+/// A playback in a paused group is not over, but not playing either.
+pub fn is_playback_playing(playback: &Playback) -> bool {
+    playback.0.is_playing()
+}
+
+/// Change the volume of this playback only, to fade the music out for example.
+pub fn set_playback_volume(playback: &Playback, volume: f32) {
+    if playback.is_over() {
+        return;
+    }
+
+    *playback.0.volume.lock().unwrap() = volume;
+
+    let ctx = &mut get_context().audio_context;
+    let volume = ctx.mixed_volume(volume, playback.0.group);
+    playback.0.set_native_volume(&mut ctx.native_ctx, volume);
+}
+
+/// Change the pitch of this playback only, resampling it the same way as
+/// [PlaySoundParams::speed]: 2.0 is an octave higher, and twice as fast.
+pub fn set_playback_pitch(playback: &Playback, pitch: f32) {
+    change_voice(playback, |voice| voice.with_speed(pitch));
 }
 
+/// Pan this playback from -1.0, all left, to 1.0, all right. 0.0 by default.
+///
+/// The pan is constant power, the sound is as loud in the middle as on the sides.
+/// Mono sounds get panned as stereo, the sounds with more than two channels are not.
+/// It plays the sound a part at a time, like [set_sound_speed]: every change renders
+/// the part from there again, a sweep with a change every frame does so every frame.
+pub fn set_playback_pan(playback: &Playback, pan: f32) {
+    change_voice(playback, |voice| voice.with_pan(pan));
+}
+
+fn change_voice(playback: &Playback, change: impl FnOnce(Voice) -> Voice) {
+    if playback.is_over() {
+        return;
+    }
+
+    let voice = change(*playback.0.voice.lock().unwrap());
+    let ctx = &mut get_context().audio_context;
+    let volume = ctx.mixed_volume(*playback.0.volume.lock().unwrap(), playback.0.group);
+    let mut garbage = vec![];
+    playback.0.set_voice(voice, volume, &mut garbage);
+    drop(garbage);
+}
+
+/// Make a group of playbacks, the ones started with it by [play_sound_in_group],
+/// to change their volume together: the music and the sound effects of the settings menu.
+///
+/// Groups are flat, a playback is in one group at most, and there is no way to remove one.
+/// Creating a group with the name of an existing one returns the existing one.
+pub fn create_group(name: &str) -> SoundGroup {
+    let ctx = &mut get_context().audio_context;
+
+    if let Some(group) = ctx.groups.iter().position(|group| group.name == name) {
+        return SoundGroup(group);
+    }
+    ctx.groups.push(Group {
+        name: name.to_owned(),
+        volume: Ramp::new(1.),
+        paused: false,
+    });
+    SoundGroup(ctx.groups.len() - 1)
+}
+
+/// Scale the volume of the playing and the future playbacks of the group, 1.0 by default.
+///
+/// The volume goes there over the next frames, a tenth of a second from 0 to 1,
+/// so that a sudden change is not heard as a click.
+pub fn set_group_volume(group: SoundGroup, volume: f32) {
+    get_context().audio_context.groups[group.0].volume.target = volume;
+}
+
+/// The volume given to [set_group_volume], even while it is still ramping there.
+pub fn group_volume(group: SoundGroup) -> f32 {
+    get_context().audio_context.groups[group.0].volume.target
+}
+
+/// Pause the playbacks of the group, for the sound effects while the pause menu is open.
+///
+/// They go on from where they were on [resume_group], and the ones started
+/// meanwhile start then. Resuming in the middle of a WAV or Ogg Vorbis sound
+/// plays it a part at a time from there, like [set_sound_position], decoded off
+/// the main thread; the other formats start over from the beginning.
+pub fn pause_group(group: SoundGroup) {
+    let ctx = &mut get_context().audio_context;
+    ctx.groups[group.0].paused = true;
+
+    let mut garbage = vec![];
+    for playback in &ctx.playbacks {
+        if playback.group == Some(group) && !playback.is_over() {
+            playback.pause(&mut ctx.native_ctx, &mut garbage);
+        }
+    }
+    drop(garbage);
+}
+
+pub fn resume_group(group: SoundGroup) {
+    let ctx = &mut get_context().audio_context;
+    ctx.groups[group.0].paused = false;
+
+    for playback in &ctx.playbacks {
+        if playback.group == Some(group) {
+            let volume = ctx.mixed_volume(*playback.volume.lock().unwrap(), playback.group);
+            playback.resume(&mut ctx.native_ctx, volume);
+        }
+    }
+}
+
+pub fn is_group_paused(group: SoundGroup) -> bool {
+    get_context().audio_context.groups[group.0].paused
+}
+
+/// Scale the volume of every playback, grouped or not, 1.0 by default.
+///
+/// Ramps like [set_group_volume].
+pub fn set_master_volume(volume: f32) {
+    get_context().audio_context.master_volume.target = volume;
+}
+
+pub fn master_volume() -> f32 {
+    get_context().audio_context.master_volume.target
+}
+
+/// Set the volume of every playing playback of the sound, like [set_playback_volume] does
+/// for one. The group and master volumes still scale it.
 pub fn set_sound_volume(sound: &Sound, volume: f32) {
     let ctx = &mut get_context().audio_context;
-    sound.0 .0.set_volume(&mut ctx.native_ctx, volume);
+    for playback in &ctx.playbacks {
+        if Arc::ptr_eq(&playback.sound.0, &sound.0) {
+            *playback.volume.lock().unwrap() = volume;
+        }
+    }
+    ctx.remix();
+}
+
+/// Change the speed of every playing playback of the sound, 1.0 plays it as it is,
+/// 2.0 twice as fast and an octave higher. The new playbacks start at [PlaySoundParams::speed].
+///
+/// The audio backend only plays sounds as they are, so macroquad resamples them: the playbacks
+/// at another speed are decoded and handed to the backend a couple of seconds at a time.
+/// That works for the WAV and, with the "audio" feature, Ogg Vorbis sounds, the other formats
+/// play as they are. A change fades over a few hundredths of a second, it is not instant.
+/// Speeds are clamped to 1/16..=16.
+pub fn set_sound_speed(sound: &Sound, speed: f32) {
+    let ctx = &mut get_context().audio_context;
+    let mut garbage = vec![];
+    for playback in &ctx.playbacks {
+        if Arc::ptr_eq(&playback.sound.0, &sound.0) && !playback.is_over() {
+            let volume = ctx.mixed_volume(*playback.volume.lock().unwrap(), playback.group);
+            let voice = playback.voice.lock().unwrap().with_speed(speed);
+            playback.set_voice(voice, volume, &mut garbage);
+        }
+    }
+    drop(garbage);
+}
+
+/// Whether the sound was started with [play_sound] and did not end or get stopped yet.
+///
+/// Looped sounds play until [stop_sound]. The playback is timed from the `play_sound` call,
+/// with the length read from the WAV or Ogg Vorbis header. When the length can't be read,
+/// a one-shot is reported as not playing right after it starts.
+/// Only the last playback counts, and not while its group is paused.
+pub fn is_sound_playing(sound: &Sound) -> bool {
+    sound.last().is_some_and(|playback| playback.is_playing())
+}
+
+/// Seconds into the last playback of the sound, wrapping around for looped sounds.
+///
+/// None when the sound is not playing, and when its length can't be read from the file.
+pub fn sound_position(sound: &Sound) -> Option<f32> {
+    sound
+        .last()
+        .and_then(|playback| playback.position())
+        .map(|position| position as f32)
+}
+
+/// Jump to `position` seconds into the last playback of the sound, the one
+/// [sound_position] tells about. Looped sounds wrap around, one-shots seeked
+/// past their end are over.
+///
+/// Like a [speed](set_sound_speed) change, the playback goes on a part at a time
+/// from there, it works for the same formats and fades over a few hundredths of a second.
+/// Ogg Vorbis can only be decoded from the start: seeking far into a long file takes
+/// a moment, the playback goes on where it was meanwhile.
+pub fn set_sound_position(sound: &Sound, position: f32) {
+    let Some(playback) = sound.last().filter(|playback| !playback.is_over()) else {
+        return;
+    };
+
+    let ctx = &mut get_context().audio_context;
+    let volume = ctx.mixed_volume(*playback.volume.lock().unwrap(), playback.group);
+    let mut garbage = vec![];
+    playback.seek(position as f64, volume, &mut garbage);
+    drop(garbage);
+}
+
+#[test]
+fn volume_ramp() {
+    let mut ramp = Ramp::new(1.);
+    assert!(!ramp.update(1. / 60.));
+
+    ramp.target = 0.;
+    let mut frames = 0;
+    while ramp.update(VOLUME_RAMP / 4.) {
+        assert!((0. ..=1.).contains(&ramp.current));
+        frames += 1;
+    }
+    assert_eq!(ramp.current, 0.);
+    assert_eq!(frames, 4);
 }
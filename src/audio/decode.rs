@@ -0,0 +1,615 @@
+//! Decoding WAV and Ogg Vorbis files on macroquad's side, a part at a time.
+//!
+//! The audio backend only plays whole sounds from their start. Playing from the
+//! middle, at another speed, or without decoding a long file up front is done by
+//! decoding the needed part here and handing it to the backend as a short WAV
+//! file, see [Stream](super::stream::Stream).
+
+use std::{f32::consts::SQRT_2, sync::Arc};
+
+#[cfg(feature = "audio")]
+use lewton::inside_ogg::OggStreamReader;
+#[cfg(feature = "audio")]
+use std::io::Cursor;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Format {
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+/// How the samples of a WAV file are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Encoding {
+    U8,
+    I16,
+    I24,
+    I32,
+    F32,
+}
+
+impl Encoding {
+    fn size(self) -> usize {
+        match self {
+            Encoding::U8 => 1,
+            Encoding::I16 => 2,
+            Encoding::I24 => 3,
+            Encoding::I32 | Encoding::F32 => 4,
+        }
+    }
+
+    /// The sample at the start of `bytes`, as 16 bit.
+    fn decode(self, bytes: &[u8]) -> i16 {
+        match self {
+            Encoding::U8 => (bytes[0] as i16 - 128) << 8,
+            Encoding::I16 => i16::from_le_bytes([bytes[0], bytes[1]]),
+            // the most significant bytes, they come last
+            Encoding::I24 => i16::from_le_bytes([bytes[1], bytes[2]]),
+            Encoding::I32 => i16::from_le_bytes([bytes[2], bytes[3]]),
+            Encoding::F32 => {
+                let sample = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                (sample.clamp(-1., 1.) * i16::MAX as f32) as i16
+            }
+        }
+    }
+}
+
+enum Source {
+    Wav {
+        data: Arc<[u8]>,
+        /// Where the samples start in the file.
+        start: usize,
+        block_align: usize,
+        encoding: Encoding,
+    },
+    #[cfg(feature = "audio")]
+    Ogg {
+        data: Arc<[u8]>,
+        reader: Box<OggStreamReader<Cursor<Arc<[u8]>>>>,
+        /// The last decoded packet, `taken` samples of it read already.
+        packet: Vec<i16>,
+        taken: usize,
+    },
+}
+
+/// Reads the frames of a sound in order, as interleaved 16 bit samples.
+pub(crate) struct Decoder {
+    format: Format,
+    /// Frames in the whole sound, when the headers tell.
+    frames: Option<u64>,
+    source: Source,
+    /// Frames read or skipped so far.
+    frame: u64,
+}
+
+impl Decoder {
+    /// None for the formats other than WAV and, with the "audio" feature, Ogg Vorbis.
+    pub(crate) fn new(data: Arc<[u8]>) -> Option<Decoder> {
+        if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WAVE") {
+            let (format, start, frames, block_align, encoding) = wav_layout(&data)?;
+            return Some(Decoder {
+                format,
+                frames: Some(frames),
+                source: Source::Wav {
+                    data,
+                    start,
+                    block_align,
+                    encoding,
+                },
+                frame: 0,
+            });
+        }
+
+        #[cfg(feature = "audio")]
+        if data.starts_with(b"OggS") {
+            let reader = OggStreamReader::new(Cursor::new(data.clone())).ok()?;
+            let format = Format {
+                channels: reader.ident_hdr.audio_channels as u16,
+                sample_rate: reader.ident_hdr.audio_sample_rate,
+            };
+            if format.channels == 0 || format.sample_rate == 0 {
+                return None;
+            }
+            return Some(Decoder {
+                format,
+                frames: ogg_frames(&data),
+                source: Source::Ogg {
+                    data,
+                    reader: Box::new(reader),
+                    packet: vec![],
+                    taken: 0,
+                },
+                frame: 0,
+            });
+        }
+
+        None
+    }
+
+    pub(crate) fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Frames in the whole sound, None when the headers don't tell.
+    pub(crate) fn frames(&self) -> Option<u64> {
+        self.frames
+    }
+
+    /// Go back to the first frame.
+    pub(crate) fn rewind(&mut self) {
+        self.frame = 0;
+        #[cfg(feature = "audio")]
+        if let Source::Ogg {
+            data,
+            reader,
+            packet,
+            taken,
+        } = &mut self.source
+        {
+            // it read the headers fine the first time
+            if let Ok(new) = OggStreamReader::new(Cursor::new(data.clone())) {
+                **reader = new;
+            }
+            packet.clear();
+            *taken = 0;
+        }
+    }
+
+    /// Append up to `frames` frames to `samples`, fewer at the end of the sound.
+    /// Returns how many were read.
+    pub(crate) fn read(&mut self, frames: usize, samples: &mut Vec<i16>) -> usize {
+        self.advance(frames, Some(samples))
+    }
+
+    /// Skip up to `frames` frames, fewer at the end of the sound.
+    /// Returns how many were skipped.
+    ///
+    /// Ogg Vorbis has to be decoded to skip through it, it takes as long as reading.
+    pub(crate) fn skip(&mut self, frames: u64) -> u64 {
+        let frames = frames.min(usize::MAX as u64) as usize;
+        self.advance(frames, None) as u64
+    }
+
+    fn advance(&mut self, frames: usize, mut samples: Option<&mut Vec<i16>>) -> usize {
+        let channels = self.format.channels as usize;
+        let done = match &mut self.source {
+            Source::Wav {
+                data,
+                start,
+                block_align,
+                encoding,
+            } => {
+                let left = self.frames.unwrap_or(0) - self.frame;
+                let frames = (frames as u64).min(left) as usize;
+                if let Some(samples) = &mut samples {
+                    samples.reserve(frames * channels);
+                    let first = *start + self.frame as usize * *block_align;
+                    for frame in 0..frames {
+                        let at = first + frame * *block_align;
+                        for channel in 0..channels {
+                            samples.push(encoding.decode(&data[at + channel * encoding.size()..]));
+                        }
+                    }
+                }
+                frames
+            }
+            #[cfg(feature = "audio")]
+            Source::Ogg {
+                reader,
+                packet,
+                taken,
+                ..
+            } => {
+                let mut done = 0;
+                while done < frames {
+                    if *taken == packet.len() {
+                        match reader.read_dec_packet_itl() {
+                            Ok(Some(next)) => {
+                                *packet = next;
+                                *taken = 0;
+                                continue;
+                            }
+                            // a broken page ends the sound, like the end of the file
+                            Ok(None) | Err(_) => break,
+                        }
+                    }
+                    let count = ((packet.len() - *taken) / channels).min(frames - done);
+                    if count == 0 {
+                        // a packet that is not made of whole frames
+                        *taken = packet.len();
+                        continue;
+                    }
+                    if let Some(samples) = &mut samples {
+                        samples.extend_from_slice(&packet[*taken..*taken + count * channels]);
+                    }
+                    *taken += count * channels;
+                    done += count;
+                }
+                done
+            }
+        };
+        self.frame += done as u64;
+        done
+    }
+}
+
+/// Where the samples of a WAV file are and how they are stored:
+/// (format, where they start, frames, block align, encoding).
+fn wav_layout(data: &[u8]) -> Option<(Format, usize, u64, usize, Encoding)> {
+    let u16_at = |at: usize| Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?));
+    let u32_at = |at: usize| Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?));
+
+    let (mut fmt, mut at) = (None, 12);
+    while let Some(id) = data.get(at..at + 4) {
+        let size = u32_at(at + 4)? as usize;
+        match id {
+            b"fmt " => {
+                let mut tag = u16_at(at + 8)?;
+                // WAVE_FORMAT_EXTENSIBLE, the actual format is the start of the sub format GUID
+                if tag == 0xfffe {
+                    tag = u16_at(at + 32)?;
+                }
+                let format = Format {
+                    channels: u16_at(at + 10)?,
+                    sample_rate: u32_at(at + 12)?,
+                };
+                let block_align = u16_at(at + 20)? as usize;
+                let encoding = match (tag, u16_at(at + 22)?) {
+                    (1, 8) => Encoding::U8,
+                    (1, 16) => Encoding::I16,
+                    (1, 24) => Encoding::I24,
+                    (1, 32) => Encoding::I32,
+                    (3, 32) => Encoding::F32,
+                    _ => return None,
+                };
+                fmt = Some((format, block_align, encoding));
+            }
+            b"data" => {
+                let (format, block_align, encoding) = fmt?;
+                let frame_size = format.channels as usize * encoding.size();
+                if frame_size == 0 || format.sample_rate == 0 || block_align < frame_size {
+                    return None;
+                }
+                let start = at + 8;
+                // streamed files have a wrong data size, the rest of the file is the data then
+                let size = size.min(data.len() - start);
+                return Some((
+                    format,
+                    start,
+                    (size / block_align) as u64,
+                    block_align,
+                    encoding,
+                ));
+            }
+            _ => {}
+        }
+        // chunks are padded to an even size
+        at += 8 + size + size % 2;
+    }
+    None
+}
+
+/// Frames in an Ogg Vorbis file: the granule position of the last page.
+fn ogg_frames(data: &[u8]) -> Option<u64> {
+    let last_page = data.windows(4).rposition(|window| window == b"OggS")?;
+    let granule = data.get(last_page + 6..last_page + 14)?;
+    Some(u64::from_le_bytes(granule.try_into().ok()?))
+}
+
+/// Length of a WAV or Ogg Vorbis file in seconds, from its headers.
+pub(crate) fn sound_duration(data: &[u8]) -> Option<f64> {
+    let u32_at = |at: usize| Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?));
+
+    if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WAVE") {
+        // from the byte rate, it works for the compressed WAV formats too
+        let (mut byte_rate, mut at) = (None, 12);
+        while let Some(id) = data.get(at..at + 4) {
+            let size = u32_at(at + 4)? as usize;
+            match id {
+                b"fmt " => byte_rate = u32_at(at + 16),
+                b"data" => {
+                    let size = size.min(data.len() - (at + 8));
+                    return Some(size as f64 / byte_rate.filter(|&rate| rate > 0)? as f64);
+                }
+                _ => {}
+            }
+            at += 8 + size + size % 2;
+        }
+        return None;
+    }
+
+    if data.starts_with(b"OggS") {
+        let header = data.windows(7).position(|window| window == b"\x01vorbis")?;
+        let sample_rate = u32_at(header + 12)?;
+        let frames = ogg_frames(data)?;
+        return (sample_rate > 0).then(|| frames as f64 / sample_rate as f64);
+    }
+
+    None
+}
+
+/// A part of a sound to decode: `frames` frames, after skipping `skip` of them.
+/// The looped ones go on from the start at the end of the sound.
+pub(crate) struct Part {
+    pub skip: u64,
+    pub frames: usize,
+    pub looped: bool,
+    pub samples: Vec<i16>,
+}
+
+impl Part {
+    pub(crate) fn new(skip: u64, frames: usize, looped: bool) -> Part {
+        Part {
+            skip,
+            frames,
+            looped,
+            samples: vec![],
+        }
+    }
+
+    fn is_done(&self, channels: usize) -> bool {
+        self.skip == 0 && self.samples.len() / channels >= self.frames
+    }
+
+    /// Decode or skip up to `budget` more frames, true once the part is done.
+    /// A one-shot sound ending before the part does ends the part early.
+    pub(crate) fn work(&mut self, decoder: &mut Decoder, mut budget: usize) -> bool {
+        let channels = decoder.format.channels as usize;
+        // rewinding twice in a row, the sound is empty
+        let mut rewound = false;
+        while budget > 0 && !self.is_done(channels) {
+            let done = if self.skip > 0 {
+                let skipped = decoder.skip(self.skip.min(budget as u64));
+                self.skip -= skipped;
+                skipped as usize
+            } else {
+                let left = self.frames - self.samples.len() / channels;
+                decoder.read(left.min(budget), &mut self.samples)
+            };
+
+            if done > 0 {
+                budget -= done;
+                rewound = false;
+            } else if self.looped && !rewound {
+                decoder.rewind();
+                rewound = true;
+            } else {
+                self.skip = 0;
+                self.frames = self.samples.len() / channels;
+            }
+        }
+        self.is_done(channels)
+    }
+}
+
+/// How a playback differs from the sound as it is in the file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Voice {
+    /// 2.0 plays twice as fast, an octave higher.
+    pub speed: f32,
+    /// -1.0 is all left, 1.0 all right.
+    pub pan: f32,
+}
+
+impl Voice {
+    /// The sound as it is.
+    pub(crate) const PLAIN: Voice = Voice { speed: 1., pan: 0. };
+
+    pub(crate) fn is_plain(&self) -> bool {
+        *self == Voice::PLAIN
+    }
+
+    /// With `speed` clamped to 1/16..=16: further than 4 octaves away the chunks
+    /// would take forever to decode, or be made of less than a frame.
+    pub(crate) fn with_speed(self, speed: f32) -> Voice {
+        let speed = if speed.is_nan() { 1. } else { speed };
+        Voice {
+            speed: speed.clamp(1. / 16., 16.),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_pan(self, pan: f32) -> Voice {
+        let pan = if pan.is_nan() { 0. } else { pan };
+        Voice {
+            pan: pan.clamp(-1., 1.),
+            ..self
+        }
+    }
+}
+
+/// Left and right gains of a constant power pan: the sum of their squares is 1,
+/// so that the sound is as loud in the middle as on the sides.
+fn pan_gains(pan: f32) -> (f32, f32) {
+    let angle = (pan.clamp(-1., 1.) + 1.) * std::f32::consts::FRAC_PI_4;
+    (angle.cos(), angle.sin())
+}
+
+/// Interleaved `samples` of `format` with the `voice` applied, as a WAV file.
+///
+/// The speed resamples them linearly. A pan makes a mono sound stereo and
+/// moves a stereo one towards a side, the sounds with more channels are not panned.
+/// The first `fade_in` and the last `fade_out` frames of `samples` fade,
+/// for two parts of a sound to crossfade.
+pub(crate) fn render(
+    format: Format,
+    samples: &[i16],
+    voice: Voice,
+    fade_in: usize,
+    fade_out: usize,
+) -> Vec<u8> {
+    let channels = format.channels as usize;
+    let frames = samples.len() / channels;
+    let speed = voice.speed as f64;
+
+    let rendered = if frames == 0 {
+        0
+    } else {
+        ((frames - 1) as f64 / speed) as usize + 1
+    };
+    let panned = voice.pan != 0. && channels <= 2;
+    let out_channels = if panned { 2 } else { channels };
+    // as loud as the sound itself in the middle
+    let (left, right) = pan_gains(voice.pan);
+    let (left, right) = (left * SQRT_2, right * SQRT_2);
+
+    let mut out = Vec::with_capacity(rendered * out_channels);
+    for i in 0..rendered {
+        let at = i as f64 * speed;
+        let frame = (at as usize).min(frames - 1);
+        let next = (frame + 1).min(frames - 1);
+        let t = (at - frame as f64) as f32;
+
+        let mut gain = 1f32;
+        if fade_in > 0 && at < fade_in as f64 {
+            gain = (at / fade_in as f64) as f32;
+        }
+        let from_end = (frames - 1) as f64 - at;
+        if fade_out > 0 && from_end < fade_out as f64 {
+            gain = gain.min((from_end / fade_out as f64) as f32);
+        }
+
+        let sample = |channel: usize| {
+            let a = samples[frame * channels + channel] as f32;
+            let b = samples[next * channels + channel] as f32;
+            (a + (b - a) * t) * gain
+        };
+        // `as` saturates
+        if panned {
+            out.push((sample(0) * left) as i16);
+            out.push((sample(channels - 1) * right) as i16);
+        } else {
+            out.extend((0..channels).map(|channel| sample(channel) as i16));
+        }
+    }
+    wav_16bit(out_channels as u16, format.sample_rate, &out)
+}
+
+/// A 16 bit PCM WAV file of the interleaved `samples`.
+pub(crate) fn wav_16bit(channels: u16, sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+    let data_size = samples.len() as u32 * 2;
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+    wav.extend(b"RIFF");
+    wav.extend((36 + data_size).to_le_bytes());
+    wav.extend(b"WAVEfmt ");
+    wav.extend(16u32.to_le_bytes());
+    wav.extend(1u16.to_le_bytes()); // PCM
+    wav.extend(channels.to_le_bytes());
+    wav.extend(sample_rate.to_le_bytes());
+    wav.extend((sample_rate * channels as u32 * 2).to_le_bytes());
+    wav.extend((channels * 2).to_le_bytes());
+    wav.extend(16u16.to_le_bytes());
+    wav.extend(b"data");
+    wav.extend(data_size.to_le_bytes());
+    for sample in samples {
+        wav.extend(sample.to_le_bytes());
+    }
+    wav
+}
+
+#[test]
+fn wav_duration() {
+    // 16 bit mono at 8000Hz, a quarter of a second
+    let samples = 2000u32;
+    let mut wav = vec![];
+    wav.extend(b"RIFF");
+    wav.extend((36 + 12 + samples * 2).to_le_bytes());
+    wav.extend(b"WAVEfmt ");
+    wav.extend(16u32.to_le_bytes());
+    wav.extend(1u16.to_le_bytes()); // PCM
+    wav.extend(1u16.to_le_bytes()); // channels
+    wav.extend(8000u32.to_le_bytes()); // sample rate
+    wav.extend(16000u32.to_le_bytes()); // byte rate
+    wav.extend(2u16.to_le_bytes()); // block align
+    wav.extend(16u16.to_le_bytes()); // bits per sample
+    wav.extend(b"LIST");
+    wav.extend(3u32.to_le_bytes());
+    wav.extend([0; 4]); // an odd sized chunk with its padding byte
+    wav.extend(b"data");
+    wav.extend((samples * 2).to_le_bytes());
+    wav.extend(vec![0; samples as usize * 2]);
+
+    assert_eq!(sound_duration(&wav), Some(0.25));
+    assert_eq!(sound_duration(b"not a sound"), None);
+}
+
+#[test]
+fn wav_decode() {
+    // 4 stereo frames at 4Hz, a second
+    let wav: Arc<[u8]> = wav_16bit(2, 4, &[0, 1, 2, 3, 4, 5, 6, 7]).into();
+    let mut decoder = Decoder::new(wav).unwrap();
+    assert_eq!(decoder.frames(), Some(4));
+
+    let mut samples = vec![];
+    assert_eq!(decoder.skip(1), 1);
+    assert_eq!(decoder.read(2, &mut samples), 2);
+    assert_eq!(samples, [2, 3, 4, 5]);
+    assert_eq!(decoder.read(2, &mut samples), 1);
+    assert_eq!(decoder.read(2, &mut samples), 0);
+
+    // looping over the end
+    decoder.rewind();
+    let mut part = Part::new(3, 3, true);
+    assert!(part.work(&mut decoder, usize::MAX));
+    assert_eq!(part.samples, [6, 7, 0, 1, 2, 3]);
+
+    // a one-shot ends the part early
+    decoder.rewind();
+    let mut part = Part::new(3, 3, false);
+    assert!(part.work(&mut decoder, usize::MAX));
+    assert_eq!(part.samples, [6, 7]);
+
+    assert!(Decoder::new(b"not a sound".to_vec().into()).is_none());
+}
+
+#[test]
+fn resampling() {
+    let format = Format {
+        channels: 1,
+        sample_rate: 4,
+    };
+    let samples = [0, 100, 200, 300, 400];
+    assert_eq!(
+        render(format, &samples, Voice::PLAIN, 0, 0),
+        wav_16bit(1, 4, &samples)
+    );
+    assert_eq!(
+        render(format, &samples, Voice::PLAIN.with_speed(2.), 0, 0),
+        wav_16bit(1, 4, &[0, 200, 400])
+    );
+    assert_eq!(
+        render(format, &samples, Voice::PLAIN.with_speed(0.5), 0, 0),
+        wav_16bit(1, 4, &[0, 50, 100, 150, 200, 250, 300, 350, 400])
+    );
+    // faded at both ends
+    assert_eq!(
+        render(format, &samples, Voice::PLAIN, 2, 2),
+        wav_16bit(1, 4, &[0, 50, 200, 150, 0])
+    );
+}
+
+#[test]
+fn constant_power_pan() {
+    for pan in [-1., -0.5, 0., 0.3, 1.] {
+        let (left, right) = pan_gains(pan);
+        assert!((left * left + right * right - 1.).abs() < 1e-6);
+    }
+    assert_eq!(pan_gains(-1.).1, 0.);
+    assert!(pan_gains(1.).0.abs() < 1e-6);
+
+    // mono made stereo, as loud as it is in the middle
+    let format = Format {
+        channels: 1,
+        sample_rate: 4,
+    };
+    let left = Voice::PLAIN.with_pan(-1.);
+    assert_eq!(
+        render(format, &[1000, -1000], left, 0, 0),
+        wav_16bit(2, 4, &[1414, 0, -1414, 0])
+    );
+    let middle = Voice::PLAIN.with_pan(-0.).with_speed(2.);
+    assert_eq!(
+        render(format, &[1000, 0, -1000], middle, 0, 0),
+        wav_16bit(1, 4, &[1000, -1000])
+    );
+}
@@ -0,0 +1,545 @@
+//! Playing a sound through the audio backend a couple of seconds at a time.
+
+use super::{
+    decode::{render, Decoder, Format, Part, Voice},
+    is_loaded, PlaySoundParams, QuadSndContext, QuadSndPlayback, QuadSndSound, QuadSndSoundGuarded,
+};
+use std::sync::{Arc, Mutex};
+
+/// Seconds of playback handed to the backend at once.
+const CHUNK: f64 = 2.;
+
+/// Seconds of the first chunk, short to start soon.
+const FIRST_CHUNK: f64 = 0.25;
+
+/// Seconds two chunks overlap to crossfade, and a replaced playback takes to fade out.
+const CROSSFADE: f64 = 0.05;
+
+/// Frames decoded every frame on the web, where there are no threads to decode in.
+#[cfg(target_arch = "wasm32")]
+const DECODED_PER_FRAME: usize = 8192;
+
+/// Decoding the next part of a [Stream] without blocking the frame:
+/// in a thread, or a bit every frame on the web.
+enum Decoding {
+    Idle(Decoder),
+    #[cfg(not(target_arch = "wasm32"))]
+    Thread(Arc<Mutex<Option<(Decoder, Part)>>>),
+    #[cfg(target_arch = "wasm32")]
+    Steps(Decoder, Part),
+    /// The decoding thread panicked, the stream ends with what it has.
+    Failed,
+}
+
+impl Decoding {
+    fn is_idle(&self) -> bool {
+        matches!(self, Decoding::Idle(_))
+    }
+
+    fn start(&mut self, part: Part) {
+        let Decoding::Idle(mut decoder) = std::mem::replace(self, Decoding::Failed) else {
+            panic!("already decoding");
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let result = Arc::new(Mutex::new(None));
+            {
+                let result = result.clone();
+                let mut part = part;
+                std::thread::spawn(move || {
+                    part.work(&mut decoder, usize::MAX);
+                    *result.lock().unwrap() = Some((decoder, part));
+                });
+            }
+            *self = Decoding::Thread(result);
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            *self = Decoding::Steps(decoder, part);
+        }
+    }
+
+    /// The part once it is decoded, None while it is not,
+    /// and when the decoding failed: see [Decoding::Failed].
+    fn poll(&mut self) -> Option<Part> {
+        let (decoder, part) = match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Decoding::Thread(result) => {
+                let done = result.lock().unwrap().take();
+                match done {
+                    Some(done) => done,
+                    // the thread is gone without a result
+                    None if Arc::strong_count(result) == 1 => {
+                        *self = Decoding::Failed;
+                        return None;
+                    }
+                    None => return None,
+                }
+            }
+            #[cfg(target_arch = "wasm32")]
+            Decoding::Steps(decoder, part) => {
+                if !part.work(decoder, DECODED_PER_FRAME) {
+                    return None;
+                }
+                let Decoding::Steps(decoder, part) = std::mem::replace(self, Decoding::Failed)
+                else {
+                    unreachable!()
+                };
+                (decoder, part)
+            }
+            _ => return None,
+        };
+        *self = Decoding::Idle(decoder);
+        Some(part)
+    }
+}
+
+/// A part of the stream loaded in the backend: frames `from..to`, with the voice applied.
+struct Chunk {
+    sound: QuadSndSoundGuarded,
+    from: u64,
+    to: u64,
+    speed: f32,
+    /// When the backend started playing it. While it is queued, when it should,
+    /// None for as soon as it is loaded.
+    start: Option<f64>,
+    playback: Option<QuadSndPlayback>,
+}
+
+impl Chunk {
+    /// Frames of the sound played per second.
+    fn rate(&self, format: Format) -> f64 {
+        format.sample_rate as f64 * self.speed as f64
+    }
+
+    fn end(&self, format: Format) -> Option<f64> {
+        Some(self.start? + (self.to - self.from) as f64 / self.rate(format))
+    }
+
+    fn frame_at(&self, format: Format, now: f64) -> u64 {
+        let played = self.start.map_or(0., |start| (now - start).max(0.));
+        (self.from + (played * self.rate(format)).round() as u64).min(self.to)
+    }
+}
+
+/// A playback replaced by a cut, fading out once the stream plays again.
+struct Fading {
+    playback: QuadSndPlayback,
+    /// The chunk it plays, None for the sound itself.
+    sound: Option<QuadSndSoundGuarded>,
+    gain: f32,
+}
+
+/// A sound played a chunk at a time: the backend only plays whole sounds from
+/// the start, as they are in the file.
+///
+/// The sound is decoded ahead, a chunk at a time, off the main thread. Each chunk gets
+/// the [Voice] applied and is loaded in the backend as a sound of its own, to start
+/// right when the previous one reaches its end. The chunks overlap by [CROSSFADE]
+/// with their ends faded, so that the starting time being off by a bit is not heard.
+/// Looped sounds go on from the start within a chunk, the loop itself has no gap.
+///
+/// Only what is decoded ahead stays in memory, along with the file. Changing the
+/// voice cuts to a new chunk from where the stream is, fading out the old ones.
+///
+/// Frames count from the start of the sound up, on and on past its end for the looped ones.
+pub(super) struct Stream {
+    format: Format,
+    /// Frames in the whole sound, to wrap the position of the looped ones.
+    frames: Option<u64>,
+    looped: bool,
+    voice: Voice,
+    volume: f32,
+    decoding: Decoding,
+    /// Frames asked of the decoding in progress, 0 when there is none.
+    requested: usize,
+    /// Decoded frames still needed, the first of them is frame `input_from`.
+    input: Vec<i16>,
+    input_from: u64,
+    /// The decoding reached the end of a one-shot, the input ends there.
+    ended: bool,
+    /// The first frame of the next chunk.
+    next: u64,
+    /// The next chunk crossfades with the last one, otherwise it fades in when `fade_in`.
+    crossfade: bool,
+    fade_in: bool,
+    /// The next chunk is a [FIRST_CHUNK], to start soon.
+    short: bool,
+    /// After a cut, when and at which speed the stream was at `next`:
+    /// it goes on meanwhile, the next chunk catches up.
+    cut: Option<(f64, f32)>,
+    queued: Option<Chunk>,
+    playing: Vec<Chunk>,
+    fading: Vec<Fading>,
+    /// The voice to cut to once the queued chunk starts.
+    pending: Option<Voice>,
+}
+
+impl Stream {
+    /// A stream from `position` seconds into the sound, or None when the format can't be
+    /// decoded on macroquad's side. It fades in when `fade_in`, to not click in the middle.
+    pub(super) fn new(
+        data: Arc<[u8]>,
+        position: f64,
+        voice: Voice,
+        looped: bool,
+        volume: f32,
+        fade_in: bool,
+    ) -> Option<Stream> {
+        let decoder = Decoder::new(data)?;
+        let format = decoder.format();
+        let frames = decoder.frames();
+
+        let mut first = (position.max(0.) * format.sample_rate as f64) as u64;
+        match frames {
+            Some(frames) if looped && frames > 0 => first %= frames,
+            Some(frames) => first = first.min(frames),
+            None => {}
+        }
+
+        let mut stream = Stream {
+            format,
+            frames,
+            looped,
+            voice,
+            volume,
+            decoding: Decoding::Idle(decoder),
+            requested: 0,
+            input: vec![],
+            input_from: first,
+            ended: false,
+            next: first,
+            crossfade: false,
+            fade_in,
+            short: true,
+            cut: None,
+            queued: None,
+            playing: vec![],
+            fading: vec![],
+            pending: None,
+        };
+        stream.requested = stream.chunk_frames(FIRST_CHUNK);
+        stream
+            .decoding
+            .start(Part::new(first, stream.requested, looped));
+        Some(stream)
+    }
+
+    /// Fade `playback` out once the stream starts, it plays the sound itself as it is.
+    /// The stream goes on from where it is by then.
+    pub(super) fn replace(&mut self, playback: QuadSndPlayback, now: f64) {
+        self.fading.push(Fading {
+            playback,
+            sound: None,
+            gain: 1.,
+        });
+        self.cut = Some((now, 1.));
+    }
+
+    /// Fade the playbacks of `old` out once this one starts, for a seek.
+    /// This one goes on from its position meanwhile.
+    pub(super) fn take_over(
+        &mut self,
+        mut old: Stream,
+        now: f64,
+        garbage: &mut Vec<QuadSndSoundGuarded>,
+    ) {
+        self.fade_out(std::mem::take(&mut old.playing), garbage);
+        self.fading.append(&mut old.fading);
+        garbage.extend(old.queued.take().map(|chunk| chunk.sound));
+        self.cut = Some((now, self.voice.speed));
+    }
+
+    fn fade_out(&mut self, chunks: Vec<Chunk>, garbage: &mut Vec<QuadSndSoundGuarded>) {
+        for chunk in chunks {
+            match chunk.playback {
+                Some(playback) => self.fading.push(Fading {
+                    playback,
+                    sound: Some(chunk.sound),
+                    gain: 1.,
+                }),
+                None => garbage.push(chunk.sound),
+            }
+        }
+    }
+
+    /// Frames of the sound in `seconds` of playback.
+    fn chunk_frames(&self, seconds: f64) -> usize {
+        (seconds * self.format.sample_rate as f64 * self.voice.speed as f64).ceil() as usize
+    }
+
+    fn crossfade_frames(&self) -> u64 {
+        (CROSSFADE * self.format.sample_rate as f64 * self.voice.speed as f64).ceil() as u64
+    }
+
+    fn input_end(&self) -> u64 {
+        self.input_from + (self.input.len() / self.format.channels as usize) as u64
+    }
+
+    /// Where `next` is by `now` after a cut.
+    fn caught_up(&self, now: f64) -> u64 {
+        match self.cut {
+            Some((at, speed)) => {
+                let elapsed = (now - at).max(0.) * self.format.sample_rate as f64 * speed as f64;
+                self.next + elapsed.round() as u64
+            }
+            None => self.next,
+        }
+    }
+
+    /// The frame playing, from the last chunk started.
+    fn frame(&self, now: f64) -> u64 {
+        match self.playing.last() {
+            Some(chunk) => chunk.frame_at(self.format, now),
+            None => self.caught_up(now),
+        }
+    }
+
+    /// Seconds into the sound, wrapped for the looped ones.
+    pub(super) fn position(&self, now: f64) -> f64 {
+        let frame = match self.frames {
+            Some(frames) if self.looped && frames > 0 => self.frame(now) % frames,
+            Some(frames) => self.frame(now).min(frames),
+            None => self.frame(now),
+        };
+        frame as f64 / self.format.sample_rate as f64
+    }
+
+    /// Whether a one-shot played all of its chunks.
+    pub(super) fn is_over(&self, now: f64) -> bool {
+        self.ended
+            && self.queued.is_none()
+            && self.caught_up(now) >= self.input_end()
+            && self
+                .playing
+                .iter()
+                .all(|chunk| chunk.end(self.format).is_some_and(|end| now >= end))
+    }
+
+    pub(super) fn set_volume(&mut self, ctx: &mut QuadSndContext, volume: f32) {
+        self.volume = volume;
+        for chunk in &self.playing {
+            if let Some(playback) = &chunk.playback {
+                playback.set_volume(ctx, volume);
+            }
+        }
+        for fading in &self.fading {
+            fading.playback.set_volume(ctx, volume * fading.gain);
+        }
+    }
+
+    /// Cut to `voice` from where the stream is.
+    pub(super) fn set_voice(
+        &mut self,
+        voice: Voice,
+        now: f64,
+        garbage: &mut Vec<QuadSndSoundGuarded>,
+    ) {
+        // a chunk waiting to load would be thrown away before it plays,
+        // on and on with a change every frame
+        if self
+            .queued
+            .as_ref()
+            .is_some_and(|chunk| chunk.start.is_none())
+        {
+            self.pending = Some(voice);
+            return;
+        }
+        self.pending = None;
+        if voice == self.voice {
+            return;
+        }
+
+        let frame = self.frame(now).max(self.input_from);
+        let speed = match (self.playing.last(), self.cut) {
+            (Some(chunk), _) => chunk.speed,
+            (None, Some((_, speed))) => speed,
+            (None, None) => self.voice.speed,
+        };
+        let playing = std::mem::take(&mut self.playing);
+        self.fade_out(playing, garbage);
+        garbage.extend(self.queued.take().map(|chunk| chunk.sound));
+
+        self.voice = voice;
+        self.next = frame;
+        self.cut = Some((now, speed));
+        self.crossfade = false;
+        self.short = true;
+        self.fade_in = true;
+    }
+
+    /// Stop it in the backend, see [Garbage](super::Garbage) for the sounds.
+    pub(super) fn stop(
+        &mut self,
+        ctx: &mut QuadSndContext,
+        garbage: &mut Vec<QuadSndSoundGuarded>,
+    ) {
+        for chunk in self.playing.drain(..).chain(self.queued.take()) {
+            if let Some(playback) = chunk.playback {
+                playback.stop(ctx);
+            }
+            garbage.push(chunk.sound);
+        }
+        for fading in self.fading.drain(..) {
+            fading.playback.stop(ctx);
+            garbage.extend(fading.sound);
+        }
+        self.ended = true;
+        self.input = vec![];
+        self.input_from = self.next;
+    }
+
+    /// Once a frame: take the decoded part, start the next chunk when it's time,
+    /// fade out the replaced playbacks, and decode and render ahead.
+    pub(super) fn update(
+        &mut self,
+        ctx: &mut QuadSndContext,
+        now: f64,
+        dt: f64,
+        garbage: &mut Vec<QuadSndSoundGuarded>,
+    ) {
+        let channels = self.format.channels as usize;
+
+        if let Some(part) = self.decoding.poll() {
+            if part.samples.len() / channels < self.requested {
+                self.ended = true;
+            }
+            self.input.extend_from_slice(&part.samples);
+            self.requested = 0;
+        } else if matches!(self.decoding, Decoding::Failed) {
+            self.ended = true;
+            self.requested = 0;
+        }
+
+        let format = self.format;
+        let (ended, playing): (Vec<_>, _) = std::mem::take(&mut self.playing)
+            .into_iter()
+            .partition(|chunk| chunk.end(format).is_some_and(|end| now >= end));
+        self.playing = playing;
+        garbage.extend(ended.into_iter().map(|chunk| chunk.sound));
+
+        // what a cut or the next chunk may still need
+        let keep = self
+            .frame(now)
+            .min(self.next.saturating_sub(self.crossfade_frames()))
+            .clamp(self.input_from, self.input_end());
+        self.input
+            .drain(..(keep - self.input_from) as usize * channels);
+        self.input_from = keep;
+
+        let chunk = self.chunk_frames(CHUNK);
+        let ahead = self.caught_up(now) + 2 * chunk as u64;
+        if self.decoding.is_idle() && !self.ended && self.input_end() < ahead {
+            self.requested = chunk;
+            self.decoding.start(Part::new(0, chunk, self.looped));
+        }
+
+        if self.queued.is_none() {
+            self.render_next(ctx, now);
+        }
+
+        // started a bit early rather than late, off by half a frame at most
+        let ready = self.queued.as_ref().is_some_and(|chunk| {
+            is_loaded(&chunk.sound.0) && !chunk.start.is_some_and(|start| now < start - dt / 2.)
+        });
+        if ready {
+            let mut chunk = self.queued.take().unwrap();
+            let params = PlaySoundParams {
+                looped: false,
+                volume: self.volume,
+                ..Default::default()
+            };
+            chunk.playback = Some(chunk.sound.0.play(ctx, params.into()));
+            chunk.start = Some(now);
+            self.playing.push(chunk);
+
+            if let Some(voice) = self.pending.take() {
+                self.set_voice(voice, now, garbage);
+            }
+        }
+
+        if !self.playing.is_empty() {
+            let step = (dt / CROSSFADE) as f32;
+            for mut fading in std::mem::take(&mut self.fading) {
+                fading.gain -= step;
+                if fading.gain <= 0. {
+                    fading.playback.stop(ctx);
+                    garbage.extend(fading.sound);
+                } else {
+                    fading.playback.set_volume(ctx, self.volume * fading.gain);
+                    self.fading.push(fading);
+                }
+            }
+        }
+    }
+
+    /// Queue the chunk after the last one, once enough of it is decoded.
+    fn render_next(&mut self, ctx: &mut QuadSndContext, now: f64) {
+        let channels = self.format.channels as usize;
+        let seconds = if self.short { FIRST_CHUNK } else { CHUNK };
+        let chunk = self.chunk_frames(seconds) as u64;
+        let input_end = self.input_end();
+
+        let next = self.caught_up(now);
+        if !self.ended && input_end < next + chunk {
+            return;
+        }
+        let to = (next + chunk).min(input_end);
+        if to <= next {
+            return;
+        }
+
+        let crossfade = self.crossfade_frames();
+        let overlap = if self.crossfade {
+            crossfade.min(next - self.input_from)
+        } else {
+            0
+        };
+        let from = next - overlap;
+        let fade_in = match () {
+            _ if self.crossfade => overlap,
+            _ if self.fade_in => crossfade,
+            _ => 0,
+        };
+        // the end of a one-shot, nothing to crossfade with
+        let fade_out = if self.ended && to == input_end {
+            0
+        } else {
+            crossfade
+        };
+
+        let samples = &self.input[(from - self.input_from) as usize * channels
+            ..(to - self.input_from) as usize * channels];
+        let wav = render(
+            self.format,
+            samples,
+            self.voice,
+            fade_in as usize,
+            fade_out as usize,
+        );
+
+        // right when the last chunk reaches the overlap
+        let start = match self.playing.last() {
+            Some(last) if self.crossfade => last
+                .start
+                .map(|start| start + (from - last.from) as f64 / last.rate(self.format)),
+            _ => None,
+        };
+        self.queued = Some(Chunk {
+            sound: QuadSndSoundGuarded(QuadSndSound::load(ctx, &wav)),
+            from,
+            to,
+            speed: self.voice.speed,
+            start,
+            playback: None,
+        });
+
+        self.next = to;
+        self.crossfade = true;
+        self.short = false;
+        self.fade_in = false;
+        self.cut = None;
+    }
+}
@@ -5,9 +5,9 @@ use crate::{
     math::Rect,
     prelude::RenderPass,
     texture::RenderTarget,
-    window::{screen_height, screen_width},
+    window::{dpi_scale, screen_height, screen_width},
 };
-use glam::{vec2, vec3, Mat4, Vec2, Vec3};
+use glam::{vec2, vec3, Mat4, Vec2, Vec3, Vec4Swizzles};
 
 pub trait Camera {
     fn matrix(&self) -> Mat4;
@@ -121,46 +121,286 @@ impl Camera2D {
     /// Returns the screen space position for a 2d camera world space position.
     ///
     /// Screen position in window space - from (0, 0) to (screen_width, screen_height()).
+    /// With a render target - in pixels of the target, (0, 0) is the top left corner
+    /// when the target is drawn with `flip_y: true`.
     pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
-        let mat = self.matrix();
-        let transform = mat.transform_point3(vec3(point.x, point.y, 0.));
+        let ndc = self.matrix().transform_point3(vec3(point.x, point.y, 0.));
 
-        vec2(
-            (transform.x / 2. + 0.5) * screen_width(),
-            (0.5 - transform.y / 2.) * screen_height(),
+        ndc_to_screen(
+            screen_rect(self.render_target.as_ref(), self.viewport),
+            ndc.truncate(),
         )
     }
 
     /// Returns the world space position for a 2d camera screen space position.
     ///
     /// Point is a screen space position, often mouse x and y.
+    /// Same coordinates as in [Camera2D::world_to_screen].
+    ///
+    /// This is the inverse of [Camera2D::world_to_screen], with the camera rotation, zoom,
+    /// offset, viewport and render target size taken into account.
     pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
-        let dims = self
-            .viewport()
-            .map(|(vx, vy, vw, vh)| Rect {
-                x: vx as f32,
-                y: screen_height() - (vy + vh) as f32,
-                w: vw as f32,
-                h: vh as f32,
-            })
-            .unwrap_or(Rect {
-                x: 0.0,
-                y: 0.0,
-                w: screen_width(),
-                h: screen_height(),
-            });
-
-        let point = vec2(
-            (point.x - dims.x) / dims.w * 2. - 1.,
-            1. - (point.y - dims.y) / dims.h * 2.,
+        let ndc = screen_to_ndc(
+            screen_rect(self.render_target.as_ref(), self.viewport),
+            point,
         );
         let inv_mat = self.matrix().inverse();
-        let transform = inv_mat.transform_point3(vec3(point.x, point.y, 0.));
+        let transform = inv_mat.transform_point3(vec3(ndc.x, ndc.y, 0.));
 
         vec2(transform.x, transform.y)
     }
 }
 
+/// Moves a [Camera2D] after a target, keeping the view inside the level.
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # async fn f(player: Vec2) {
+/// let mut controller = CameraController::new(Camera2D::from_display_rect(Rect::new(
+///     0.,
+///     0.,
+///     screen_width(),
+///     screen_height(),
+/// )));
+/// controller.set_bounds(Rect::new(0., 0., 4000., 3000.));
+///
+/// loop {
+///     controller.follow(player, 5., get_frame_time());
+///     set_camera(&controller.camera);
+///     // ...
+///     next_frame().await
+/// }
+/// # }
+/// ```
+///
+/// The controller is a [Camera] itself: `set_camera(&controller)` uses the camera
+/// with the [CameraShake] applied on top, while `controller.camera` stays unaffected by it.
+#[derive(Debug)]
+pub struct CameraController {
+    pub camera: Camera2D,
+    pub shake: CameraShake,
+    bounds: Option<Rect>,
+    deadzone: Option<Rect>,
+}
+
+impl CameraController {
+    pub fn new(camera: Camera2D) -> CameraController {
+        CameraController {
+            camera,
+            shake: CameraShake::default(),
+            bounds: None,
+            deadzone: None,
+        }
+    }
+
+    /// World space rect the camera should never look outside of.
+    ///
+    /// If the level is smaller than the view, the camera is centered on it instead.
+    /// Camera rotation is not taken into account.
+    pub fn set_bounds(&mut self, bounds: Rect) {
+        self.bounds = Some(bounds);
+    }
+
+    /// World space rect, relative to the camera target, the followed point may move
+    /// inside of without moving the camera.
+    ///
+    /// `Rect::new(-50., -30., 100., 60.)` lets the point move 50 units left and right
+    /// and 30 units up and down from the center of the view.
+    pub fn set_deadzone(&mut self, deadzone: Rect) {
+        self.deadzone = Some(deadzone);
+    }
+
+    /// Moves `camera.target` towards `target`.
+    ///
+    /// `lerp` is how fast the camera catches up, independent of the frame rate:
+    /// each second the remaining distance shrinks `e^lerp` times.
+    /// `f32::INFINITY` moves the camera right to the target.
+    /// Otherwise the camera stays in place while `dt` is zero, e.g. with `set_time_scale(0.)`.
+    pub fn follow(&mut self, target: Vec2, lerp: f32, dt: f32) {
+        if dt <= 0. && lerp.is_finite() {
+            return;
+        }
+
+        let current = self.camera.target;
+
+        let mut desired = target;
+        if let Some(deadzone) = self.deadzone {
+            desired = current;
+            let relative = target - current;
+            if relative.x < deadzone.left() {
+                desired.x = target.x - deadzone.left();
+            } else if relative.x > deadzone.right() {
+                desired.x = target.x - deadzone.right();
+            }
+            if relative.y < deadzone.top() {
+                desired.y = target.y - deadzone.top();
+            } else if relative.y > deadzone.bottom() {
+                desired.y = target.y - deadzone.bottom();
+            }
+        }
+
+        // -inf * 0 is NaN, an infinite lerp gets the exact answer instead
+        let t = if lerp.is_finite() {
+            (1. - (-lerp * dt).exp()).clamp(0., 1.)
+        } else {
+            1.
+        };
+        self.camera.target = current + (desired - current) * t;
+
+        if let Some(bounds) = self.bounds {
+            self.camera.target = self.clamp_to_bounds(self.camera.target, bounds);
+        }
+    }
+
+    /// Starts shaking the camera, or makes the current shake stronger.
+    /// The total trauma is capped at 1.0.
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.shake.trauma = (self.shake.trauma + trauma).clamp(0., 1.);
+    }
+
+    /// Advances the shake animation and decays the trauma, should be called once a frame.
+    pub fn update_shake(&mut self, dt: f32) {
+        let shake = &mut self.shake;
+        shake.trauma = (shake.trauma - dt / shake.duration).max(0.);
+        if shake.trauma == 0. {
+            shake.time = 0.;
+        } else {
+            shake.time += dt;
+        }
+    }
+
+    fn clamp_to_bounds(&self, target: Vec2, bounds: Rect) -> Vec2 {
+        let camera = &self.camera;
+        let invert_y = if camera.render_target.is_some() {
+            1.0
+        } else {
+            -1.0
+        };
+        let scale = vec2(camera.zoom.x, camera.zoom.y * invert_y);
+
+        // distance from the target to the view edges, same math as in Camera2D::matrix
+        let a = (vec2(-1., -1.) - camera.offset) / scale;
+        let b = (vec2(1., 1.) - camera.offset) / scale;
+        let (near, far) = (a.min(b), a.max(b));
+
+        let min = vec2(bounds.left(), bounds.top()) - near;
+        let max = vec2(bounds.right(), bounds.bottom()) - far;
+        let center = (min + max) / 2.;
+
+        vec2(
+            if min.x <= max.x {
+                target.x.clamp(min.x, max.x)
+            } else {
+                center.x
+            },
+            if min.y <= max.y {
+                target.y.clamp(min.y, max.y)
+            } else {
+                center.y
+            },
+        )
+    }
+}
+
+impl Camera for CameraController {
+    fn matrix(&self) -> Mat4 {
+        let matrix = self.camera.matrix();
+        let (offset, angle) = self.shake.current();
+        if offset == Vec2::ZERO && angle == 0. {
+            return matrix;
+        }
+
+        // shake in pixels around the center of the view, so rotation is not skewed by the aspect
+        let rect = screen_rect(self.camera.render_target.as_ref(), self.camera.viewport);
+        let half_size = vec3(rect.w / 2., rect.h / 2., 1.);
+        let shake = Mat4::from_translation(offset.extend(0.))
+            * Mat4::from_axis_angle(vec3(0., 0., 1.), angle.to_radians());
+
+        Mat4::from_scale(half_size.recip()) * shake * Mat4::from_scale(half_size) * matrix
+    }
+
+    fn depth_enabled(&self) -> bool {
+        self.camera.depth_enabled()
+    }
+
+    fn render_pass(&self) -> Option<RenderPass> {
+        self.camera.render_pass()
+    }
+
+    fn viewport(&self) -> Option<(i32, i32, i32, i32)> {
+        self.camera.viewport()
+    }
+}
+
+/// Trauma based screen shake of a [CameraController].
+///
+/// The shake strength is trauma squared, so small hits give a barely noticeable shake
+/// and it fades out smoothly.
+#[derive(Debug, Clone)]
+pub struct CameraShake {
+    /// Largest displacement, in pixels. (16.0, 16.0) by default.
+    pub max_offset: Vec2,
+    /// Largest rotation in degrees, 3.0 by default.
+    pub max_angle: f32,
+    /// How fast the camera wobbles, in noise periods per second. 15.0 by default.
+    pub frequency: f32,
+    /// Seconds it takes a full 1.0 trauma to decay. 1.0 by default.
+    pub duration: f32,
+    trauma: f32,
+    time: f32,
+}
+
+impl Default for CameraShake {
+    fn default() -> CameraShake {
+        CameraShake {
+            max_offset: vec2(16., 16.),
+            max_angle: 3.,
+            frequency: 15.,
+            duration: 1.,
+            trauma: 0.,
+            time: 0.,
+        }
+    }
+}
+
+impl CameraShake {
+    /// Current trauma, from 0.0 to 1.0.
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Offset in pixels and rotation in degrees for this frame.
+    fn current(&self) -> (Vec2, f32) {
+        let strength = self.trauma * self.trauma;
+        if strength == 0. {
+            return (Vec2::ZERO, 0.);
+        }
+
+        let t = self.time * self.frequency;
+        let offset = vec2(smooth_noise(0, t), smooth_noise(1, t)) * self.max_offset;
+        let angle = smooth_noise(2, t) * self.max_angle;
+
+        (offset * strength, angle * strength)
+    }
+}
+
+/// 1D value noise from -1.0 to 1.0, smoothly changing between integer `t`s.
+fn smooth_noise(seed: u32, t: f32) -> f32 {
+    let hash = |n: i32| {
+        let mut x = (n as u32).wrapping_mul(0x9e37_79b1) ^ seed.wrapping_mul(0x85eb_ca6b);
+        x ^= x >> 15;
+        x = x.wrapping_mul(0x2c1b_3c6d);
+        x ^= x >> 12;
+        x as f32 / u32::MAX as f32 * 2. - 1.
+    };
+
+    let i = t.floor();
+    let f = t - i;
+    let (a, b) = (hash(i as i32), hash(i as i32 + 1));
+
+    a + (b - a) * f * f * (3. - 2. * f)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Projection {
     Perspective,
@@ -180,7 +420,8 @@ pub struct Camera3D {
     pub fovy: f32,
     /// Screen aspect ratio.
     ///
-    /// By default aspect is calculated with screen_width() / screen_height() on each frame.
+    /// By default aspect is calculated on each frame from the size of the viewport,
+    /// or the render target, or the screen.
     pub aspect: Option<f32>,
     /// Camera projection type, perspective or orthographics.
     pub projection: Projection,
@@ -224,7 +465,10 @@ impl Default for Camera3D {
 
 impl Camera for Camera3D {
     fn matrix(&self) -> Mat4 {
-        let aspect = self.aspect.unwrap_or(screen_width() / screen_height());
+        let aspect = self.aspect.unwrap_or_else(|| {
+            let rect = screen_rect(self.render_target.as_ref(), self.viewport);
+            rect.w / rect.h
+        });
 
         match self.projection {
             Projection::Perspective => {
@@ -254,7 +498,88 @@ impl Camera for Camera3D {
     }
 }
 
+impl Camera3D {
+    /// Returns the screen space position of a 3d world space point,
+    /// or None if the point is behind the camera.
+    ///
+    /// Same screen coordinates as in [Camera2D::world_to_screen].
+    pub fn world_to_screen(&self, point: Vec3) -> Option<Vec2> {
+        let clip = self.matrix() * point.extend(1.);
+        if clip.w <= 0. {
+            return None;
+        }
+
+        Some(ndc_to_screen(
+            screen_rect(self.render_target.as_ref(), self.viewport),
+            clip.xy() / clip.w,
+        ))
+    }
+
+    /// Returns a world space ray going through the screen space point, often mouse x and y.
+    ///
+    /// The ray starts at the near plane, the direction is normalized.
+    pub fn screen_to_ray(&self, screen_point: Vec2) -> (Vec3, Vec3) {
+        let ndc = screen_to_ndc(
+            screen_rect(self.render_target.as_ref(), self.viewport),
+            screen_point,
+        );
+        let inv_mat = self.matrix().inverse();
+        let near = inv_mat.project_point3(ndc.extend(-1.));
+        let far = inv_mat.project_point3(ndc.extend(1.));
+
+        (near, (far - near).normalize())
+    }
+}
+
+/// The part of the screen or the render target the camera draws to,
+/// in top left origin coordinates: logical pixels for the screen, pixels for a render target.
+fn screen_rect(
+    render_target: Option<&RenderTarget>,
+    viewport: Option<(i32, i32, i32, i32)>,
+) -> Rect {
+    let (width, height, scale) = match render_target {
+        Some(render_target) => (
+            render_target.texture.width(),
+            render_target.texture.height(),
+            1.,
+        ),
+        // viewports are in physical pixels
+        None => (screen_width(), screen_height(), dpi_scale()),
+    };
+
+    match viewport {
+        Some((x, y, w, h)) => {
+            let (x, y, w, h) = (
+                x as f32 / scale,
+                y as f32 / scale,
+                w as f32 / scale,
+                h as f32 / scale,
+            );
+            // viewport's origin is at the bottom left
+            Rect::new(x, height - (y + h), w, h)
+        }
+        None => Rect::new(0., 0., width, height),
+    }
+}
+
+fn ndc_to_screen(rect: Rect, ndc: Vec2) -> Vec2 {
+    vec2(
+        rect.x + (ndc.x / 2. + 0.5) * rect.w,
+        rect.y + (0.5 - ndc.y / 2.) * rect.h,
+    )
+}
+
+fn screen_to_ndc(rect: Rect, point: Vec2) -> Vec2 {
+    vec2(
+        (point.x - rect.x) / rect.w * 2. - 1.,
+        1. - (point.y - rect.y) / rect.h * 2.,
+    )
+}
+
 /// Set active 2D or 3D camera.
+///
+/// With a viewport, all the drawing is also clipped to it,
+/// in addition to the [set_scissor](crate::window::set_scissor) rectangle.
 pub fn set_camera(camera: &dyn Camera) {
     let context = get_context();
 
@@ -268,6 +593,16 @@ pub fn set_camera(camera: &dyn Camera) {
     context.gl.viewport(camera.viewport());
     context.gl.depth_test(camera.depth_enabled());
     context.camera_matrix = Some(camera.matrix());
+
+    context.viewport_scissor = camera.viewport().map(|(x, y, w, h)| {
+        let target_height = match camera.render_pass() {
+            Some(render_pass) => render_pass.color_texture.height(),
+            None => miniquad::window::screen_size().1,
+        };
+        // viewport's origin is at the bottom left, scissor's at the top left
+        (x, target_height as i32 - (y + h), w, h)
+    });
+    context.apply_scissor();
 }
 
 /// Reset default 2D camera mode.
@@ -281,10 +616,15 @@ pub fn set_default_camera() {
     context.gl.viewport(None);
     context.gl.depth_test(false);
     context.camera_matrix = None;
+
+    context.viewport_scissor = None;
+    context.apply_scissor();
 }
 
 pub(crate) struct CameraState {
     render_pass: Option<miniquad::RenderPass>,
+    viewport: Option<(i32, i32, i32, i32)>,
+    viewport_scissor: Option<(i32, i32, i32, i32)>,
     depth_test: bool,
     matrix: Option<Mat4>,
 }
@@ -294,6 +634,8 @@ pub fn push_camera_state() {
 
     let camera_state = CameraState {
         render_pass: context.gl.get_active_render_pass(),
+        viewport: context.gl.get_active_viewport(),
+        viewport_scissor: context.viewport_scissor,
         depth_test: context.gl.is_depth_test_enabled(),
         matrix: context.camera_matrix,
     };
@@ -307,7 +649,81 @@ pub fn pop_camera_state() {
         context.perform_render_passes();
 
         context.gl.render_pass(camera_state.render_pass);
+        context.gl.viewport(camera_state.viewport);
         context.gl.depth_test(camera_state.depth_test);
         context.camera_matrix = camera_state.matrix;
+
+        context.viewport_scissor = camera_state.viewport_scissor;
+        context.apply_scissor();
+    }
+}
+
+#[test]
+fn camera_controller_bounds() {
+    // 200x100 world units visible, target in the center of the view
+    let mut controller =
+        CameraController::new(Camera2D::from_display_rect(Rect::new(0., 0., 200., 100.)));
+    controller.set_bounds(Rect::new(0., 0., 1000., 500.));
+
+    controller.follow(vec2(-300., 800.), f32::INFINITY, 0.016);
+    assert_eq!(controller.camera.target, vec2(100., 450.));
+
+    controller.follow(vec2(500., 250.), f32::INFINITY, 0.016);
+    assert_eq!(controller.camera.target, vec2(500., 250.));
+
+    // zooming in shows half as much, so the target can get closer to the edge
+    controller.camera.zoom *= 2.;
+    controller.follow(vec2(0., 0.), f32::INFINITY, 0.016);
+    assert_eq!(controller.camera.target, vec2(50., 25.));
+
+    // a level smaller than the view is centered
+    controller.set_bounds(Rect::new(10., 10., 20., 20.));
+    controller.follow(vec2(0., 0.), f32::INFINITY, 0.016);
+    assert_eq!(controller.camera.target, vec2(20., 20.));
+
+    // the camera does not move while the point is inside the deadzone
+    let mut controller = CameraController::new(Camera2D::default());
+    controller.set_deadzone(Rect::new(-10., -10., 20., 20.));
+    controller.follow(vec2(5., -5.), f32::INFINITY, 0.016);
+    assert_eq!(controller.camera.target, vec2(0., 0.));
+    controller.follow(vec2(15., 0.), f32::INFINITY, 0.016);
+    assert_eq!(controller.camera.target, vec2(5., 0.));
+}
+
+#[test]
+fn camera_controller_zero_dt() {
+    let mut controller = CameraController::new(Camera2D::default());
+
+    // first frame, or paused time
+    controller.follow(vec2(10., 20.), 5., 0.);
+    assert_eq!(controller.camera.target, vec2(0., 0.));
+
+    controller.follow(vec2(10., 20.), f32::INFINITY, 0.);
+    assert_eq!(controller.camera.target, vec2(10., 20.));
+
+    controller.follow(vec2(30., 40.), 5., 0.016);
+    assert!(controller.camera.target.is_finite());
+}
+
+#[test]
+fn camera_shake_decay() {
+    let mut controller = CameraController::new(Camera2D::default());
+    controller.camera.target = vec2(10., 20.);
+
+    controller.add_trauma(0.7);
+    controller.add_trauma(0.7);
+    assert_eq!(controller.shake.trauma(), 1.);
+
+    controller.update_shake(0.1);
+    let (offset, angle) = controller.shake.current();
+    assert!(offset.abs().cmple(controller.shake.max_offset).all());
+    assert!(angle.abs() <= controller.shake.max_angle);
+
+    // once decayed nothing is left, and the target was never touched
+    for _ in 0..100 {
+        controller.update_shake(1. / 60.);
     }
+    assert_eq!(controller.shake.trauma(), 0.);
+    assert_eq!(controller.shake.current(), (Vec2::ZERO, 0.));
+    assert_eq!(controller.camera.target, vec2(10., 20.));
 }
@@ -111,7 +111,8 @@ impl Color {
         )
     }
 
-    /// Build a color from a hexadecimal u32.
+    /// Build an opaque color from a hexadecimal `0xRRGGBB` u32,
+    /// the highest byte is ignored. See [Color::from_hex_rgba] for `0xRRGGBBAA`.
     ///
     /// # Example
     ///
@@ -130,6 +131,90 @@ impl Color {
         Self::from_rgba(bytes[1], bytes[2], bytes[3], 255)
     }
 
+    /// Build a color from a hexadecimal `0xRRGGBBAA` u32.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use macroquad::prelude::*;
+    ///
+    /// let translucent = Color::from_hex_rgba(0x3CA7D580);
+    /// assert_eq!(translucent.with_alpha(1.), Color::from_hex(0x3CA7D5));
+    /// assert_eq!(translucent.a, 128. / 255.);
+    /// ```
+    pub const fn from_hex_rgba(hex: u32) -> Color {
+        let bytes: [u8; 4] = hex.to_be_bytes();
+
+        Self::from_rgba(bytes[0], bytes[1], bytes[2], bytes[3])
+    }
+
+    /// The color as a `0xRRGGBB` u32, alpha is dropped.
+    ///
+    /// Components are clamped to 0.0..1.0 and rounded to the nearest step of 1/255,
+    /// so `Color::from_hex(color.to_hex())` gives back any color made with `from_hex`.
+    pub fn to_hex(&self) -> u32 {
+        self.to_hex_rgba() >> 8
+    }
+
+    /// The color as a `0xRRGGBBAA` u32, components are clamped and rounded
+    /// like in [Color::to_hex].
+    pub fn to_hex_rgba(&self) -> u32 {
+        let [r, g, b, a] =
+            [self.r, self.g, self.b, self.a].map(|c| (c.clamp(0., 1.) * 255.).round() as u8);
+
+        u32::from_be_bytes([r, g, b, a])
+    }
+
+    /// Build an opaque color from hue, saturation and value, all in 0..1.
+    /// The hue wraps around, so 1.2 is the same as 0.2.
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Color {
+        hsv_to_rgb(h, s, v)
+    }
+
+    /// Hue, saturation and value of the color, all in 0..1. Alpha is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use macroquad::prelude::*;
+    ///
+    /// // a lighter shade of the same hue
+    /// let (h, s, v) = Color::new(1., 0., 0., 1.).to_hsv();
+    /// assert_eq!(Color::from_hsv(h, s * 0.5, v), Color::new(1., 0.5, 0.5, 1.));
+    /// ```
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        rgb_to_hsv(*self)
+    }
+
+    /// Parse a `RRGGBB` or `RRGGBBAA` hexadecimal string, with or without a leading `#`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use macroquad::prelude::*;
+    ///
+    /// assert_eq!(Color::from_hex_str("#3CA7D5"), Some(Color::from_hex(0x3CA7D5)));
+    /// assert_eq!(Color::from_hex_str("ff000080").map(|c| c.a), Some(128. / 255.));
+    /// assert_eq!(Color::from_hex_str("#red"), None);
+    /// ```
+    pub fn from_hex_str(hex: &str) -> Option<Color> {
+        let hex = hex.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) || (hex.len() != 6 && hex.len() != 8) {
+            return None;
+        }
+
+        let channel = |n: usize| u8::from_str_radix(&hex[n * 2..n * 2 + 2], 16).ok();
+        let alpha = if hex.len() == 8 { channel(3)? } else { 255 };
+
+        Some(Color::from_rgba(
+            channel(0)?,
+            channel(1)?,
+            channel(2)?,
+            alpha,
+        ))
+    }
+
     /// Create a vec4 of red, green, blue, and alpha components.
     pub const fn to_vec(&self) -> glam::Vec4 {
         glam::Vec4::new(self.r, self.g, self.b, self.a)
@@ -267,3 +352,69 @@ pub fn rgb_to_hsl(color: Color) -> (f32, f32, f32) {
 
     (h, s, l)
 }
+
+/// Hue, saturation and value, all in 0..1, to an opaque color.
+pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> Color {
+    let h = (h - h.floor()) * 6.;
+    let c = v * s;
+    let x = c * (1. - (h % 2. - 1.).abs());
+    let m = v - c;
+
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.),
+        1 => (x, c, 0.),
+        2 => (0., c, x),
+        3 => (0., x, c),
+        4 => (x, 0., c),
+        _ => (c, 0., x),
+    };
+
+    Color::new(r + m, g + m, b + m, 1.0)
+}
+
+/// Hue, saturation and value of the color, all in 0..1.
+/// The hue of grays is 0.
+pub fn rgb_to_hsv(color: Color) -> (f32, f32, f32) {
+    let Color { r, g, b, .. } = color;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let s = if max == 0. { 0. } else { delta / max };
+    if delta == 0. {
+        return (0., s, max);
+    }
+
+    let h = if max == r {
+        (g - b) / delta
+    } else if max == g {
+        (b - r) / delta + 2.
+    } else {
+        (r - g) / delta + 4.
+    };
+
+    ((h / 6.).rem_euclid(1.), s, max)
+}
+
+#[test]
+fn hsv_round_trip() {
+    for color in [RED, GREEN, SKYBLUE, VIOLET, GRAY, WHITE, BLACK] {
+        let (h, s, v) = rgb_to_hsv(color);
+        let back = hsv_to_rgb(h, s, v);
+        for (a, b) in [(color.r, back.r), (color.g, back.g), (color.b, back.b)] {
+            assert!((a - b).abs() < 1e-5, "{color:?} -> {back:?}");
+        }
+    }
+    assert_eq!(hsv_to_rgb(1. / 3., 1., 1.), Color::new(0., 1., 0., 1.));
+}
+
+#[test]
+fn hex_round_trip() {
+    assert_eq!(Color::from_hex(0x3CA7D5).to_hex(), 0x3CA7D5);
+    assert_eq!(Color::from_hex_rgba(0x3CA7D580).to_hex_rgba(), 0x3CA7D580);
+    assert_eq!(Color::from_hex(0xFF3CA7D5), Color::from_hex(0x3CA7D5));
+
+    // out of range components are clamped
+    assert_eq!(Color::new(2., -1., 0.5, 1.).to_hex_rgba(), 0xFF0080FF);
+}
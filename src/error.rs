@@ -5,8 +5,38 @@ pub enum Error {
         kind: miniquad::fs::Error,
         path: String,
     },
+    /// A shader did not compile or the program did not link, with the driver's log.
     ShaderError(miniquad::ShaderError),
     ImageError(image::ImageError),
+    /// Sprites given to a texture atlas builder do not fit into its max size.
+    TextureAtlasOverflow {
+        max_size: u16,
+    },
+    /// The line of an `InputMap` text is not `name: bindings`, or has an unknown binding.
+    InputMapParseError {
+        line: usize,
+    },
+    /// A skin description is not valid json, or one of its values has the wrong type.
+    SkinParseError(String),
+    /// An image or a font of a skin style did not load.
+    SkinFileError {
+        /// Name of the style, like `button_style`.
+        style: String,
+        path: String,
+        source: Box<Error>,
+    },
+    /// A line of a model or material file, see [load_model](crate::models::load_model),
+    /// could not be read.
+    ModelParseError {
+        path: String,
+        /// From 1.
+        line: usize,
+        message: String,
+    },
+    /// A mesh has more vertices than its `u16` indices can point to.
+    MeshTooBig {
+        vertices: usize,
+    },
     UnknownError(&'static str),
 }
 
@@ -30,7 +60,25 @@ impl From<image::ImageError> for Error {
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "error: {self:?}")
+        match self {
+            // the driver's log as is, its line:column positions are the useful part
+            Error::ShaderError(miniquad::ShaderError::CompilationError {
+                shader_type,
+                error_message,
+            }) => write!(
+                f,
+                "error: {shader_type:?} shader compilation failed:\n{error_message}"
+            ),
+            Error::ShaderError(miniquad::ShaderError::LinkError(error_message)) => {
+                write!(f, "error: shader program linking failed:\n{error_message}")
+            }
+            Error::ModelParseError {
+                path,
+                line,
+                message,
+            } => write!(f, "error: {path}:{line}: {message}"),
+            _ => write!(f, "error: {self:?}"),
+        }
     }
 }
 
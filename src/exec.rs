@@ -47,6 +47,23 @@ impl Future for FileLoadingFuture {
     }
 }
 
+/// Result of a computation running on another thread.
+/// Returns Pending until the thread stores the value.
+pub struct ThreadFuture<T> {
+    pub result: Arc<Mutex<Option<T>>>,
+}
+
+impl<T> Future for ThreadFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, _context: &mut Context) -> Poll<Self::Output> {
+        match self.result.lock().unwrap().take() {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        }
+    }
+}
+
 fn waker() -> Waker {
     unsafe fn clone(data: *const ()) -> RawWaker {
         RawWaker::new(data, &VTABLE)
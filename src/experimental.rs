@@ -8,3 +8,4 @@ pub mod collections;
 pub mod coroutines;
 pub mod scene;
 pub mod state_machine;
+pub mod tween;
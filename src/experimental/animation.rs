@@ -70,6 +70,18 @@ pub struct Animation {
     pub fps: u32,
 }
 
+/// How an animation goes on after its last frame, see [AnimatedSprite::set_playback].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Playback {
+    /// Back to the first frame.
+    #[default]
+    Loop,
+    /// Stops on the last frame, see [AnimatedSprite::finished].
+    Once,
+    /// Back and forth between the first and the last frame.
+    PingPong,
+}
+
 /// Specific animation frame
 pub struct AnimationFrame {
     /// Area of current frame in source image
@@ -84,12 +96,21 @@ pub struct AnimatedSprite {
     tile_width: f32,
     tile_height: f32,
     animations: Vec<Animation>,
+    playbacks: Vec<Playback>,
 
     current_animation: usize,
     time: f32,
     frame: u32,
+    // going towards the first frame in ping-pong
+    backwards: bool,
+    finished: bool,
+    // the frames shown by the last update, in order
+    changed_frames: Vec<u32>,
     /// Controls if frame should be updated on [update][Self::update]
     pub playing: bool,
+    /// Multiplies the fps of the animations, negative values play them backwards.
+    /// 1 by default.
+    pub speed: f32,
 }
 
 impl AnimatedSprite {
@@ -103,21 +124,49 @@ impl AnimatedSprite {
             tile_width: tile_width as f32,
             tile_height: tile_height as f32,
             animations: animations.to_vec(),
+            playbacks: vec![Playback::default(); animations.len()],
             current_animation: 0,
             time: 0.0,
             frame: 0,
+            backwards: false,
+            finished: false,
+            changed_frames: vec![],
             playing,
+            speed: 1.,
         }
     }
 
+    /// All the animations loop by default.
+    pub fn set_playback(&mut self, animation: usize, playback: Playback) {
+        self.playbacks[animation] = playback;
+    }
+
     /// Choose animation to display
     ///
     /// **Note:** the animations is not reset when switching, for this use [set_frame][Self::set_frame]
+    /// or [set_animation_ex][Self::set_animation_ex]
     pub fn set_animation(&mut self, animation: usize) {
+        self.set_animation_ex(animation, true);
+    }
+
+    /// Choose animation to display, from its first frame unless `keep_frame` is true.
+    ///
+    /// Switching to the animation already playing with `keep_frame` does nothing.
+    pub fn set_animation_ex(&mut self, animation: usize, keep_frame: bool) {
+        if animation == self.current_animation && keep_frame {
+            return;
+        }
         self.current_animation = animation;
 
-        let animation = &self.animations[self.current_animation];
-        self.frame %= animation.frames;
+        if keep_frame {
+            let animation = &self.animations[self.current_animation];
+            self.frame %= animation.frames.max(1);
+        } else {
+            self.frame = 0;
+            self.time = 0.;
+            self.backwards = false;
+        }
+        self.finished = false;
     }
 
     /// Currently chosen animation
@@ -128,6 +177,7 @@ impl AnimatedSprite {
     /// Set specific frame for animation
     pub fn set_frame(&mut self, frame: u32) {
         self.frame = frame;
+        self.finished = false;
     }
 
     /// Returns whether the last frame is being displayed
@@ -136,20 +186,94 @@ impl AnimatedSprite {
         self.frame == animation.frames - 1
     }
 
+    /// Returns true once an animation played [Playback::Once] is over:
+    /// its last frame was shown for a whole frame time, or its first one when
+    /// played backwards. Until the animation or the frame is set again.
+    pub const fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The frame the last [update][Self::update] switched to, if it did.
+    pub fn frame_changed(&self) -> Option<u32> {
+        self.changed_frames.last().copied()
+    }
+
+    /// Returns true if the last [update][Self::update] showed `frame`, even when
+    /// it went past it within one update, e.g. a footstep sound on the third frame.
+    pub fn entered_frame(&self, frame: u32) -> bool {
+        self.changed_frames.contains(&frame)
+    }
+
     /// Update current frame
     ///
     /// Switches to the next frame every `1. / current_animation.fps` seconds
     pub fn update(&mut self) {
+        self.advance(get_frame_time());
+    }
+
+    /// Same as [update][Self::update], with `dt` instead of the frame time,
+    /// e.g. for a fixed timestep.
+    pub fn advance(&mut self, dt: f32) {
+        self.changed_frames.clear();
+
         let animation = &self.animations[self.current_animation];
+        let frames = animation.frames.max(1);
+        let playback = self.playbacks[self.current_animation];
+        self.frame %= frames;
+
+        if !self.playing || animation.fps == 0 || self.speed == 0. {
+            return;
+        }
+
+        let frame_time = 1. / animation.fps as f32;
+        self.time += dt * self.speed.abs();
+        let steps = (self.time / frame_time) as u64;
+        self.time -= steps as f32 * frame_time;
 
-        if self.playing {
-            self.time += get_frame_time();
-            if self.time > 1. / animation.fps as f32 {
-                self.frame += 1;
-                self.time = 0.0;
+        // a long update shows one whole cycle at most
+        let cycle = match playback {
+            Playback::Loop => frames as u64,
+            Playback::Once => u64::MAX,
+            Playback::PingPong => (frames as u64 * 2 - 2).max(1),
+        };
+        let steps = if steps > cycle {
+            cycle + steps % cycle
+        } else {
+            steps
+        };
+
+        let reverse = self.speed < 0.;
+        for _ in 0..steps {
+            if self.finished {
+                break;
             }
+            self.step(frames, playback, reverse);
+        }
+    }
+
+    fn step(&mut self, frames: u32, playback: Playback, reverse: bool) {
+        let frame = self.frame as i64;
+        let direction = if reverse != self.backwards { -1 } else { 1 };
+        let next = frame + direction;
+
+        let next = match playback {
+            Playback::Loop => next.rem_euclid(frames as i64),
+            Playback::Once if (0..frames as i64).contains(&next) => next,
+            Playback::Once => {
+                self.finished = true;
+                return;
+            }
+            Playback::PingPong if (0..frames as i64).contains(&next) => next,
+            Playback::PingPong => {
+                self.backwards = !self.backwards;
+                (frame - direction).clamp(0, frames as i64 - 1)
+            }
+        };
+
+        if next != frame {
+            self.frame = next as u32;
+            self.changed_frames.push(self.frame);
         }
-        self.frame %= animation.frames;
     }
 
     /// Get current frame
@@ -167,3 +291,83 @@ impl AnimatedSprite {
         }
     }
 }
+
+#[cfg(test)]
+fn test_sprite(playback: Playback) -> AnimatedSprite {
+    let mut sprite = AnimatedSprite::new(
+        16,
+        16,
+        &[
+            Animation {
+                name: "walk".to_string(),
+                row: 0,
+                frames: 4,
+                fps: 10,
+            },
+            Animation {
+                name: "idle".to_string(),
+                row: 1,
+                frames: 2,
+                fps: 10,
+            },
+        ],
+        true,
+    );
+    sprite.set_playback(0, playback);
+    sprite
+}
+
+#[test]
+fn animation_frame_events() {
+    let mut sprite = test_sprite(Playback::Loop);
+
+    // short updates add up
+    sprite.advance(0.06);
+    assert_eq!(sprite.frame_changed(), None);
+    sprite.advance(0.06);
+    assert_eq!(sprite.frame_changed(), Some(1));
+
+    // a long one goes past several frames, and reports all of them
+    sprite.advance(0.25);
+    assert_eq!(sprite.frame_changed(), Some(3));
+    assert!(sprite.entered_frame(2) && sprite.entered_frame(3));
+    sprite.advance(0.1);
+    assert!(sprite.entered_frame(0));
+
+    // a very long one wraps around a whole cycle at most
+    sprite.advance(9.98);
+    assert_eq!(sprite.frame_changed(), Some(0));
+    assert!((0..4).all(|frame| sprite.entered_frame(frame)));
+
+    sprite.speed = -1.;
+    sprite.advance(0.1);
+    assert_eq!(sprite.frame_changed(), Some(3));
+}
+
+#[test]
+fn animation_playback() {
+    let mut sprite = test_sprite(Playback::Once);
+    sprite.advance(0.35);
+    assert!(sprite.is_last_frame() && !sprite.finished());
+    sprite.advance(1.);
+    assert!(sprite.is_last_frame() && sprite.finished());
+    assert_eq!(sprite.frame_changed(), None);
+
+    let mut sprite = test_sprite(Playback::PingPong);
+    let mut frames = vec![];
+    for _ in 0..8 {
+        sprite.advance(0.1);
+        frames.extend(sprite.frame_changed());
+    }
+    assert_eq!(frames, [1, 2, 3, 2, 1, 0, 1, 2]);
+
+    // switching keeps the frame, or starts the animation over
+    sprite.set_animation(1);
+    assert_eq!(sprite.frame().source_rect.x, 0.);
+    sprite.set_animation(0);
+    sprite.set_frame(3);
+    sprite.set_animation_ex(1, true);
+    assert_eq!(sprite.frame().source_rect.x, 16.);
+    sprite.set_animation_ex(0, false);
+    assert_eq!(sprite.frame().source_rect.x, 0.);
+}
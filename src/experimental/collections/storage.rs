@@ -19,8 +19,10 @@ use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::{
     cell::RefCell,
+    marker::PhantomData,
     ops::{Deref, DerefMut},
     rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
 };
 
 std::thread_local! {
@@ -45,44 +47,503 @@ fn get_storage() -> &'static mut HashMap<TypeId, Box<dyn Any>> {
     })
 }
 
+/// Shared borrow of a stored value.
+/// Keeps the value alive even if it gets removed from the storage meanwhile.
+struct StorageRef<T: 'static> {
+    // `borrow` points into `_data`, so it is declared first to be dropped first
+    borrow: std::cell::Ref<'static, T>,
+    _data: Rc<RefCell<T>>,
+}
+
+impl<T: 'static> Deref for StorageRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.borrow
+    }
+}
+
+/// Mutable borrow of a stored value.
+/// Keeps the value alive even if it gets removed from the storage meanwhile.
+struct StorageRefMut<T: 'static> {
+    // `borrow` points into `_data`, so it is declared first to be dropped first
+    borrow: std::cell::RefMut<'static, T>,
+    _data: Rc<RefCell<T>>,
+}
+
+impl<T: 'static> Deref for StorageRefMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.borrow
+    }
+}
+
+impl<T: 'static> DerefMut for StorageRefMut<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.borrow
+    }
+}
+
+/// A storage entry.
+/// Every `store` gets a new id, that is what handles compare against.
+struct Stored<T> {
+    id: u64,
+    data: Rc<RefCell<T>>,
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+fn get_stored<T: Any>() -> Option<&'static Stored<T>> {
+    get_storage()
+        .get(&TypeId::of::<T>())
+        .and_then(|data| data.downcast_ref::<Stored<T>>())
+}
+
+fn get_cell<T: Any>() -> Option<Rc<RefCell<T>>> {
+    get_stored::<T>().map(|stored| stored.data.clone())
+}
+
 /// Store data in global storage.
 /// Will silently overwrite an old value if any.
 pub fn store<T: Any>(data: T) {
-    get_storage().insert(TypeId::of::<T>(), Box::new(Rc::new(RefCell::new(data))));
+    let stored = Stored {
+        id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        data: Rc::new(RefCell::new(data)),
+    };
+    get_storage().insert(TypeId::of::<T>(), Box::new(stored));
+}
+
+/// Get reference to data from global storage,
+/// storing the result of `init` first if there is no data available with this type.
+/// `init` is not called when the data is already stored.
+/// Will panic if the data is mutably borrowed.
+///
+/// ```
+/// use macroquad::experimental::collections::storage;
+///
+/// struct Settings {
+///     volume: f32,
+/// }
+///
+/// assert_eq!(storage::get_or_store(|| Settings { volume: 0.5 }).volume, 0.5);
+/// storage::get_mut::<Settings>().volume = 1.0;
+/// assert_eq!(storage::get_or_store(|| Settings { volume: 0.5 }).volume, 1.0);
+/// ```
+pub fn get_or_store<T: Any>(init: impl FnOnce() -> T) -> impl Deref<Target = T> {
+    if !get_storage().contains_key(&TypeId::of::<T>()) {
+        store(init());
+    }
+
+    get::<T>()
+}
+
+/// Remove data from global storage and return it.
+///
+/// Will return None if there is no data available with this type
+/// or if the data is still borrowed by some `get`/`get_mut` guard.
+/// In the latter case the data is left in the storage.
+///
+/// ```
+/// use macroquad::experimental::collections::storage;
+///
+/// struct LevelName(&'static str);
+///
+/// storage::store(LevelName("forest"));
+/// assert_eq!(storage::remove::<LevelName>().unwrap().0, "forest");
+/// assert!(storage::try_get::<LevelName>().is_none());
+/// ```
+pub fn remove<T: Any>() -> Option<T> {
+    let storage = get_storage();
+
+    // each borrow guard holds its own Rc, so a single strong reference
+    // means nobody is looking at the data right now
+    let stored = storage.get(&TypeId::of::<T>())?;
+    if Rc::strong_count(&stored.downcast_ref::<Stored<T>>()?.data) != 1 {
+        return None;
+    }
+
+    let stored = storage
+        .remove(&TypeId::of::<T>())?
+        .downcast::<Stored<T>>()
+        .ok()?;
+    Rc::try_unwrap(stored.data).ok().map(RefCell::into_inner)
+}
+
+/// Remove all the data from global storage.
+///
+/// Guards obtained with `get`/`get_mut` before the call remain valid:
+/// `clear` only drops the storage entries, the data they point to
+/// is dropped together with the last outstanding guard.
+pub fn clear() {
+    get_storage().clear();
+}
+
+/// Reason why data could not be borrowed from global storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorrowError {
+    /// There is no data available with this type.
+    NotStored,
+    /// The data is already borrowed and the new borrow would conflict with it.
+    AlreadyBorrowed,
+}
+
+impl std::fmt::Display for BorrowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BorrowError::NotStored => write!(f, "no data of this type in storage"),
+            BorrowError::AlreadyBorrowed => write!(f, "data is already borrowed"),
+        }
+    }
+}
+
+impl std::error::Error for BorrowError {}
+
+fn borrow<T: Any>() -> Result<StorageRef<T>, BorrowError> {
+    let data = get_cell::<T>().ok_or(BorrowError::NotStored)?;
+    // the RefCell lives on the heap behind `data`, which is kept alive by the guard
+    let cell: &'static RefCell<T> = unsafe { &*Rc::as_ptr(&data) };
+
+    Ok(StorageRef {
+        borrow: cell
+            .try_borrow()
+            .map_err(|_| BorrowError::AlreadyBorrowed)?,
+        _data: data,
+    })
+}
+
+fn borrow_mut<T: Any>() -> Result<StorageRefMut<T>, BorrowError> {
+    let data = get_cell::<T>().ok_or(BorrowError::NotStored)?;
+    // the RefCell lives on the heap behind `data`, which is kept alive by the guard
+    let cell: &'static RefCell<T> = unsafe { &*Rc::as_ptr(&data) };
+
+    Ok(StorageRefMut {
+        borrow: cell
+            .try_borrow_mut()
+            .map_err(|_| BorrowError::AlreadyBorrowed)?,
+        _data: data,
+    })
+}
+
+fn borrow_failed<T: Any>(err: BorrowError) -> ! {
+    panic!(
+        "storage: can't borrow {}: {err}",
+        std::any::type_name::<T>()
+    )
 }
 
 /// Get reference to data from global storage.
-/// Will panic if there is no data available with this type.
+/// Will panic if there is no data available with this type
+/// or if the data is mutably borrowed.
 pub fn get<T: Any>() -> impl Deref<Target = T> {
-    try_get::<T>().unwrap()
+    borrow::<T>().unwrap_or_else(|err| borrow_failed::<T>(err))
 }
 
 /// Get reference to data from global storage.
 /// Will return None if there is no data available with this type.
+/// Will panic if the data is mutably borrowed.
 pub fn try_get<T: Any>() -> Option<impl Deref<Target = T>> {
-    get_storage()
-        .get(&TypeId::of::<T>())
-        .as_ref()
-        .and_then(|data| {
-            data.downcast_ref::<Rc<RefCell<T>>>()
-                .map(|data| data.borrow())
-        })
+    match borrow::<T>() {
+        Ok(data) => Some(data),
+        Err(BorrowError::NotStored) => None,
+        Err(err) => borrow_failed::<T>(err),
+    }
 }
 
 /// Get mutable reference to data from global storage.
 /// Will return None if there is no data available with this type.
+/// Will panic if the data is already borrowed.
 pub fn try_get_mut<T: Any>() -> Option<impl DerefMut<Target = T>> {
-    get_storage()
-        .get(&TypeId::of::<T>())
-        .as_ref()
-        .and_then(|data| {
-            data.downcast_ref::<Rc<RefCell<T>>>()
-                .map(|data| data.borrow_mut())
-        })
+    match borrow_mut::<T>() {
+        Ok(data) => Some(data),
+        Err(BorrowError::NotStored) => None,
+        Err(err) => borrow_failed::<T>(err),
+    }
 }
 
 /// Get mutable reference to data from global storage.
-/// Will panic if there is no data available with this type.
+/// Will panic if there is no data available with this type
+/// or if the data is already borrowed.
 pub fn get_mut<T: Any>() -> impl DerefMut<Target = T> {
-    try_get_mut::<T>().unwrap()
+    borrow_mut::<T>().unwrap_or_else(|err| borrow_failed::<T>(err))
+}
+
+/// Run `f` with a mutable reference to data from global storage.
+/// The borrow is released before `with_mut` returns, so it can't outlive the closure.
+/// Will panic if there is no data available with this type
+/// or if the data is already borrowed.
+///
+/// ```
+/// use macroquad::experimental::collections::storage;
+///
+/// struct Score(u32);
+///
+/// storage::store(Score(0));
+/// let score = storage::with_mut(|score: &mut Score| {
+///     score.0 += 10;
+///     score.0
+/// });
+/// assert_eq!(score, 10);
+/// ```
+pub fn with_mut<T: Any, R>(f: impl FnOnce(&mut T) -> R) -> R {
+    let mut data = borrow_mut::<T>().unwrap_or_else(|err| borrow_failed::<T>(err));
+
+    f(&mut data)
+}
+
+/// Run `f` with a mutable reference to data from global storage.
+/// Will return None if there is no data available with this type
+/// or if the data is already borrowed.
+pub fn try_with_mut<T: Any, R>(f: impl FnOnce(&mut T) -> R) -> Option<R> {
+    let mut data = borrow_mut::<T>().ok()?;
+
+    Some(f(&mut data))
+}
+
+/// Get reference to data from global storage.
+/// Never panics, reports missing or mutably borrowed data as an error instead.
+pub fn try_borrow<T: Any>() -> Result<impl Deref<Target = T>, BorrowError> {
+    borrow::<T>()
+}
+
+/// Get mutable reference to data from global storage.
+/// Never panics, reports missing or already borrowed data as an error instead.
+///
+/// ```
+/// use macroquad::experimental::collections::storage::{self, BorrowError};
+///
+/// struct Score(u32);
+///
+/// assert_eq!(storage::try_borrow_mut::<Score>().err(), Some(BorrowError::NotStored));
+///
+/// storage::store(Score(0));
+/// let score = storage::get::<Score>();
+/// assert_eq!(storage::try_borrow_mut::<Score>().err(), Some(BorrowError::AlreadyBorrowed));
+/// drop(score);
+///
+/// storage::try_borrow_mut::<Score>().unwrap().0 += 1;
+/// assert_eq!(storage::get::<Score>().0, 1);
+/// ```
+pub fn try_borrow_mut<T: Any>() -> Result<impl DerefMut<Target = T>, BorrowError> {
+    borrow_mut::<T>()
+}
+
+/// Owned handle to data in global storage.
+///
+/// Unlike the guards returned by `get`/`get_mut` a handle does not borrow the data,
+/// so it can be kept in a struct or moved into a coroutine and locked on demand.
+/// The handle becomes invalid once the data is removed from the storage
+/// or overwritten by another `store`.
+///
+/// A handle is only an id of the stored value, it does not point to the data.
+/// That makes it `Send`, so it can be moved into `start_coroutine`.
+/// The storage itself is per thread: on any other thread the handle is invalid.
+///
+/// ```
+/// use macroquad::experimental::collections::storage;
+///
+/// struct Score(u32);
+///
+/// storage::store(Score(0));
+/// let score = storage::handle::<Score>();
+/// score.lock().unwrap().0 += 1;
+/// assert_eq!(storage::get::<Score>().0, 1);
+///
+/// storage::store(Score(0));
+/// assert!(score.lock().is_none());
+/// ```
+pub struct Handle<T: 'static> {
+    id: u64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static> Clone for Handle<T> {
+    fn clone(&self) -> Handle<T> {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for Handle<T> {}
+
+impl<T: Any> Handle<T> {
+    /// Returns true if the data this handle points to is still in the storage.
+    pub fn is_valid(&self) -> bool {
+        get_stored::<T>().is_some_and(|stored| stored.id == self.id)
+    }
+
+    /// Get mutable reference to the data.
+    /// Will return None if the handle is no longer valid.
+    /// Will panic if the data is already borrowed.
+    pub fn lock(&self) -> Option<impl DerefMut<Target = T>> {
+        match self.try_lock() {
+            Ok(data) => Some(data),
+            Err(BorrowError::NotStored) => None,
+            Err(err) => borrow_failed::<T>(err),
+        }
+    }
+
+    /// Get mutable reference to the data.
+    /// Never panics, reports invalid handle or already borrowed data as an error instead.
+    pub fn try_lock(&self) -> Result<impl DerefMut<Target = T>, BorrowError> {
+        if !self.is_valid() {
+            return Err(BorrowError::NotStored);
+        }
+
+        borrow_mut::<T>()
+    }
+}
+
+/// Get an owned handle to data from global storage.
+/// Will panic if there is no data available with this type.
+pub fn handle<T: Any>() -> Handle<T> {
+    try_handle::<T>().unwrap_or_else(|| borrow_failed::<T>(BorrowError::NotStored))
+}
+
+/// Get an owned handle to data from global storage.
+/// Will return None if there is no data available with this type.
+pub fn try_handle<T: Any>() -> Option<Handle<T>> {
+    get_stored::<T>().map(|stored| Handle {
+        id: stored.id,
+        _marker: PhantomData,
+    })
+}
+
+#[test]
+fn storage_remove() {
+    struct LevelTilemap(Vec<u8>);
+
+    store(LevelTilemap(vec![1, 2, 3]));
+    {
+        let _tilemap = get::<LevelTilemap>();
+        assert!(remove::<LevelTilemap>().is_none());
+        assert!(try_get::<LevelTilemap>().is_some());
+    }
+
+    assert_eq!(remove::<LevelTilemap>().unwrap().0, vec![1, 2, 3]);
+    assert!(try_get::<LevelTilemap>().is_none());
+    assert!(remove::<LevelTilemap>().is_none());
+}
+
+#[test]
+fn storage_clear_with_outstanding_borrow() {
+    struct EnemySpawner(u32);
+
+    store(EnemySpawner(5));
+    let mut spawner = get_mut::<EnemySpawner>();
+    clear();
+
+    assert!(try_get::<EnemySpawner>().is_none());
+    spawner.0 += 1;
+    assert_eq!(spawner.0, 6);
+    drop(spawner);
+
+    store(EnemySpawner(0));
+    assert_eq!(get::<EnemySpawner>().0, 0);
+}
+
+#[test]
+fn storage_borrow_conflict() {
+    struct Player(i32);
+
+    store(Player(0));
+    let player = get_mut::<Player>();
+    assert_eq!(
+        try_borrow::<Player>().err(),
+        Some(BorrowError::AlreadyBorrowed)
+    );
+    assert_eq!(
+        try_borrow_mut::<Player>().err(),
+        Some(BorrowError::AlreadyBorrowed)
+    );
+    drop(player);
+
+    let _first = try_borrow::<Player>().unwrap();
+    let _second = try_borrow::<Player>().unwrap();
+    assert_eq!(
+        try_borrow_mut::<Player>().err(),
+        Some(BorrowError::AlreadyBorrowed)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Player")]
+fn storage_borrow_conflict_panic_names_type() {
+    struct Player;
+
+    store(Player);
+    let _player = get::<Player>();
+    let _player_mut = get_mut::<Player>();
+}
+
+#[test]
+fn storage_get_or_store() {
+    struct Counter(u32);
+
+    let mut calls = 0;
+    assert_eq!(
+        get_or_store(|| {
+            calls += 1;
+            Counter(1)
+        })
+        .0,
+        1
+    );
+    get_mut::<Counter>().0 = 2;
+    assert_eq!(
+        get_or_store(|| {
+            calls += 1;
+            Counter(1)
+        })
+        .0,
+        2
+    );
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn storage_with_mut() {
+    struct Health(i32);
+
+    assert_eq!(try_with_mut(|health: &mut Health| health.0), None);
+
+    store(Health(10));
+    with_mut(|health: &mut Health| health.0 -= 3);
+    assert_eq!(get::<Health>().0, 7);
+
+    let _health = get::<Health>();
+    assert_eq!(try_with_mut(|health: &mut Health| health.0), None);
+}
+
+#[test]
+fn storage_handle_invalidation() {
+    struct Spawner(u32);
+
+    store(Spawner(0));
+    let spawner = handle::<Spawner>();
+    let cloned = spawner.clone();
+    spawner.lock().unwrap().0 += 1;
+    assert_eq!(cloned.lock().unwrap().0, 1);
+
+    {
+        let _spawner = get::<Spawner>();
+        assert_eq!(spawner.try_lock().err(), Some(BorrowError::AlreadyBorrowed));
+    }
+
+    store(Spawner(5));
+    assert!(!spawner.is_valid());
+    assert!(spawner.lock().is_none());
+    assert_eq!(spawner.try_lock().err(), Some(BorrowError::NotStored));
+
+    let spawner = handle::<Spawner>();
+    assert_eq!(spawner.lock().unwrap().0, 5);
+    remove::<Spawner>().unwrap();
+    assert!(spawner.lock().is_none());
+
+    // handles can be moved to other threads, but there is no data for them there
+    store(Spawner(7));
+    let spawner = handle::<Spawner>();
+    std::thread::spawn(move || assert!(!spawner.is_valid()))
+        .join()
+        .unwrap();
+    assert!(spawner.is_valid());
 }
@@ -57,6 +57,10 @@ pub(crate) struct CoroutinesContext {
     coroutines: GenerationalStorage<CoroutineState>,
     active_coroutine_now: Option<f64>,
     active_coroutine_delta: Option<f64>,
+    /// The coroutine being polled right now. When it stops itself, its future
+    /// can't be dropped until the poll is over, so `stop_polled` is set instead.
+    polled: Option<GenerationalId>,
+    stop_polled: bool,
 }
 
 impl CoroutinesContext {
@@ -65,14 +69,23 @@ impl CoroutinesContext {
             coroutines: GenerationalStorage::new(),
             active_coroutine_now: None,
             active_coroutine_delta: None,
+            polled: None,
+            stop_polled: false,
         }
     }
 
     pub fn update(&mut self) {
-        self.coroutines.retain(|coroutine| {
+        self.coroutines.retain(|id, coroutine| {
             if let CoroutineState::Running(ref mut f) = coroutine {
                 if !f.manual_poll {
-                    if let Some(v) = resume(&mut f.future) {
+                    self.polled = Some(id);
+                    let res = resume(&mut f.future);
+                    self.polled = None;
+
+                    if std::mem::take(&mut self.stop_polled) {
+                        return false;
+                    }
+                    if let Some(v) = res {
                         if f.has_value {
                             *coroutine = CoroutineState::Value(v);
                         } else {
@@ -100,20 +113,50 @@ pub struct Coroutine<T = ()> {
     _phantom: PhantomData<T>,
 }
 
+fn is_done(id: GenerationalId) -> bool {
+    let context = &get_context().coroutines_context;
+
+    let coroutine = context.coroutines.get(id);
+
+    if let Some(coroutine) = coroutine {
+        return coroutine.is_value() || coroutine.is_nothing();
+    }
+
+    true
+}
+
 impl<T: 'static + Any> Coroutine<T> {
     /// Returns true if the coroutine finished or was stopped.
     pub fn is_done(&self) -> bool {
-        let context = &get_context().coroutines_context;
+        is_done(self.id)
+    }
 
-        let coroutine = context.coroutines.get(self.id);
+    /// Same as [stop_coroutine].
+    pub fn stop(self) {
+        stop_coroutine(self);
+    }
 
-        if let Some(coroutine) = coroutine {
-            return coroutine.is_value() || coroutine.is_nothing();
-        }
+    /// Stop the coroutine if it is still running, a no-op otherwise:
+    /// unlike [Coroutine::stop], the value of a finished coroutine can still be retrieved.
+    ///
+    /// The future is dropped right away, or at its next `.await` when the coroutine
+    /// cancels itself. A cancelled coroutine in manual poll mode is stopped as well,
+    /// [Coroutine::poll] does nothing on it.
+    pub fn cancel(self) {
+        let context = &get_context().coroutines_context;
 
-        true
+        if let Some(CoroutineState::Running(_)) = context.coroutines.get(self.id) {
+            stop_coroutine(self);
+        }
     }
 
+    /// The value returned by the coroutine: `Some` once it finished, then `None` again,
+    /// the value is moved out.
+    ///
+    /// A finished coroutine keeps its value until it is retrieved, or until
+    /// the coroutine is stopped or [scene::clear](crate::experimental::scene::clear)
+    /// stops all the coroutines. Coroutines returning `()` keep nothing around
+    /// and give `Some(())` every time once done.
     pub fn retrieve(&self) -> Option<T> {
         let context = &mut get_context().coroutines_context;
 
@@ -186,7 +229,13 @@ impl<T: 'static + Any> Coroutine<T> {
             context.active_coroutine_now = f.manual_time;
             context.active_coroutine_delta = Some(delta_time);
             *f.manual_time.as_mut().unwrap() += delta_time;
-            if let Some(v) = resume(&mut f.future) {
+            context.polled = Some(self.id);
+            let res = resume(&mut f.future);
+            context.polled = None;
+
+            if std::mem::take(&mut context.stop_polled) {
+                context.coroutines.free(self.id);
+            } else if let Some(v) = res {
                 if f.has_value {
                     *coroutine = CoroutineState::Value(v);
                 } else {
@@ -227,38 +276,182 @@ pub fn stop_all_coroutines() {
     context.coroutines.clear();
 }
 
+/// Stops the coroutine, it will not be polled again and [Coroutine::is_done] becomes true.
+///
+/// Safe to call from within the coroutine itself, and on coroutines that are done already.
 pub fn stop_coroutine<T: 'static + Any>(coroutine: Coroutine<T>) {
     let context = &mut get_context().coroutines_context;
 
-    context.coroutines.free(coroutine.id);
+    if context.polled == Some(coroutine.id) {
+        context.stop_polled = true;
+    } else {
+        context.coroutines.free(coroutine.id);
+    }
+}
+
+/// The time since the last poll of the coroutine being polled:
+/// the frame time, or the `delta_time` given to [Coroutine::poll].
+pub(crate) fn frame_delta() -> f32 {
+    get_context()
+        .coroutines_context
+        .active_coroutine_delta
+        .map_or(crate::time::get_frame_time(), |delta| delta as f32)
 }
 
 pub struct TimerDelayFuture {
     pub(crate) remaining_time: f32,
+    pub(crate) polled: bool,
 }
 
 impl Future for TimerDelayFuture {
     type Output = Option<()>;
 
     fn poll(mut self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
-        let delta = get_context()
-            .coroutines_context
-            .active_coroutine_delta
-            .unwrap_or(crate::time::get_frame_time() as _);
-
-        self.remaining_time -= delta as f32;
+        self.remaining_time -= frame_delta();
 
-        if self.remaining_time <= 0.0 {
+        // always one frame at least, so even a wait of 0 lets the rest of the game run
+        if self.remaining_time <= 0.0 && self.polled {
             Poll::Ready(Some(()))
         } else {
+            self.polled = true;
             Poll::Pending
         }
     }
 }
 
+/// Wait for `time` seconds of the coroutine's clock: the frame time, scaled by
+/// [set_time_scale](crate::time::set_time_scale), or the `delta_time` given to
+/// [Coroutine::poll] for the manually polled ones.
+///
+/// Takes one frame at least, `wait_seconds(0.0)` is a wait for the next frame.
 pub const fn wait_seconds(time: f32) -> TimerDelayFuture {
     TimerDelayFuture {
         remaining_time: time,
+        polled: false,
+    }
+}
+
+/// The error of [timeout].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
+impl std::fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out")
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+pub struct TimeoutFuture<F: Future> {
+    future: Pin<Box<F>>,
+    timer: TimerDelayFuture,
+}
+
+impl<F: Future> Future for TimeoutFuture<F> {
+    type Output = Result<F::Output, TimedOut>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if let Poll::Ready(value) = self.future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(value));
+        }
+
+        match Pin::new(&mut self.timer).poll(cx) {
+            Poll::Ready(_) => Poll::Ready(Err(TimedOut)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Run `future` for `time` seconds at most, on the same clock as [wait_seconds].
+///
+/// ```skip
+/// match timeout(5., load_string("level.json")).await {
+///     Ok(level) => ..,
+///     Err(TimedOut) => ..,
+/// }
+/// ```
+///
+/// The future is dropped when the time is out.
+pub fn timeout<F: Future>(time: f32, future: F) -> TimeoutFuture<F> {
+    TimeoutFuture {
+        future: Box::pin(future),
+        timer: wait_seconds(time),
+    }
+}
+
+pub struct WaitForAllFuture {
+    coroutines: Vec<GenerationalId>,
+}
+
+impl Future for WaitForAllFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
+        if self.coroutines.iter().all(|id| is_done(*id)) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Wait until all the coroutines are done, finished or stopped.
+pub fn wait_for_all<T: 'static + Any>(coroutines: &[Coroutine<T>]) -> WaitForAllFuture {
+    WaitForAllFuture {
+        coroutines: coroutines.iter().map(|coroutine| coroutine.id).collect(),
+    }
+}
+
+pub struct WaitForAnyFuture {
+    coroutines: Vec<GenerationalId>,
+}
+
+impl Future for WaitForAnyFuture {
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
+        match self.coroutines.iter().position(|id| is_done(*id)) {
+            Some(n) => Poll::Ready(n),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Wait until one of the coroutines is done, resolves to its index in `coroutines`,
+/// the lowest one if several are.
+///
+/// Panics if `coroutines` is empty.
+pub fn wait_for_any<T: 'static + Any>(coroutines: &[Coroutine<T>]) -> WaitForAnyFuture {
+    assert!(!coroutines.is_empty(), "wait_for_any: no coroutines");
+
+    WaitForAnyFuture {
+        coroutines: coroutines.iter().map(|coroutine| coroutine.id).collect(),
+    }
+}
+
+pub struct FrameDelayFuture {
+    pub(crate) remaining_frames: u32,
+}
+
+impl Future for FrameDelayFuture {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
+        if self.remaining_frames == 0 {
+            Poll::Ready(())
+        } else {
+            self.remaining_frames -= 1;
+            Poll::Pending
+        }
+    }
+}
+
+/// Wait for `frames` frames, or polls for the manually polled coroutines.
+/// `wait_frames(0)` does not wait at all.
+pub const fn wait_frames(frames: u32) -> FrameDelayFuture {
+    FrameDelayFuture {
+        remaining_frames: frames,
     }
 }
 
@@ -1,4 +1,4 @@
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct GenerationalId {
     id: usize,
     generation: usize,
@@ -47,11 +47,7 @@ impl<T> GenerationalStorage<T> {
     }
 
     pub fn get(&self, id: GenerationalId) -> Option<&T> {
-        if id.id > self.vec.len() {
-            return None;
-        }
-
-        let cell = self.vec[id.id].as_ref()?;
+        let cell = self.vec.get(id.id)?.as_ref()?;
         if cell.generation != id.generation {
             return None;
         }
@@ -60,11 +56,7 @@ impl<T> GenerationalStorage<T> {
     }
 
     pub fn get_mut(&mut self, id: GenerationalId) -> Option<&mut T> {
-        if id.id > self.vec.len() {
-            return None;
-        }
-
-        let cell = self.vec[id.id].as_mut()?;
+        let cell = self.vec.get_mut(id.id)?.as_mut()?;
         if cell.generation != id.generation {
             return None;
         }
@@ -76,7 +68,7 @@ impl<T> GenerationalStorage<T> {
     /// In other words, remove all elements e such that f(&mut e) returns false. This method operates in place, visiting each element exactly once in the original order, and preserves the order of the retained elements.
     pub fn retain<F>(&mut self, mut f: F)
     where
-        F: FnMut(&mut T) -> bool,
+        F: FnMut(GenerationalId, &mut T) -> bool,
     {
         for (id, cell) in self.vec.iter_mut().enumerate() {
             if cell.is_none() {
@@ -84,8 +76,14 @@ impl<T> GenerationalStorage<T> {
             }
 
             let c = cell.as_mut().unwrap();
-            let pred = f(&mut c.state);
             let old_generation = c.generation;
+            let pred = f(
+                GenerationalId {
+                    id,
+                    generation: old_generation,
+                },
+                &mut c.state,
+            );
 
             if !pred {
                 self.free_indices.push((id, old_generation));
@@ -99,18 +97,22 @@ impl<T> GenerationalStorage<T> {
     }
 
     pub fn clear(&mut self) {
-        self.vec.clear();
-        self.free_indices.clear();
+        // the cells are freed one by one to keep their generations,
+        // otherwise the IDs from before would point to the new data
+        for (id, cell) in self.vec.iter_mut().enumerate() {
+            if let Some(cell) = cell.take() {
+                self.free_indices.push((id, cell.generation));
+            }
+        }
     }
 
     pub fn free(&mut self, id: GenerationalId) {
-        // an attempt to free a cell by an outdated ID
+        // an attempt to free a cell by an outdated ID, or a cell that is free already
         // this is a legit request, no need to panic or anything, just
         // dont ruin the data that lives there now
-        if let Some(cell) = &self.vec[id.id] {
-            if cell.generation != id.generation {
-                return;
-            }
+        match self.vec.get(id.id) {
+            Some(Some(cell)) if cell.generation == id.generation => {}
+            _ => return,
         }
 
         self.free_indices.push((id.id, id.generation));
@@ -121,3 +123,25 @@ impl<T> GenerationalStorage<T> {
         self.vec.capacity() * size_of::<GenerationalCell<T>>()
     }
 }
+
+#[test]
+fn free_twice() {
+    let mut storage = GenerationalStorage::new();
+
+    let a = storage.push(1);
+    storage.free(a);
+    storage.free(a);
+
+    // one free cell, not two references to the same one
+    let b = storage.push(2);
+    let c = storage.push(3);
+    assert_eq!(storage.get(a), None);
+    assert_eq!(storage.get(b), Some(&2));
+    assert_eq!(storage.get(c), Some(&3));
+
+    storage.clear();
+    let d = storage.push(4);
+    storage.free(b);
+    storage.free(c);
+    assert_eq!(storage.get(d), Some(&4));
+}
@@ -5,6 +5,9 @@ use crate::camera::Camera2D;
 pub use macroquad_macro::CapabilityTrait;
 
 mod arena;
+mod transform;
+
+pub use transform::Transform;
 
 #[rustfmt::skip]
 pub trait Node {
@@ -41,7 +44,7 @@ pub struct Handle<T: 'static> {
 
 unsafe impl<T: 'static> Send for Handle<T> {}
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct HandleUntyped(Id);
 
 impl<T: 'static> std::fmt::Debug for Handle<T> {
@@ -72,6 +75,18 @@ impl<T> Handle<T> {
         HandleUntyped(self.id.unwrap())
     }
 
+    /// Returns false for the null handle and for the handles of deleted nodes,
+    /// including the ones deleted this frame that are still in memory.
+    pub fn is_valid(&self) -> bool {
+        self.id.is_some_and(|id| {
+            get_scene()
+                .nodes
+                .get(id.id)
+                .and_then(Option::as_ref)
+                .is_some_and(|cell| cell.id == id && !cell.pending_delete)
+        })
+    }
+
     pub const fn as_trait<T1: ?Sized>(&self) {}
 }
 
@@ -142,6 +157,9 @@ impl<T: 'static> RefMut<T> {
         unsafe { (*self.capabilities).push(Box::new(x)) };
     }
 
+    /// The node is skipped by every pass left in the frame, a node deleted in `update`
+    /// is not drawn even if it was already updated. It stays in memory until the end
+    /// of the frame though, so handles to it can still be used until then.
     pub fn delete(self) {
         assert!(self.handle.id.is_some());
 
@@ -194,6 +212,7 @@ impl<'a> RefMutAny<'a> {
         }
     }
 
+    /// Same as [RefMut::delete].
     pub fn delete(self) {
         unsafe {
             *self.used = false;
@@ -262,9 +281,31 @@ struct Cell {
     data_len: usize,
     permanent: bool,
     initialized: bool,
+    // inactive nodes are not updated, invisible ones are not drawn
+    active: bool,
+    visible: bool,
+    // deleted, to be freed at the end of the frame
+    pending_delete: bool,
+    // interned in `Scene::tags`
+    tags: Vec<u32>,
+    parent: Option<Id>,
+    children: Vec<Id>,
+    on_delete: OnDelete,
+    // relative to the parent
+    transform: Transform,
     used: *mut bool,
 }
 
+/// What happens to the children of a deleted node, see [set_on_delete].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OnDelete {
+    /// The children are deleted with their parent, and theirs with them.
+    #[default]
+    DeleteChildren,
+    /// The children stay where they are and become roots.
+    DetachChildren,
+}
+
 unsafe impl Sync for Scene {}
 
 fn virtual_drop<T: Node + 'static>(data: *mut ()) {
@@ -289,6 +330,14 @@ impl Cell {
             virtual_drop: &(virtual_drop::<T> as fn(*mut ())) as *const fn(*mut ()),
             data_len: size_of::<T>(),
             initialized: false,
+            active: true,
+            visible: true,
+            pending_delete: false,
+            tags: vec![],
+            parent: None,
+            children: vec![],
+            on_delete: OnDelete::default(),
+            transform: Transform::IDENTITY,
         }
     }
 
@@ -312,8 +361,16 @@ impl Cell {
         self.id.generation += 1;
         self.initialized = false;
         self.permanent = false;
+        self.active = true;
+        self.visible = true;
+        self.pending_delete = false;
 
         self.capabilities.clear();
+        self.tags.clear();
+        self.parent = None;
+        self.children.clear();
+        self.on_delete = OnDelete::default();
+        self.transform = Transform::IDENTITY;
 
         std::mem::forget(data);
     }
@@ -333,6 +390,10 @@ struct Scene {
 
     any_map: std::collections::HashMap<std::any::TypeId, Vec<(HandleUntyped, *mut u8)>>,
     free_nodes: Vec<Cell>,
+    pending_delete: Vec<Id>,
+    tags: std::collections::HashMap<String, u32>,
+    // `dense` is to be put back in the parents first order
+    hierarchy_changed: bool,
 }
 
 impl Scene {
@@ -343,17 +404,22 @@ impl Scene {
             nodes: Vec::new(),
             arena: arena::Arena::new(),
             free_nodes: Vec::new(),
+            pending_delete: Vec::new(),
             camera: [Some(Camera2D::default()), None, None, None],
             camera_pos: crate::vec2(0., 0.),
             acc: 0.0,
             current_time: crate::time::get_time(),
             in_fixed_update: false,
             any_map: std::collections::HashMap::new(),
+            tags: std::collections::HashMap::new(),
+            hierarchy_changed: false,
         }
     }
 
     pub fn clear(&mut self) {
         self.any_map.clear();
+        // the permanent nodes may have lost their parents or children
+        self.hierarchy_changed = true;
 
         for cell in &mut self.nodes {
             if let Some(Cell {
@@ -463,9 +529,45 @@ impl Scene {
         }
     }
 
+    // the node is freed in `free_deleted`, at the end of the frame,
+    // so the update pass going on can still get to it by its handle
     pub fn delete(&mut self, id: Id) {
-        if let Some(node) = self.nodes[id.id].take() {
-            assert_eq!(node.id.generation, id.generation);
+        let Some(cell) = self.nodes[id.id].as_mut() else {
+            return;
+        };
+        assert_eq!(cell.id.generation, id.generation);
+        if cell.pending_delete {
+            return;
+        }
+        cell.pending_delete = true;
+        self.pending_delete.push(id);
+
+        let on_delete = cell.on_delete;
+        for child in std::mem::take(&mut cell.children) {
+            if self.cell(child).is_none() {
+                continue;
+            }
+            match on_delete {
+                OnDelete::DeleteChildren => self.delete(child),
+                OnDelete::DetachChildren => {
+                    let transform = self.world_transform(child);
+                    let child = self.cell_mut(child).unwrap();
+                    child.parent = None;
+                    child.transform = transform;
+                    self.hierarchy_changed = true;
+                }
+            }
+        }
+        self.remove_parent(id);
+    }
+
+    fn free_deleted(&mut self) {
+        for id in self.pending_delete.drain(..) {
+            // already dropped by `clear`, the cell may even belong to a new node by now
+            if !self.nodes[id.id].as_ref().is_some_and(|cell| cell.id == id) {
+                continue;
+            }
+            let node = self.nodes[id.id].take().unwrap();
 
             self.dense_ongoing.push(Err(id));
 
@@ -476,7 +578,104 @@ impl Scene {
         }
     }
 
+    fn set_flags(&mut self, id: Id, f: impl FnOnce(&mut Cell)) {
+        if let Some(cell) = self.cell_mut(id) {
+            f(cell);
+        }
+    }
+
+    // `None` for the nodes that are freed, even if their cell went to a new node since
+    fn cell(&self, id: Id) -> Option<&Cell> {
+        self.nodes[id.id].as_ref().filter(|cell| cell.id == id)
+    }
+
+    fn cell_mut(&mut self, id: Id) -> Option<&mut Cell> {
+        self.nodes[id.id].as_mut().filter(|cell| cell.id == id)
+    }
+
+    fn set_parent(&mut self, child: Id, parent: Id) {
+        let mut ancestor = Some(parent);
+        while let Some(id) = ancestor {
+            assert!(
+                id != child,
+                "set_parent: the node would be its own ancestor"
+            );
+            ancestor = self.cell(id).and_then(|cell| cell.parent);
+        }
+        if self.cell(child).is_none() || self.cell(parent).is_none() {
+            return;
+        }
+
+        self.remove_parent(child);
+        self.cell_mut(child).unwrap().parent = Some(parent);
+        self.cell_mut(parent).unwrap().children.push(child);
+        self.hierarchy_changed = true;
+    }
+
+    fn remove_parent(&mut self, child: Id) {
+        let Some(parent) = self.cell_mut(child).and_then(|cell| cell.parent.take()) else {
+            return;
+        };
+        if let Some(parent) = self.cell_mut(parent) {
+            parent.children.retain(|id| *id != child);
+        }
+        self.hierarchy_changed = true;
+    }
+
+    fn world_transform(&self, id: Id) -> Transform {
+        let Some(cell) = self.cell(id) else {
+            return Transform::IDENTITY;
+        };
+
+        let mut transform = cell.transform;
+        let mut parent = cell.parent;
+        while let Some(cell) = parent.and_then(|id| self.cell(id)) {
+            transform = cell.transform.combine(transform);
+            parent = cell.parent;
+        }
+        transform
+    }
+
+    // parents before their children, the rest in the order the nodes were added
+    fn sort_hierarchy(&mut self) {
+        fn add_subtree(scene: &Scene, id: Id, order: &mut Vec<Id>) {
+            order.push(id);
+            for child in &scene.cell(id).unwrap().children {
+                if scene.cell(*child).is_some() {
+                    add_subtree(scene, *child, order);
+                }
+            }
+        }
+
+        if !std::mem::take(&mut self.hierarchy_changed) {
+            return;
+        }
+
+        let mut order = Vec::with_capacity(self.dense.len());
+        for id in &self.dense {
+            let cell = self.cell(*id).unwrap();
+            if cell.parent.and_then(|parent| self.cell(parent)).is_none() {
+                add_subtree(self, *id, &mut order);
+            }
+        }
+        assert_eq!(order.len(), self.dense.len());
+        self.dense = order;
+    }
+
+    fn intern_tag(&mut self, tag: &str) -> u32 {
+        let len = self.tags.len() as u32;
+        *self.tags.entry(tag.to_owned()).or_insert(len)
+    }
+
+    fn has_tag(&self, id: Id, tag: u32) -> bool {
+        self.nodes[id.id]
+            .as_ref()
+            .is_some_and(|cell| cell.id == id && cell.tags.contains(&tag))
+    }
+
     pub fn update(&mut self) {
+        self.sort_hierarchy();
+
         for node in &mut self.iter() {
             let cell = self.nodes[node.handle.0.id].as_mut().unwrap();
             if !cell.initialized {
@@ -501,10 +700,14 @@ impl Scene {
         }
 
         self.current_time = new_time;
-        self.acc += frame_time;
+        // the fixed updates slow down and pause with the rest of the game
+        self.acc += frame_time * crate::time::get_time_scale() as f64;
 
         for node in &mut self.iter() {
             let cell = self.nodes[node.handle.0.id].as_mut().unwrap();
+            if !cell.active {
+                continue;
+            }
             let node: RefMut<()> = node.to_typed::<()>();
             unsafe { (*cell.update)(node) };
         }
@@ -513,6 +716,9 @@ impl Scene {
             self.acc -= CONST_FPS;
             for node in &mut self.iter() {
                 let cell = self.nodes[node.handle.0.id].as_mut().unwrap();
+                if !cell.active {
+                    continue;
+                }
                 let node: RefMut<()> = node.to_typed::<()>();
 
                 self.in_fixed_update = true;
@@ -528,6 +734,9 @@ impl Scene {
 
             for node in &mut self.iter() {
                 let cell = self.nodes[node.handle.0.id].as_mut().unwrap();
+                if !cell.visible {
+                    continue;
+                }
                 let node: RefMut<()> = node.to_typed::<()>();
                 unsafe { (*cell.draw)(node) };
             }
@@ -535,6 +744,8 @@ impl Scene {
             crate::prelude::pop_camera_state();
         }
 
+        self.free_deleted();
+
         for id in self.dense_ongoing.drain(0..) {
             match id {
                 Ok(id) => {
@@ -573,7 +784,13 @@ impl Iterator for MagicVecIterator {
         }
         let cell = cell.as_mut().unwrap();
 
-        if unsafe { *cell.used } {
+        // the node was deleted this frame and its cell already went to a new node,
+        // which has its own entry at the end of `dense`
+        if cell.id != ix {
+            return self.next();
+        }
+
+        if cell.pending_delete || unsafe { *cell.used } {
             return self.next();
         }
 
@@ -647,6 +864,147 @@ pub fn add_node<T: Node>(node: T) -> Handle<T> {
     get_scene().add_node(node)
 }
 
+/// Inactive nodes skip `update` and `fixed_update` but keep their state and are still drawn,
+/// e.g. the game world behind a pause menu.
+///
+/// Does nothing if the node was deleted.
+pub fn set_node_active<T>(handle: Handle<T>, active: bool) {
+    if let Some(id) = handle.id {
+        get_scene().set_flags(id, |cell| cell.active = active);
+    }
+}
+
+/// Invisible nodes skip `draw`, but are still updated.
+///
+/// Does nothing if the node was deleted.
+pub fn set_node_visible<T>(handle: Handle<T>, visible: bool) {
+    if let Some(id) = handle.id {
+        get_scene().set_flags(id, |cell| cell.visible = visible);
+    }
+}
+
+/// Put the node in a group, like "enemy" or "pickup", to be found with [find_nodes_by_tag]
+/// whatever its type is.
+///
+/// A node can have any number of tags, they go away with the node when it is deleted.
+pub fn add_tag<T>(handle: Handle<T>, tag: &str) {
+    if let Some(id) = handle.id {
+        let scene = get_scene();
+        let tag = scene.intern_tag(tag);
+        scene.set_flags(id, |cell| {
+            if !cell.tags.contains(&tag) {
+                cell.tags.push(tag);
+            }
+        });
+    }
+}
+
+pub fn remove_tag<T>(handle: Handle<T>, tag: &str) {
+    let scene = get_scene();
+    if let (Some(id), Some(&tag)) = (handle.id, scene.tags.get(tag)) {
+        scene.set_flags(id, |cell| cell.tags.retain(|t| *t != tag));
+    }
+}
+
+pub fn has_tag<T>(handle: Handle<T>, tag: &str) -> bool {
+    let scene = get_scene();
+    match (handle.id, scene.tags.get(tag)) {
+        (Some(id), Some(&tag)) => scene.has_tag(id, tag),
+        _ => false,
+    }
+}
+
+/// All the nodes tagged with [add_tag], of any type.
+///
+/// Skips the same nodes as [find_nodes_by_type].
+pub fn find_nodes_by_tag(tag: &str) -> impl Iterator<Item = RefMutAny<'static>> {
+    let scene = get_scene();
+    let tag = scene.tags.get(tag).copied();
+
+    scene
+        .iter()
+        .filter(move |node| tag.is_some_and(|tag| get_scene().has_tag(node.handle.0, tag)))
+}
+
+/// The nodes of type `T` among [find_nodes_by_tag], the others are skipped.
+pub fn find_nodes_by_tag_as<T: Any>(tag: &str) -> impl Iterator<Item = RefMut<T>> {
+    find_nodes_by_tag(tag)
+        .filter(|node| node.is::<T>())
+        .map(|node| node.to_typed())
+}
+
+/// Attach `child` to `parent`: the transform of the child becomes relative to the parent's,
+/// and the child is updated and drawn after it.
+///
+/// The child keeps its local [Transform], [world_transform] reflects the new parent
+/// right away, while the update and draw order only changes on the next frame.
+/// By default, deleting the parent deletes the child, see [set_on_delete].
+///
+/// Panics if `parent` is `child` or one of its children.
+pub fn set_parent<T, U>(child: Handle<T>, parent: Handle<U>) {
+    if let (Some(child), Some(parent)) = (child.id, parent.id) {
+        get_scene().set_parent(child, parent);
+    }
+}
+
+/// Make the node a root again, its local transform becomes relative to the world.
+pub fn clear_parent<T>(child: Handle<T>) {
+    if let Some(child) = child.id {
+        get_scene().remove_parent(child);
+    }
+}
+
+pub fn parent<T>(handle: Handle<T>) -> Option<HandleUntyped> {
+    let scene = get_scene();
+    let parent = scene.cell(handle.id?)?.parent?;
+    scene.cell(parent).map(|cell| HandleUntyped(cell.id))
+}
+
+pub fn children<T>(handle: Handle<T>) -> Vec<HandleUntyped> {
+    let scene = get_scene();
+    handle
+        .id
+        .and_then(|id| scene.cell(id))
+        .map(|cell| {
+            cell.children
+                .iter()
+                .filter(|child| scene.cell(**child).is_some())
+                .map(|child| HandleUntyped(*child))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn set_on_delete<T>(handle: Handle<T>, on_delete: OnDelete) {
+    if let Some(id) = handle.id {
+        get_scene().set_flags(id, |cell| cell.on_delete = on_delete);
+    }
+}
+
+/// The transform of the node relative to its parent, or to the world for the roots.
+pub fn set_transform<T>(handle: Handle<T>, transform: Transform) {
+    if let Some(id) = handle.id {
+        get_scene().set_flags(id, |cell| cell.transform = transform);
+    }
+}
+
+/// Identity for the deleted nodes.
+pub fn transform<T>(handle: Handle<T>) -> Transform {
+    let scene = get_scene();
+    handle
+        .id
+        .and_then(|id| scene.cell(id))
+        .map_or(Transform::IDENTITY, |cell| cell.transform)
+}
+
+/// The transform of the node combined with the ones of all its parents,
+/// where the node is to be drawn.
+pub fn world_transform<T>(handle: Handle<T>) -> Transform {
+    handle
+        .id
+        .map_or(Transform::IDENTITY, |id| get_scene().world_transform(id))
+}
+
 pub(crate) fn update() {
     get_scene().update()
 }
@@ -676,6 +1034,11 @@ pub fn find_nodes_with<T: Any + Copy>() -> impl Iterator<Item = NodeWith<T>> {
     }
 }
 
+/// All the nodes of type `T`, e.g. all the enemies.
+///
+/// Deleted nodes and nodes that are borrowed right now, like the node whose
+/// `update` is running, are skipped rather than panicking.
+/// Unlike [find_nodes_with], which looks for the values given to [RefMut::provides].
 pub fn find_nodes_by_type<T: Any>() -> impl Iterator<Item = RefMut<T>> {
     get_scene()
         .iter()
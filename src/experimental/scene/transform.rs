@@ -0,0 +1,64 @@
+use crate::math::{vec2, Vec2};
+
+/// Position, rotation and scale of a node, relative to its parent,
+/// see [set_transform](super::set_transform).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub position: Vec2,
+    /// In radians.
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform::IDENTITY
+    }
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        position: vec2(0., 0.),
+        rotation: 0.,
+        scale: vec2(1., 1.),
+    };
+
+    pub const fn from_position(position: Vec2) -> Transform {
+        Transform {
+            position,
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// Where a point given relative to this transform ends up.
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.position + Vec2::from_angle(self.rotation).rotate(point * self.scale)
+    }
+
+    /// `child`, given relative to this transform, in the space this transform is in.
+    ///
+    /// Rotations add up and scales multiply, a child of a parent scaled unevenly
+    /// is not skewed.
+    pub fn combine(&self, child: Transform) -> Transform {
+        Transform {
+            position: self.transform_point(child.position),
+            rotation: self.rotation + child.rotation,
+            scale: self.scale * child.scale,
+        }
+    }
+}
+
+#[test]
+fn combine() {
+    let parent = Transform {
+        position: vec2(10., 0.),
+        rotation: std::f32::consts::FRAC_PI_2,
+        scale: vec2(2., 2.),
+    };
+    let child = parent.combine(Transform::from_position(vec2(1., 0.)));
+
+    assert!(child.position.distance(vec2(10., 2.)) < 1e-5);
+    assert_eq!(child.rotation, std::f32::consts::FRAC_PI_2);
+    assert_eq!(child.scale, vec2(2., 2.));
+    assert_eq!(Transform::IDENTITY.combine(child), child);
+}
@@ -0,0 +1,270 @@
+//! Values going from one to another over time, with an easing curve.
+//!
+//! A [Tween] is updated by hand, with the frame time or a fixed timestep:
+//! ```
+//! use macroquad::experimental::tween::{Easing, Tween};
+//! use macroquad::math::vec2;
+//!
+//! let mut popup = Tween::new(vec2(0., -200.), vec2(0., 100.), 0.3, Easing::CubicOut);
+//! popup.update(0.3);
+//! assert_eq!(popup.value(), vec2(0., 100.));
+//! assert!(popup.finished());
+//! ```
+//! From a coroutine, [animate] does it over the next frames:
+//! ```ignore
+//! start_coroutine(async move {
+//!     animate(0.3, Easing::QuadOut, |t| scene::get_node(popup).alpha = t).await;
+//! });
+//! ```
+
+use crate::{
+    color::Color,
+    experimental::coroutines::{frame_delta, wait_frames},
+    math::{Vec2, Vec3},
+};
+
+use std::f32::consts::PI;
+
+/// Values a [Tween] can animate.
+pub trait Lerp: Copy {
+    /// `self` at `t == 0`, `to` at `t == 1`. `t` may go past these with some easings.
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, to: f32, t: f32) -> f32 {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Vec2 {
+    fn lerp(self, to: Vec2, t: f32) -> Vec2 {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Vec3 {
+    fn lerp(self, to: Vec3, t: f32) -> Vec3 {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(self, to: Color, t: f32) -> Color {
+        Color::new(
+            self.r.lerp(to.r, t),
+            self.g.lerp(to.g, t),
+            self.b.lerp(to.b, t),
+            self.a.lerp(to.a, t),
+        )
+    }
+}
+
+/// The curves from <https://easings.net>.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+impl Easing {
+    /// Maps the progress `t`, clamped to `0..=1`, through the curve.
+    /// 0 gives 0 and 1 gives 1, the elastic curves overshoot in between.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        if t == 0. || t == 1. {
+            return t;
+        }
+
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1. - (1. - t) * (1. - t),
+            Easing::QuadInOut if t < 0.5 => 2. * t * t,
+            Easing::QuadInOut => 1. - (-2. * t + 2.).powi(2) / 2.,
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1. - (1. - t).powi(3),
+            Easing::CubicInOut if t < 0.5 => 4. * t * t * t,
+            Easing::CubicInOut => 1. - (-2. * t + 2.).powi(3) / 2.,
+            Easing::ElasticIn => {
+                -(2f32.powf(10. * t - 10.)) * ((10. * t - 10.75) * (2. * PI / 3.)).sin()
+            }
+            Easing::ElasticOut => {
+                2f32.powf(-10. * t) * ((10. * t - 0.75) * (2. * PI / 3.)).sin() + 1.
+            }
+            Easing::ElasticInOut => {
+                let wave = ((20. * t - 11.125) * (2. * PI / 4.5)).sin();
+                if t < 0.5 {
+                    -(2f32.powf(20. * t - 10.) * wave) / 2.
+                } else {
+                    2f32.powf(-20. * t + 10.) * wave / 2. + 1.
+                }
+            }
+            Easing::BounceIn => 1. - bounce_out(1. - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut if t < 0.5 => (1. - bounce_out(1. - 2. * t)) / 2.,
+            Easing::BounceInOut => (1. + bounce_out(2. * t - 1.)) / 2.,
+        }
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1. / D {
+        N * t * t
+    } else if t < 2. / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Goes from `from` to `to` in `duration` seconds of [Tween::update].
+#[derive(Clone, Copy, Debug)]
+pub struct Tween<T: Lerp> {
+    from: T,
+    to: T,
+    duration: f32,
+    easing: Easing,
+    elapsed: f32,
+}
+
+impl<T: Lerp> Tween<T> {
+    pub const fn new(from: T, to: T, duration: f32, easing: Easing) -> Tween<T> {
+        Tween {
+            from,
+            to,
+            duration,
+            easing,
+            elapsed: 0.,
+        }
+    }
+
+    /// Advance the tween by `dt` seconds, it stops at the end.
+    pub fn update(&mut self, dt: f32) {
+        self.elapsed = (self.elapsed + dt).min(self.duration.max(0.));
+    }
+
+    /// From 0 at the start to 1 at the end, before the easing.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0. {
+            return 1.;
+        }
+        self.elapsed / self.duration
+    }
+
+    /// `to` exactly once finished.
+    pub fn value(&self) -> T {
+        if self.finished() {
+            return self.to;
+        }
+        self.from.lerp(self.to, self.easing.apply(self.progress()))
+    }
+
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Start over from `from`.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.;
+    }
+}
+
+/// Calls `f` once a frame for `duration` seconds of the coroutine's clock,
+/// with the eased progress from 0 to 1. The last call is always with 1.
+///
+/// Like [wait_seconds](crate::experimental::coroutines::wait_seconds),
+/// manually polled coroutines go by the `delta_time` of their polls.
+pub async fn animate<F: FnMut(f32)>(duration: f32, easing: Easing, mut f: F) {
+    let mut tween = Tween::new(0., 1., duration, easing);
+
+    f(tween.value());
+    while !tween.finished() {
+        wait_frames(1).await;
+        tween.update(frame_delta());
+        f(tween.value());
+    }
+}
+
+#[test]
+fn easing_ends() {
+    use Easing::*;
+
+    for easing in [
+        Linear,
+        QuadIn,
+        QuadOut,
+        QuadInOut,
+        CubicIn,
+        CubicOut,
+        CubicInOut,
+        ElasticIn,
+        ElasticOut,
+        ElasticInOut,
+        BounceIn,
+        BounceOut,
+        BounceInOut,
+    ] {
+        assert_eq!(easing.apply(0.), 0., "{easing:?}");
+        assert_eq!(easing.apply(1.), 1., "{easing:?}");
+        assert_eq!(easing.apply(-1.), 0., "{easing:?}");
+        assert_eq!(easing.apply(2.), 1., "{easing:?}");
+        // continuous at the middle of the in-outs
+        let middle = easing.apply(0.5);
+        assert!((easing.apply(0.4999) - middle).abs() < 1e-2, "{easing:?}");
+        assert!((easing.apply(0.5001) - middle).abs() < 1e-2, "{easing:?}");
+    }
+}
+
+#[test]
+fn tween_fixed_timestep() {
+    let run = |steps: usize, dt: f32| {
+        let mut tween = Tween::new(Vec2::ZERO, Vec2::new(10., -10.), 1., Easing::BounceOut);
+        let values: Vec<_> = (0..steps)
+            .map(|_| {
+                tween.update(dt);
+                tween.value()
+            })
+            .collect();
+        (values, tween)
+    };
+
+    // the same steps give the same values
+    assert_eq!(run(90, 1. / 60.).0, run(90, 1. / 60.).0);
+
+    // clamped at the end
+    let (_, tween) = run(90, 1. / 60.);
+    assert!(tween.finished());
+    assert_eq!(tween.progress(), 1.);
+    assert_eq!(tween.value(), Vec2::new(10., -10.));
+
+    let (values, tween) = run(1, 5.);
+    assert_eq!(values, [Vec2::new(10., -10.)]);
+    assert!(tween.finished());
+
+    let mut empty = Tween::new(1., 2., 0., Easing::Linear);
+    assert!(empty.finished());
+    assert_eq!(empty.value(), 2.);
+    empty.update(1.);
+    assert_eq!(empty.value(), 2.);
+}
@@ -2,12 +2,18 @@
 
 use crate::{exec, Error};
 
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
 /// Load file from the path and block until its loaded
 /// Will use filesystem on PC and do http request on web
 pub async fn load_file(path: &str) -> Result<Vec<u8>, Error> {
     fn load_file_inner(path: &str) -> exec::FileLoadingFuture {
-        use std::sync::{Arc, Mutex};
-
         let contents = Arc::new(Mutex::new(None));
         let path = path.to_owned();
 
@@ -26,6 +32,10 @@ pub async fn load_file(path: &str) -> Result<Vec<u8>, Error> {
         exec::FileLoadingFuture { contents }
     }
 
+    load_file_inner(&resolve_path(path)).await
+}
+
+fn resolve_path(path: &str) -> String {
     #[cfg(target_os = "ios")]
     let _ = std::env::set_current_dir(std::env::current_exe().unwrap().parent().unwrap());
 
@@ -36,7 +46,141 @@ pub async fn load_file(path: &str) -> Result<Vec<u8>, Error> {
         path.to_string()
     };
 
-    load_file_inner(&path).await
+    #[cfg(target_os = "android")]
+    let path = path.to_string();
+
+    path
+}
+
+/// Shared view on how much of a file requested with [load_file_with_progress] is already loaded.
+#[derive(Clone, Debug, Default)]
+pub struct FileProgress {
+    loaded: Arc<AtomicU64>,
+    total: Arc<AtomicU64>,
+}
+
+impl FileProgress {
+    /// Amount of bytes already loaded.
+    pub fn bytes_loaded(&self) -> u64 {
+        self.loaded.load(Ordering::Relaxed)
+    }
+
+    /// File size, if already known.
+    pub fn bytes_total(&self) -> Option<u64> {
+        match self.total.load(Ordering::Relaxed) {
+            0 => None,
+            total => Some(total),
+        }
+    }
+
+    /// Loaded part of the file, from 0.0 to 1.0.
+    /// None while the file size is not known yet.
+    pub fn fraction(&self) -> Option<f32> {
+        self.bytes_total()
+            .map(|total| (self.bytes_loaded() as f64 / total as f64).min(1.) as f32)
+    }
+
+    fn finish(&self, len: u64) {
+        self.total.store(len, Ordering::Relaxed);
+        self.loaded.store(len, Ordering::Relaxed);
+    }
+}
+
+/// Same as [load_file], but also returns a [FileProgress] to display a loading bar with.
+///
+/// On desktop the file is read on a separate thread and the progress updates as the chunks
+/// arrive.
+///
+/// # Limitation
+///
+/// On web, android and ios there is no progress: the backend hands over the file in one piece,
+/// the request's own progress events are not forwarded. [FileProgress::bytes_total] and
+/// [FileProgress::fraction] stay None until the file is loaded and then jump to the end,
+/// so show a spinner rather than a bar while they are None.
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # use macroquad::experimental::coroutines::start_coroutine;
+/// # async fn f() -> Result<(), macroquad::Error> {
+/// let (bytes, progress) = load_file_with_progress("atlas.png");
+/// let bytes = start_coroutine(bytes);
+/// while !bytes.is_done() {
+///     let fraction = progress.fraction().unwrap_or(0.);
+///     draw_rectangle(10., 10., 200. * fraction, 20., WHITE);
+///     next_frame().await;
+/// }
+/// let bytes = bytes.retrieve().unwrap()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn load_file_with_progress(
+    path: &str,
+) -> (
+    impl Future<Output = Result<Vec<u8>, Error>> + 'static,
+    FileProgress,
+) {
+    let path = resolve_path(path);
+    let progress = FileProgress::default();
+    let contents = Arc::new(Mutex::new(None));
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    {
+        let contents = contents.clone();
+        let progress = progress.clone();
+
+        std::thread::spawn(move || {
+            let bytes = read_with_progress(&path, &progress).map_err(|err| Error::FileError {
+                kind: miniquad::fs::Error::IOError(err),
+                path,
+            });
+            *contents.lock().unwrap() = Some(bytes);
+        });
+    }
+
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    {
+        let contents = contents.clone();
+        let progress = progress.clone();
+        let err_path = path.clone();
+
+        miniquad::fs::load_file(&path, move |bytes| {
+            if let Ok(ref bytes) = bytes {
+                progress.finish(bytes.len() as u64);
+            }
+            *contents.lock().unwrap() = Some(bytes.map_err(|kind| Error::FileError {
+                kind,
+                path: err_path.clone(),
+            }));
+        });
+    }
+
+    (exec::FileLoadingFuture { contents }, progress)
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+fn read_with_progress(path: &str, progress: &FileProgress) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    progress.total.store(len, Ordering::Relaxed);
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let mut chunk = vec![0; CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        bytes.extend_from_slice(&chunk[..read]);
+        progress.loaded.fetch_add(read as u64, Ordering::Relaxed);
+    }
+    // the file might have changed since the metadata call
+    progress.finish(bytes.len() as u64);
+
+    Ok(bytes)
 }
 
 /// Load string from the path and block until its loaded.
@@ -1,4 +1,4 @@
-//! Cross-platform mouse, keyboard (and gamepads soon) module.
+//! Cross-platform mouse, keyboard and gamepads module.
 
 use std::collections::HashSet;
 
@@ -6,7 +6,21 @@ use crate::prelude::screen_height;
 use crate::prelude::screen_width;
 use crate::Vec2;
 use crate::{get_context, DroppedFile};
-pub use miniquad::{KeyCode, MouseButton};
+pub use miniquad::{CursorIcon, KeyCode, MouseButton};
+
+mod gamepad;
+pub mod gestures;
+mod input_map;
+
+pub(crate) use gamepad::GamepadsContext;
+pub use gamepad::{
+    gamepad_axis, gamepads, get_gamepad_events, is_gamepad_button_down, is_gamepad_button_pressed,
+    is_gamepad_button_released, set_gamepad_deadzone, GamepadAxis, GamepadButton, GamepadEvent,
+    GamepadId,
+};
+pub(crate) use gestures::GesturesContext;
+pub use gestures::{gestures, set_gesture_settings, Gesture, GestureSettings};
+pub use input_map::{Binding, InputMap};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TouchPhase {
@@ -28,32 +42,114 @@ impl From<miniquad::TouchPhase> for TouchPhase {
     }
 }
 
+/// A finger on the screen, see [touches].
 #[derive(Clone, Debug)]
 pub struct Touch {
+    /// Stays the same from the moment the finger goes down to the moment it is lifted,
+    /// the next finger may get it again.
     pub id: u64,
     pub phase: TouchPhase,
     pub position: Vec2,
 }
 
-/// Constrain mouse to window
+/// The id of the touch made of the mouse, see [simulate_touch_with_mouse].
+pub const MOUSE_TOUCH_ID: u64 = u64::MAX;
+
+/// Constrain mouse to window, for the mouse look of a first person camera.
+///
+/// While grabbed, the mouse position is no longer the one of the pointer: it moves by the raw
+/// motion of the mouse and goes on past the window borders, so [mouse_delta_position] keeps
+/// reporting the motion when the pointer would have left the window.
+/// Usually goes with `show_mouse(false)`.
+///
+/// On the web this is the Pointer Lock API, which browsers only grant in response to
+/// a user gesture: the pointer is locked on the next click or key press in the canvas
+/// rather than right away. The browser releases the lock on Escape, and the next click
+/// locks it again as long as the grab is on.
 pub fn set_cursor_grab(grab: bool) {
     let context = get_context();
     context.cursor_grabbed = grab;
     miniquad::window::set_cursor_grab(grab);
 }
 
+/// Whether [set_cursor_grab] is on.
+pub fn is_cursor_grabbed() -> bool {
+    get_context().cursor_grabbed
+}
+
 /// Set mouse cursor visibility
+///
+/// The cursor is only hidden over the window.
 pub fn show_mouse(shown: bool) {
-    miniquad::window::show_mouse(shown);
+    let context = get_context();
+    context.mouse_shown = shown;
+    // the system cursor stays hidden under a cursor image
+    miniquad::window::show_mouse(shown && context.cursor_image.is_none());
+}
+
+/// What the cursor functions can do on the current platform.
+///
+/// Moving the mouse cursor from the game is not one of them: the windowing backend
+/// can't warp the cursor on any platform, [set_cursor_grab] is the way to keep it in the window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CursorCapabilities {
+    /// [set_mouse_cursor] changes the cursor shape.
+    pub system_cursors: bool,
+}
+
+/// What the cursor functions can do on the current platform.
+pub fn cursor_capabilities() -> CursorCapabilities {
+    CursorCapabilities {
+        system_cursors: !cfg!(any(target_os = "android", target_os = "ios")),
+    }
+}
+
+/// Change the cursor to one of the standard system shapes.
+/// Removes the [set_mouse_cursor_image] image.
+///
+/// Every [CursorIcon] maps to the native cursor of the platform, the CSS `cursor` on the web.
+/// A shape a platform does not have falls back to the closest one it has, or to the default
+/// arrow; without [system cursors](CursorCapabilities::system_cursors) this does nothing.
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # let text_field = Rect::new(10., 10., 200., 30.);
+/// let icon = if text_field.contains(mouse_position().into()) {
+///     CursorIcon::Text
+/// } else {
+///     CursorIcon::Default
+/// };
+/// set_mouse_cursor(icon);
+/// ```
+pub fn set_mouse_cursor(icon: CursorIcon) {
+    let context = get_context();
+    context.cursor_image = None;
+    miniquad::window::show_mouse(context.mouse_shown);
+    miniquad::window::set_mouse_cursor(icon);
+}
+
+/// Use the image as the cursor, with the `hotspot_x, hotspot_y` pixel of the image
+/// at the mouse position.
+///
+/// The windowing backend has no custom cursors, so the system cursor is hidden and the image
+/// is drawn over everything at the end of the frame: it moves with the frame rate. [set_mouse_cursor] goes back to a system cursor.
+pub fn set_mouse_cursor_image(image: &crate::texture::Image, hotspot_x: f32, hotspot_y: f32) {
+    let texture = crate::texture::Texture2D::from_image(image);
+    texture.set_filter(crate::texture::FilterMode::Nearest);
+
+    let context = get_context();
+    context.cursor_image = Some((texture, Vec2::new(hotspot_x, hotspot_y)));
+    miniquad::window::show_mouse(false);
 }
 
 /// Return mouse position in pixels.
 pub fn mouse_position() -> (f32, f32) {
     let context = get_context();
+    let dpi_scale = context.dpi_scale();
 
     (
-        context.mouse_position.x / miniquad::window::dpi_scale(),
-        context.mouse_position.y / miniquad::window::dpi_scale(),
+        context.mouse_position.x / dpi_scale,
+        context.mouse_position.y / dpi_scale,
     )
 }
 
@@ -65,6 +161,9 @@ pub fn mouse_position_local() -> Vec2 {
 }
 
 /// Returns the difference between the current mouse position and the mouse position on the previous frame.
+///
+/// In the [-1; 1] range of [mouse_position_local], previous minus current.
+/// With [set_cursor_grab] this is the raw motion of the mouse, not limited by the window borders.
 pub fn mouse_delta_position() -> Vec2 {
     let context = get_context();
 
@@ -87,30 +186,90 @@ pub fn simulate_mouse_with_touch(option: bool) {
     get_context().simulate_mouse_with_touch = option;
 }
 
-/// Return touches with positions in pixels.
+/// False by default. When set to true, holding the left mouse button is reported
+/// by [touches] as a touch with the id [MOUSE_TOUCH_ID], to try touch controls on desktop.
+///
+/// The mouse makes no touch while a finger is down.
+pub fn simulate_touch_with_mouse(option: bool) {
+    get_context().simulate_touch_with_mouse = option;
+}
+
+pub fn is_simulating_touch_with_mouse() -> bool {
+    get_context().simulate_touch_with_mouse
+}
+
+/// Return touches with positions in pixels, sorted by id.
+///
+/// A touch is reported as `Ended` or `Cancelled` for one frame after the finger is lifted.
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// // pinch to zoom
+/// # let mut last_distance: Option<f32> = None;
+/// # let mut zoom = 1.;
+/// if let [a, b] = &touches()[..] {
+///     let distance = a.position.distance(b.position);
+///     if let Some(last_distance) = last_distance {
+///         zoom *= distance / last_distance;
+///     }
+///     last_distance = Some(distance);
+/// } else {
+///     last_distance = None;
+/// }
+/// ```
 pub fn touches() -> Vec<Touch> {
-    get_context().touches.values().cloned().collect()
+    let mut touches: Vec<_> = get_context().touches.values().cloned().collect();
+    touches.sort_by_key(|touch| touch.id);
+    touches
 }
 
 /// Return touches with positions in range [-1; 1].
 pub fn touches_local() -> Vec<Touch> {
-    get_context()
-        .touches
-        .values()
-        .map(|touch| {
-            let mut touch = touch.clone();
-            touch.position = convert_to_local(touch.position);
-            touch
-        })
-        .collect()
+    let mut touches = touches();
+    for touch in &mut touches {
+        touch.position = convert_to_local(touch.position);
+    }
+    touches
 }
 
+/// Scroll distance the platform reports for one wheel notch, in pixels.
+/// Used by `mouse_wheel_pixels` on platforms with notch-based wheel events.
+pub const MOUSE_WHEEL_LINE_HEIGHT: f32 = 20.;
+
+/// Return mouse wheel movement since the last frame, horizontal and vertical,
+/// in the units of the platform: wheel notches on Windows and Linux,
+/// pixels on the web and macOS.
 pub fn mouse_wheel() -> (f32, f32) {
     let context = get_context();
 
     (context.mouse_wheel.x, context.mouse_wheel.y)
 }
 
+/// Return mouse wheel movement since the last frame, horizontal and vertical, in pixels.
+///
+/// Unlike `mouse_wheel`, the result has the same scale on all platforms:
+/// notch-based deltas are multiplied by `MOUSE_WHEEL_LINE_HEIGHT`,
+/// pixel-based deltas of trackpads (web and macOS) are passed through
+/// unquantized, which is nice for smooth scrolling.
+///
+/// # Approximation
+/// miniquad's wheel event does not carry the delta mode, so the unit is guessed
+/// from the target platform, not from the event itself. A browser reporting
+/// lines or pages, or a Linux or Windows touchpad sending smooth pixel deltas,
+/// will come out mis-scaled.
+pub fn mouse_wheel_pixels() -> Vec2 {
+    let context = get_context();
+
+    if cfg!(any(
+        target_arch = "wasm32",
+        target_os = "macos",
+        target_os = "ios"
+    )) {
+        context.mouse_wheel
+    } else {
+        context.mouse_wheel * MOUSE_WHEEL_LINE_HEIGHT
+    }
+}
+
 /// Detect if the key has been pressed once
 pub fn is_key_pressed(key_code: KeyCode) -> bool {
     let context = get_context();
@@ -118,6 +277,15 @@ pub fn is_key_pressed(key_code: KeyCode) -> bool {
     context.keys_pressed.contains(&key_code)
 }
 
+/// Detect if the key has been pressed once or repeated by the OS key repeat
+/// while being held down. Follows the platform's repeat delay and rate.
+/// `is_key_repeated(key) && !is_key_pressed(key)` is true only for the repeats.
+pub fn is_key_repeated(key_code: KeyCode) -> bool {
+    let context = get_context();
+
+    context.keys_repeated.contains(&key_code)
+}
+
 /// Detect if the key is being pressed
 pub fn is_key_down(key_code: KeyCode) -> bool {
     let context = get_context();
@@ -132,18 +300,47 @@ pub fn is_key_released(key_code: KeyCode) -> bool {
     context.keys_released.contains(&key_code)
 }
 
-/// Return the last pressed char.
-/// Each "get_char_pressed" call will consume a character from the input queue.
+/// Return the next typed character, with the keyboard layout, shift and key repeat applied.
+/// Each "get_char_pressed" call will consume a character from the input queue,
+/// in the order they were typed. The characters not consumed are dropped at the end of the frame.
+///
+/// Characters made of several key presses, with dead keys or an input method,
+/// come once they are complete, as one character per codepoint.
+/// Control characters are left out: backspace, enter and tab are read
+/// with [is_key_pressed] as before.
+///
+/// While a root_ui widget has the keyboard focus the characters go to the UI
+/// and this returns None, see [is_keyboard_captured].
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # let mut message = String::new();
+/// while let Some(character) = get_char_pressed() {
+///     message.push(character);
+/// }
+/// if is_key_pressed(KeyCode::Backspace) {
+///     message.pop();
+/// }
+/// ```
 pub fn get_char_pressed() -> Option<char> {
     let context = get_context();
 
-    context.chars_pressed_queue.pop()
+    if context.keyboard_captured_by_ui {
+        return None;
+    }
+    context.chars_pressed_queue.pop_front()
 }
 
 pub(crate) fn get_char_pressed_ui() -> Option<char> {
     let context = get_context();
 
-    context.chars_pressed_ui_queue.pop()
+    context.chars_pressed_ui_queue.pop_front()
+}
+
+/// Whether a root_ui widget, like an editbox, has the keyboard focus this frame.
+/// The typed characters then go to the UI rather than to [get_char_pressed].
+pub fn is_keyboard_captured() -> bool {
+    get_context().keyboard_captured_by_ui
 }
 
 /// Return the last pressed key.
@@ -153,6 +350,7 @@ pub fn get_last_key_pressed() -> Option<KeyCode> {
     context.keys_pressed.iter().next().cloned()
 }
 
+/// All the keys pressed once this frame, see [is_key_pressed].
 pub fn get_keys_pressed() -> HashSet<KeyCode> {
     let context = get_context();
     context.keys_pressed.clone()
@@ -212,11 +410,44 @@ pub fn is_quit_requested() -> bool {
     get_context().quit_requested
 }
 
-/// Gets the files which have been dropped on the window.
+/// Gets the files which have been dropped on the window during this frame,
+/// every call within the frame returns the same files.
+///
+/// Every call returns a copy of the files with their contents, call it once a frame
+/// and keep the result rather than calling it for each use.
 pub fn get_dropped_files() -> Vec<DroppedFile> {
     get_context().dropped_files()
 }
 
+/// Whether files are being dragged over the window, to highlight a drop zone
+/// before [get_dropped_files] has them.
+///
+/// Only reported on the web, with the "drop-hover" feature and `js/drop_hover.js` loaded
+/// after `mq_js_bundle.js`. The desktop windowing backends say nothing about the files
+/// before they are dropped, there this is always false.
+pub fn is_file_hovered() -> bool {
+    #[cfg(all(feature = "drop-hover", target_arch = "wasm32"))]
+    {
+        // implemented in js/drop_hover.js
+        extern "C" {
+            fn macroquad_drop_hovered() -> u32;
+        }
+        unsafe { macroquad_drop_hovered() != 0 }
+    }
+
+    #[cfg(not(all(feature = "drop-hover", target_arch = "wasm32")))]
+    false
+}
+
+/// The paths of [get_dropped_files], for the files that have one.
+pub fn dropped_file_paths() -> Vec<std::path::PathBuf> {
+    get_context()
+        .dropped_files
+        .iter()
+        .filter_map(|file| file.path.clone())
+        .collect()
+}
+
 /// Functions for advanced input processing.
 ///
 /// Functions in this module should be used by external tools that uses miniquad system, like different UI libraries. User shouldn't use this function.
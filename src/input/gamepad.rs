@@ -0,0 +1,437 @@
+//! Gamepad state, polled once per frame.
+//!
+//! Gamepads are available with the "gamepad" feature enabled.
+//! Native platforms use gilrs. On the web the browser Gamepad API is used,
+//! with the `js/gamepad.js` plugin loaded after `mq_js_bundle.js`; browsers only
+//! report a gamepad after one of its buttons is pressed, and only gamepads with
+//! the "standard" mapping are supported.
+//! Without a backend `gamepads()` is always empty and all the queries return neutral values.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::get_context;
+
+/// Identifier of a connected gamepad.
+/// Stays the same until the gamepad is disconnected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GamepadId(pub(crate) usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    /// Bottom face button, "A" on Xbox controllers, "Cross" on PlayStation.
+    South,
+    /// Right face button, "B" on Xbox controllers, "Circle" on PlayStation.
+    East,
+    /// Top face button, "Y" on Xbox controllers, "Triangle" on PlayStation.
+    North,
+    /// Left face button, "X" on Xbox controllers, "Square" on PlayStation.
+    West,
+    LeftBumper,
+    LeftTrigger,
+    RightBumper,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    /// -1.0 is left, 1.0 is right.
+    LeftStickX,
+    /// -1.0 is down, 1.0 is up.
+    LeftStickY,
+    /// -1.0 is left, 1.0 is right.
+    RightStickX,
+    /// -1.0 is down, 1.0 is up.
+    RightStickY,
+    /// 0.0 is released, 1.0 is fully pressed.
+    LeftTrigger,
+    /// 0.0 is released, 1.0 is fully pressed.
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    const fn is_stick(self) -> bool {
+        !matches!(self, GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GamepadEvent {
+    Connected(GamepadId),
+    Disconnected(GamepadId),
+}
+
+#[derive(Default)]
+struct GamepadState {
+    buttons_down: HashSet<GamepadButton>,
+    buttons_pressed: HashSet<GamepadButton>,
+    buttons_released: HashSet<GamepadButton>,
+    axes: HashMap<GamepadAxis, f32>,
+    /// The axes at the end of the last frame.
+    previous_axes: HashMap<GamepadAxis, f32>,
+}
+
+pub(crate) struct GamepadsContext {
+    #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+    gilrs: Option<gilrs::Gilrs>,
+    gamepads: HashMap<GamepadId, GamepadState>,
+    events: Vec<GamepadEvent>,
+    deadzone: f32,
+}
+
+impl GamepadsContext {
+    pub fn new() -> GamepadsContext {
+        #[allow(unused_mut)]
+        let mut context = GamepadsContext {
+            #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+            gilrs: None,
+            gamepads: HashMap::new(),
+            events: vec![],
+            deadzone: 0.1,
+        };
+
+        #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+        match gilrs::Gilrs::new() {
+            Ok(gilrs) => {
+                // gamepads plugged in before the start will not report "Connected"
+                for (id, gamepad) in gilrs.gamepads() {
+                    if gamepad.is_connected() {
+                        context.connect(GamepadId(id.into()));
+                    }
+                }
+                context.gilrs = Some(gilrs);
+            }
+            Err(err) => crate::logging::warn!("Gamepads are not available: {}", err),
+        }
+
+        context
+    }
+
+    fn connect(&mut self, id: GamepadId) {
+        if self.gamepads.insert(id, GamepadState::default()).is_none() {
+            self.events.push(GamepadEvent::Connected(id));
+        }
+    }
+
+    fn disconnect(&mut self, id: GamepadId) {
+        if self.gamepads.remove(&id).is_some() {
+            self.events.push(GamepadEvent::Disconnected(id));
+        }
+    }
+
+    fn press(&mut self, id: GamepadId, button: GamepadButton) {
+        self.connect(id);
+        let state = self.gamepads.get_mut(&id).unwrap();
+        if state.buttons_down.insert(button) {
+            state.buttons_pressed.insert(button);
+        }
+    }
+
+    fn release(&mut self, id: GamepadId, button: GamepadButton) {
+        if let Some(state) = self.gamepads.get_mut(&id) {
+            if state.buttons_down.remove(&button) {
+                state.buttons_released.insert(button);
+            }
+        }
+    }
+
+    fn set_axis(&mut self, id: GamepadId, axis: GamepadAxis, value: f32) {
+        self.connect(id);
+        self.gamepads.get_mut(&id).unwrap().axes.insert(axis, value);
+    }
+
+    /// Process all the backend events arrived since the last frame.
+    pub(crate) fn update(&mut self) {
+        #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+        while let Some(gilrs::Event { id, event, .. }) =
+            self.gilrs.as_mut().and_then(|gilrs| gilrs.next_event())
+        {
+            use gilrs::EventType;
+
+            let id = GamepadId(id.into());
+            match event {
+                EventType::Connected => self.connect(id),
+                EventType::Disconnected => self.disconnect(id),
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(button) = gilrs_button(button) {
+                        self.press(id, button);
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(button) = gilrs_button(button) {
+                        self.release(id, button);
+                    }
+                }
+                EventType::ButtonChanged(button, value, _) => {
+                    let axis = match button {
+                        gilrs::Button::LeftTrigger2 => GamepadAxis::LeftTrigger,
+                        gilrs::Button::RightTrigger2 => GamepadAxis::RightTrigger,
+                        _ => continue,
+                    };
+                    self.set_axis(id, axis, value);
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    let axis = match axis {
+                        gilrs::Axis::LeftStickX => GamepadAxis::LeftStickX,
+                        gilrs::Axis::LeftStickY => GamepadAxis::LeftStickY,
+                        gilrs::Axis::RightStickX => GamepadAxis::RightStickX,
+                        gilrs::Axis::RightStickY => GamepadAxis::RightStickY,
+                        _ => continue,
+                    };
+                    self.set_axis(id, axis, value);
+                }
+                _ => {}
+            }
+        }
+
+        #[cfg(all(feature = "gamepad", target_arch = "wasm32"))]
+        self.poll_web();
+    }
+
+    /// The Gamepad API has no events for the buttons, so the state is diffed with the last frame.
+    #[cfg(all(feature = "gamepad", target_arch = "wasm32"))]
+    fn poll_web(&mut self) {
+        use web::*;
+
+        let slots = unsafe { macroquad_gamepad_slots() };
+        for index in 0..slots {
+            let id = GamepadId(index as usize);
+            if unsafe { macroquad_gamepad_connected(index) } == 0 {
+                self.disconnect(id);
+                continue;
+            }
+            self.connect(id);
+
+            for (i, button) in STANDARD_BUTTONS.iter().enumerate() {
+                if unsafe { macroquad_gamepad_button(index, i as u32) } > 0.5 {
+                    self.press(id, *button);
+                } else {
+                    self.release(id, *button);
+                }
+            }
+            for (i, axis, sign) in STANDARD_AXES {
+                let value = unsafe { macroquad_gamepad_axis(index, i) };
+                self.set_axis(id, axis, value * sign);
+            }
+            for (i, axis) in [
+                (6, GamepadAxis::LeftTrigger),
+                (7, GamepadAxis::RightTrigger),
+            ] {
+                let value = unsafe { macroquad_gamepad_button(index, i) };
+                self.set_axis(id, axis, value);
+            }
+        }
+
+        // slots the browser no longer reports
+        let gone: Vec<GamepadId> = self
+            .gamepads
+            .keys()
+            .copied()
+            .filter(|id| id.0 >= slots as usize)
+            .collect();
+        for id in gone {
+            self.disconnect(id);
+        }
+    }
+
+    pub(crate) fn end_frame(&mut self) {
+        self.events.clear();
+        for state in self.gamepads.values_mut() {
+            state.buttons_pressed.clear();
+            state.buttons_released.clear();
+            state.previous_axes.clone_from(&state.axes);
+        }
+    }
+
+    /// The axis with the dead zone applied to the sticks, this frame or the last one.
+    fn axis(&self, id: GamepadId, axis: GamepadAxis, previous: bool) -> f32 {
+        let value = self
+            .gamepads
+            .get(&id)
+            .and_then(|gamepad| {
+                if previous {
+                    gamepad.previous_axes.get(&axis)
+                } else {
+                    gamepad.axes.get(&axis)
+                }
+            })
+            .copied()
+            .unwrap_or(0.);
+
+        if !axis.is_stick() {
+            return value;
+        }
+
+        let deadzone = self.deadzone;
+        if value.abs() <= deadzone {
+            0.
+        } else {
+            // rescale so the output still starts from 0 right outside the dead zone
+            value.signum() * (value.abs() - deadzone) / (1. - deadzone)
+        }
+    }
+}
+
+#[cfg(all(feature = "gamepad", target_arch = "wasm32"))]
+mod web {
+    use super::{GamepadAxis, GamepadButton};
+
+    // implemented in js/gamepad.js
+    extern "C" {
+        pub fn macroquad_gamepad_slots() -> u32;
+        pub fn macroquad_gamepad_connected(index: u32) -> u32;
+        pub fn macroquad_gamepad_button(index: u32, button: u32) -> f32;
+        pub fn macroquad_gamepad_axis(index: u32, axis: u32) -> f32;
+    }
+
+    /// Buttons in the order of the "standard" Gamepad API mapping.
+    pub const STANDARD_BUTTONS: [GamepadButton; 17] = [
+        GamepadButton::South,
+        GamepadButton::East,
+        GamepadButton::West,
+        GamepadButton::North,
+        GamepadButton::LeftBumper,
+        GamepadButton::RightBumper,
+        GamepadButton::LeftTrigger,
+        GamepadButton::RightTrigger,
+        GamepadButton::Select,
+        GamepadButton::Start,
+        GamepadButton::LeftThumb,
+        GamepadButton::RightThumb,
+        GamepadButton::DPadUp,
+        GamepadButton::DPadDown,
+        GamepadButton::DPadLeft,
+        GamepadButton::DPadRight,
+        GamepadButton::Mode,
+    ];
+
+    /// The standard mapping has the Y axes pointing down.
+    pub const STANDARD_AXES: [(u32, GamepadAxis, f32); 4] = [
+        (0, GamepadAxis::LeftStickX, 1.),
+        (1, GamepadAxis::LeftStickY, -1.),
+        (2, GamepadAxis::RightStickX, 1.),
+        (3, GamepadAxis::RightStickY, -1.),
+    ];
+}
+
+#[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+fn gilrs_button(button: gilrs::Button) -> Option<GamepadButton> {
+    use gilrs::Button;
+
+    Some(match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::North => GamepadButton::North,
+        Button::West => GamepadButton::West,
+        Button::LeftTrigger => GamepadButton::LeftBumper,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+        Button::RightTrigger => GamepadButton::RightBumper,
+        Button::RightTrigger2 => GamepadButton::RightTrigger,
+        Button::Select => GamepadButton::Select,
+        Button::Start => GamepadButton::Start,
+        Button::Mode => GamepadButton::Mode,
+        Button::LeftThumb => GamepadButton::LeftThumb,
+        Button::RightThumb => GamepadButton::RightThumb,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+/// Ids of all currently connected gamepads.
+pub fn gamepads() -> impl Iterator<Item = GamepadId> {
+    let mut ids: Vec<GamepadId> = get_context().gamepads.gamepads.keys().copied().collect();
+    ids.sort();
+    ids.into_iter()
+}
+
+/// Detect if the gamepad button is being pressed.
+/// Always false for a disconnected gamepad.
+pub fn is_gamepad_button_down(id: GamepadId, button: GamepadButton) -> bool {
+    get_context()
+        .gamepads
+        .gamepads
+        .get(&id)
+        .is_some_and(|gamepad| gamepad.buttons_down.contains(&button))
+}
+
+/// Detect if the gamepad button has been pressed once this frame.
+/// Follows the same frame boundaries as [is_key_pressed](crate::input::is_key_pressed).
+pub fn is_gamepad_button_pressed(id: GamepadId, button: GamepadButton) -> bool {
+    get_context()
+        .gamepads
+        .gamepads
+        .get(&id)
+        .is_some_and(|gamepad| gamepad.buttons_pressed.contains(&button))
+}
+
+/// Detect if the gamepad button has been released this frame.
+/// False for a gamepad disconnected while the button was down.
+pub fn is_gamepad_button_released(id: GamepadId, button: GamepadButton) -> bool {
+    get_context()
+        .gamepads
+        .gamepads
+        .get(&id)
+        .is_some_and(|gamepad| gamepad.buttons_released.contains(&button))
+}
+
+/// Current value of the gamepad axis, with the dead zone applied to the sticks.
+/// Always 0.0 for a disconnected gamepad.
+pub fn gamepad_axis(id: GamepadId, axis: GamepadAxis) -> f32 {
+    get_context().gamepads.axis(id, axis, false)
+}
+
+/// [gamepad_axis] as it was at the end of the last frame.
+pub(crate) fn previous_gamepad_axis(id: GamepadId, axis: GamepadAxis) -> f32 {
+    get_context().gamepads.axis(id, axis, true)
+}
+
+/// Stick values below this threshold are reported as 0.0 by `gamepad_axis`.
+/// 0.1 by default.
+pub fn set_gamepad_deadzone(deadzone: f32) {
+    get_context().gamepads.deadzone = deadzone.clamp(0., 0.99);
+}
+
+/// Gamepads connected or disconnected since the last frame.
+pub fn get_gamepad_events() -> Vec<GamepadEvent> {
+    get_context().gamepads.events.clone()
+}
+
+#[test]
+fn gamepad_button_edges() {
+    let mut context = GamepadsContext::new();
+    let id = GamepadId(100);
+
+    context.press(id, GamepadButton::South);
+    context.press(id, GamepadButton::South);
+    assert_eq!(context.events, [GamepadEvent::Connected(id)]);
+    let state = &context.gamepads[&id];
+    assert!(state.buttons_down.contains(&GamepadButton::South));
+    assert!(state.buttons_pressed.contains(&GamepadButton::South));
+
+    // held down, pressed only on the first frame
+    context.end_frame();
+    context.press(id, GamepadButton::South);
+    let state = &context.gamepads[&id];
+    assert!(state.buttons_down.contains(&GamepadButton::South));
+    assert!(state.buttons_pressed.is_empty());
+
+    context.end_frame();
+    context.release(id, GamepadButton::South);
+    let state = &context.gamepads[&id];
+    assert!(state.buttons_down.is_empty());
+    assert!(state.buttons_released.contains(&GamepadButton::South));
+
+    context.disconnect(id);
+    assert_eq!(context.events, [GamepadEvent::Disconnected(id)]);
+}
@@ -0,0 +1,300 @@
+//! Taps, long presses, swipes, pinches and pans recognized from the touch events.
+//!
+//! Positions are in pixels, the same as in [touches](crate::input::touches).
+
+use std::collections::HashMap;
+
+use crate::{get_context, math::Vec2};
+
+use super::TouchPhase;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    /// A single finger touched and got lifted quickly, without moving.
+    Tap { pos: Vec2 },
+    /// A single finger has been held down without moving, reported once per touch,
+    /// while the finger is still down.
+    LongPress { pos: Vec2 },
+    /// Two or more fingers moved apart or together.
+    /// `scale_delta` is the change of the fingers spread since the last frame,
+    /// above 1.0 when spreading out, multiply the zoom by it for pinch-to-zoom.
+    Pinch { center: Vec2, scale_delta: f32 },
+    /// Two or more fingers moved together, `delta` is the movement of their center
+    /// since the last frame.
+    Pan { delta: Vec2 },
+    /// A single finger moved quickly and got lifted.
+    /// `dir` is normalized, `velocity` is in pixels per second.
+    Swipe { dir: Vec2, velocity: f32 },
+}
+
+/// Thresholds of the gesture recognition, the defaults are tuned for phones.
+#[derive(Clone, Copy, Debug)]
+pub struct GestureSettings {
+    /// Longest touch in seconds that still counts as a tap.
+    pub tap_max_time: f32,
+    /// How far in pixels a finger may move during a tap or a long press.
+    pub tap_max_distance: f32,
+    /// How long in seconds a finger has to be held for a long press.
+    pub long_press_time: f32,
+    /// Shortest distance in pixels for a swipe.
+    pub swipe_min_distance: f32,
+    /// Slowest speed in pixels per second for a swipe.
+    pub swipe_min_velocity: f32,
+}
+
+impl Default for GestureSettings {
+    fn default() -> GestureSettings {
+        GestureSettings {
+            tap_max_time: 0.3,
+            tap_max_distance: 10.,
+            long_press_time: 0.5,
+            swipe_min_distance: 50.,
+            swipe_min_velocity: 300.,
+        }
+    }
+}
+
+struct TrackedTouch {
+    start_pos: Vec2,
+    start_time: f64,
+    pos: Vec2,
+    /// Moved further than `tap_max_distance` since the start.
+    moved: bool,
+    long_pressed: bool,
+}
+
+/// Center and spread of the fingers, with the ids they were measured from.
+struct MultiTouch {
+    ids: Vec<u64>,
+    center: Vec2,
+    spread: f32,
+}
+
+#[derive(Default)]
+pub(crate) struct GesturesContext {
+    settings: GestureSettings,
+    touches: HashMap<u64, TrackedTouch>,
+    /// More than one finger was down since all the fingers were lifted last time,
+    /// taps, long presses and swipes are single finger only.
+    multi_touch: bool,
+    last_multi_touch: Option<MultiTouch>,
+    gestures: Vec<Gesture>,
+}
+
+impl GesturesContext {
+    pub(crate) fn touch_event(&mut self, phase: TouchPhase, id: u64, pos: Vec2, time: f64) {
+        let settings = self.settings;
+
+        match phase {
+            TouchPhase::Started => {
+                // a reused id is a new finger, the old one was lifted without an event
+                self.touches.insert(
+                    id,
+                    TrackedTouch {
+                        start_pos: pos,
+                        start_time: time,
+                        pos,
+                        moved: false,
+                        long_pressed: false,
+                    },
+                );
+                if self.touches.len() > 1 {
+                    self.multi_touch = true;
+                }
+            }
+            TouchPhase::Moved | TouchPhase::Stationary => {
+                if let Some(touch) = self.touches.get_mut(&id) {
+                    touch.pos = pos;
+                    if touch.pos.distance(touch.start_pos) > settings.tap_max_distance {
+                        touch.moved = true;
+                    }
+                }
+            }
+            TouchPhase::Ended => {
+                if let Some(touch) = self.touches.remove(&id) {
+                    let moved =
+                        touch.moved || pos.distance(touch.start_pos) > settings.tap_max_distance;
+                    let duration = (time - touch.start_time) as f32;
+
+                    if !self.multi_touch && !touch.long_pressed {
+                        let distance = pos - touch.start_pos;
+                        let velocity = distance.length() / duration.max(f32::EPSILON);
+
+                        if !moved && duration <= settings.tap_max_time {
+                            self.gestures.push(Gesture::Tap { pos });
+                        } else if distance.length() >= settings.swipe_min_distance
+                            && velocity >= settings.swipe_min_velocity
+                        {
+                            self.gestures.push(Gesture::Swipe {
+                                dir: distance.normalize(),
+                                velocity,
+                            });
+                        }
+                    }
+                }
+                self.end_touch();
+            }
+            TouchPhase::Cancelled => {
+                self.touches.remove(&id);
+                self.end_touch();
+            }
+        }
+    }
+
+    fn end_touch(&mut self) {
+        if self.touches.is_empty() {
+            self.multi_touch = false;
+        }
+    }
+
+    /// Recognizes the gestures of the fingers still down, once per frame.
+    pub(crate) fn update(&mut self, time: f64) {
+        if !self.multi_touch {
+            for touch in self.touches.values_mut() {
+                let held = (time - touch.start_time) as f32;
+                if !touch.moved && !touch.long_pressed && held >= self.settings.long_press_time {
+                    touch.long_pressed = true;
+                    self.gestures.push(Gesture::LongPress { pos: touch.pos });
+                }
+            }
+        }
+
+        if self.touches.len() < 2 {
+            self.last_multi_touch = None;
+            return;
+        }
+
+        let mut ids: Vec<u64> = self.touches.keys().copied().collect();
+        ids.sort();
+        let count = self.touches.len() as f32;
+        let center = self.touches.values().map(|touch| touch.pos).sum::<Vec2>() / count;
+        let spread = self
+            .touches
+            .values()
+            .map(|touch| touch.pos.distance(center))
+            .sum::<f32>()
+            / count;
+
+        // a finger added or lifted moves the center, measure again from there
+        if let Some(last) = self
+            .last_multi_touch
+            .as_ref()
+            .filter(|last| last.ids == ids)
+        {
+            let delta = center - last.center;
+            if delta != Vec2::ZERO {
+                self.gestures.push(Gesture::Pan { delta });
+            }
+            if last.spread > 0. && spread != last.spread {
+                self.gestures.push(Gesture::Pinch {
+                    center,
+                    scale_delta: spread / last.spread,
+                });
+            }
+        }
+
+        self.last_multi_touch = Some(MultiTouch {
+            ids,
+            center,
+            spread,
+        });
+    }
+
+    /// Forgets all the fingers, for when the app loses the touch events.
+    pub(crate) fn cancel(&mut self) {
+        self.touches.clear();
+        self.multi_touch = false;
+        self.last_multi_touch = None;
+    }
+
+    pub(crate) fn end_frame(&mut self) {
+        self.gestures.clear();
+    }
+}
+
+/// Gestures recognized this frame.
+pub fn gestures() -> Vec<Gesture> {
+    get_context().gestures.gestures.clone()
+}
+
+/// Changes the thresholds of the gesture recognition.
+pub fn set_gesture_settings(settings: GestureSettings) {
+    get_context().gestures.settings = settings;
+}
+
+#[test]
+fn tap_and_long_press() {
+    let mut context = GesturesContext::default();
+    let pos = Vec2::new(100., 100.);
+
+    // started and ended before the frame, the tap is still there
+    context.touch_event(TouchPhase::Started, 0, pos, 0.);
+    context.touch_event(TouchPhase::Ended, 0, pos, 0.1);
+    context.update(0.1);
+    assert_eq!(context.gestures, [Gesture::Tap { pos }]);
+    context.end_frame();
+
+    // the same id, reused for a finger that is held down
+    context.touch_event(TouchPhase::Started, 0, pos, 1.);
+    context.update(1.2);
+    assert!(context.gestures.is_empty());
+    context.update(1.6);
+    context.update(1.7);
+    assert_eq!(context.gestures, [Gesture::LongPress { pos }]);
+    context.end_frame();
+
+    context.touch_event(TouchPhase::Ended, 0, pos, 2.);
+    context.update(2.);
+    assert!(context.gestures.is_empty());
+}
+
+#[test]
+fn swipe() {
+    let mut context = GesturesContext::default();
+
+    context.touch_event(TouchPhase::Started, 3, Vec2::new(0., 0.), 0.);
+    context.touch_event(TouchPhase::Moved, 3, Vec2::new(0., 50.), 0.05);
+    context.touch_event(TouchPhase::Ended, 3, Vec2::new(0., 100.), 0.1);
+    assert_eq!(
+        context.gestures,
+        [Gesture::Swipe {
+            dir: Vec2::new(0., 1.),
+            velocity: 1000.
+        }]
+    );
+}
+
+#[test]
+fn pinch_and_pan() {
+    let mut context = GesturesContext::default();
+
+    // both fingers on the same frame
+    context.touch_event(TouchPhase::Started, 0, Vec2::new(0., 0.), 0.);
+    context.touch_event(TouchPhase::Started, 1, Vec2::new(100., 0.), 0.);
+    context.update(0.);
+    assert!(context.gestures.is_empty());
+
+    // spread twice as wide and moved right at once
+    context.touch_event(TouchPhase::Moved, 0, Vec2::new(-40., 0.), 0.1);
+    context.touch_event(TouchPhase::Moved, 1, Vec2::new(160., 0.), 0.1);
+    context.update(0.1);
+    assert_eq!(
+        context.gestures,
+        [
+            Gesture::Pan {
+                delta: Vec2::new(10., 0.)
+            },
+            Gesture::Pinch {
+                center: Vec2::new(60., 0.),
+                scale_delta: 2.
+            }
+        ]
+    );
+    context.end_frame();
+
+    // no tap or swipe from the fingers of a pinch
+    context.touch_event(TouchPhase::Ended, 0, Vec2::new(-40., 0.), 0.2);
+    context.touch_event(TouchPhase::Ended, 1, Vec2::new(160., 0.), 0.2);
+    context.update(0.2);
+    assert!(context.gestures.is_empty());
+}
@@ -0,0 +1,456 @@
+//! Named actions bound to keys, mouse buttons and gamepads.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::gamepad::previous_gamepad_axis;
+use super::{
+    gamepad_axis, gamepads, is_gamepad_button_down, is_gamepad_button_pressed,
+    is_gamepad_button_released, is_key_down, is_key_pressed, is_key_released, is_mouse_button_down,
+    is_mouse_button_pressed, is_mouse_button_released, GamepadAxis, GamepadButton, GamepadId,
+    KeyCode, MouseButton,
+};
+use crate::{get_context, Error};
+
+/// Half of a gamepad axis counts as down past this value.
+const AXIS_THRESHOLD: f32 = 0.5;
+
+/// One input an action can be bound to.
+/// Gamepad bindings work with any of the connected gamepads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Binding {
+    Key(KeyCode),
+    MouseButton(MouseButton),
+    GamepadButton(GamepadButton),
+    /// The positive or the negative half of an axis, analog in [InputMap::axis].
+    /// As a button it is down past 0.5, pressed and released in the frames it crosses 0.5.
+    GamepadAxis {
+        axis: GamepadAxis,
+        positive: bool,
+    },
+}
+
+impl Binding {
+    fn is_down(&self) -> bool {
+        match *self {
+            Binding::Key(key) => is_key_down(key),
+            Binding::MouseButton(button) => is_mouse_button_down(button),
+            Binding::GamepadButton(button) => {
+                gamepads().any(|id| is_gamepad_button_down(id, button))
+            }
+            Binding::GamepadAxis { .. } => self.value() > AXIS_THRESHOLD,
+        }
+    }
+
+    fn is_pressed(&self) -> bool {
+        match *self {
+            Binding::Key(key) => is_key_pressed(key),
+            Binding::MouseButton(button) => is_mouse_button_pressed(button),
+            Binding::GamepadButton(button) => {
+                gamepads().any(|id| is_gamepad_button_pressed(id, button))
+            }
+            Binding::GamepadAxis { .. } => {
+                self.value() > AXIS_THRESHOLD && self.previous_value() <= AXIS_THRESHOLD
+            }
+        }
+    }
+
+    fn is_released(&self) -> bool {
+        match *self {
+            Binding::Key(key) => is_key_released(key),
+            Binding::MouseButton(button) => is_mouse_button_released(button),
+            Binding::GamepadButton(button) => {
+                gamepads().any(|id| is_gamepad_button_released(id, button))
+            }
+            Binding::GamepadAxis { .. } => {
+                self.value() <= AXIS_THRESHOLD && self.previous_value() > AXIS_THRESHOLD
+            }
+        }
+    }
+
+    /// From 0.0 to 1.0, analog only for the gamepad axes.
+    fn value(&self) -> f32 {
+        match *self {
+            Binding::GamepadAxis { axis, positive } => {
+                Self::strongest_axis(axis, positive, gamepad_axis)
+            }
+            _ => self.is_down() as i32 as f32,
+        }
+    }
+
+    /// The gamepad axis value at the end of the last frame, 0.0 for the other bindings.
+    fn previous_value(&self) -> f32 {
+        match *self {
+            Binding::GamepadAxis { axis, positive } => {
+                Self::strongest_axis(axis, positive, previous_gamepad_axis)
+            }
+            _ => 0.,
+        }
+    }
+
+    fn strongest_axis(
+        axis: GamepadAxis,
+        positive: bool,
+        value: fn(GamepadId, GamepadAxis) -> f32,
+    ) -> f32 {
+        gamepads()
+            .map(|id| value(id, axis) * if positive { 1. } else { -1. })
+            .fold(0., f32::max)
+    }
+}
+
+/// Maps action names, like "jump" or "move_x", to the inputs that trigger them.
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// let mut map = InputMap::new();
+/// map.bind(
+///     "jump",
+///     &[
+///         Binding::Key(KeyCode::Space),
+///         Binding::GamepadButton(GamepadButton::South),
+///     ],
+/// );
+/// map.bind_axis(
+///     "move_x",
+///     &[Binding::Key(KeyCode::A), Binding::Key(KeyCode::Left)],
+///     &[Binding::Key(KeyCode::D), Binding::Key(KeyCode::Right)],
+/// );
+///
+/// if map.is_pressed("jump") {
+///     // ...
+/// }
+/// let speed = map.axis("move_x") * 100.;
+/// ```
+///
+/// The map is saved and loaded as text, with `to_string()` and `parse()`,
+/// one action per line:
+/// ```text
+/// jump: Key(Space) GamepadButton(South)
+/// move_x: Key(A) Key(Left) GamepadAxis(LeftStickX-) | Key(D) Key(Right) GamepadAxis(LeftStickX+)
+/// ```
+///
+/// With the "serde" feature the map and the bindings also implement `Serialize`
+/// and `Deserialize`, the bindings in the same text form.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap {
+    actions: HashMap<String, Vec<Binding>>,
+    axes: HashMap<String, (Vec<Binding>, Vec<Binding>)>,
+}
+
+impl InputMap {
+    pub fn new() -> InputMap {
+        InputMap::default()
+    }
+
+    /// Binds the action to the inputs, replacing its old bindings.
+    pub fn bind(&mut self, action: &str, bindings: &[Binding]) {
+        self.actions.insert(action.to_string(), bindings.to_vec());
+    }
+
+    /// Binds the axis to the inputs for its negative and positive directions,
+    /// replacing its old bindings.
+    pub fn bind_axis(&mut self, axis: &str, negative: &[Binding], positive: &[Binding]) {
+        self.axes
+            .insert(axis.to_string(), (negative.to_vec(), positive.to_vec()));
+    }
+
+    /// Removes the action or the axis.
+    pub fn unbind(&mut self, name: &str) {
+        self.actions.remove(name);
+        self.axes.remove(name);
+    }
+
+    /// The inputs the action is bound to, empty for an unknown action.
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.actions
+            .get(action)
+            .map_or(&[], |bindings| &bindings[..])
+    }
+
+    /// Any of the action's inputs is down.
+    pub fn is_down(&self, action: &str) -> bool {
+        self.bindings(action).iter().any(Binding::is_down)
+    }
+
+    /// The action has just started this frame: one of its inputs got pressed
+    /// while none of the others were already down.
+    pub fn is_pressed(&self, action: &str) -> bool {
+        let bindings = self.bindings(action);
+        bindings.iter().any(Binding::is_pressed)
+            && bindings
+                .iter()
+                .all(|binding| !binding.is_down() || binding.is_pressed())
+    }
+
+    /// The action has ended this frame: one of its inputs got released
+    /// and none of them are down anymore.
+    pub fn is_released(&self, action: &str) -> bool {
+        let bindings = self.bindings(action);
+        bindings.iter().any(Binding::is_released) && !bindings.iter().any(Binding::is_down)
+    }
+
+    /// From -1.0 to 1.0, the strongest positive input minus the strongest negative one.
+    /// 0.0 for an unknown axis.
+    pub fn axis(&self, axis: &str) -> f32 {
+        let Some((negative, positive)) = self.axes.get(axis) else {
+            return 0.;
+        };
+        let strongest =
+            |bindings: &[Binding]| bindings.iter().map(Binding::value).fold(0., f32::max);
+
+        (strongest(positive) - strongest(negative)).clamp(-1., 1.)
+    }
+
+    /// The input pressed this frame, if any, for the "press a key to rebind" screens.
+    /// Gamepad axes count in the frame they are pushed past half of the way,
+    /// a stick held from before is not reported again.
+    pub fn poll_any_binding(&self) -> Option<Binding> {
+        if let Some(key) = get_context().keys_pressed.iter().next() {
+            return Some(Binding::Key(*key));
+        }
+        if let Some(button) = get_context().mouse_pressed.iter().next() {
+            return Some(Binding::MouseButton(*button));
+        }
+        GAMEPAD_BUTTONS
+            .iter()
+            .map(|button| Binding::GamepadButton(*button))
+            .chain(GAMEPAD_AXES.iter().flat_map(|axis| {
+                [true, false].map(|positive| Binding::GamepadAxis {
+                    axis: *axis,
+                    positive,
+                })
+            }))
+            .find(Binding::is_pressed)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Key(key) => write!(f, "Key({key:?})"),
+            Binding::MouseButton(button) => write!(f, "MouseButton({button:?})"),
+            Binding::GamepadButton(button) => write!(f, "GamepadButton({button:?})"),
+            Binding::GamepadAxis { axis, positive } => {
+                write!(
+                    f,
+                    "GamepadAxis({axis:?}{})",
+                    if *positive { '+' } else { '-' }
+                )
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Binding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Binding, ()> {
+        let kind = s.split_once('(').ok_or(())?.0;
+        let candidates: Vec<Binding> = match kind {
+            "Key" => KEY_CODES.iter().map(|&key| Binding::Key(key)).collect(),
+            "MouseButton" => [MouseButton::Left, MouseButton::Right, MouseButton::Middle]
+                .into_iter()
+                .map(Binding::MouseButton)
+                .collect(),
+            "GamepadButton" => GAMEPAD_BUTTONS
+                .iter()
+                .map(|&button| Binding::GamepadButton(button))
+                .collect(),
+            "GamepadAxis" => GAMEPAD_AXES
+                .iter()
+                .flat_map(|&axis| {
+                    [true, false].map(|positive| Binding::GamepadAxis { axis, positive })
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        // the written form is the Display one, so it always reads back
+        candidates
+            .into_iter()
+            .find(|binding| binding.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Binding {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Binding {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Binding, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse()
+            .map_err(|()| serde::de::Error::custom(format!("unknown binding: {text}")))
+    }
+}
+
+impl fmt::Display for InputMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |bindings: &[Binding]| {
+            bindings
+                .iter()
+                .map(|binding| binding.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        // sorted, to keep the saved files diffable
+        let mut actions: Vec<_> = self.actions.iter().collect();
+        actions.sort_by_key(|(name, _)| *name);
+        for (name, bindings) in actions {
+            writeln!(f, "{name}: {}", list(bindings))?;
+        }
+        let mut axes: Vec<_> = self.axes.iter().collect();
+        axes.sort_by_key(|(name, _)| *name);
+        for (name, (negative, positive)) in axes {
+            writeln!(f, "{name}: {} | {}", list(negative), list(positive))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for InputMap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<InputMap, Error> {
+        let mut map = InputMap::new();
+
+        for (n, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let error = Error::InputMapParseError { line: n + 1 };
+            let (name, bindings) = line.split_once(':').ok_or(error)?;
+            let parse = |list: &str| {
+                list.split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<Vec<Binding>, ()>>()
+                    .map_err(|_| Error::InputMapParseError { line: n + 1 })
+            };
+
+            match bindings.split_once('|') {
+                Some((negative, positive)) => {
+                    map.bind_axis(name.trim(), &parse(negative)?, &parse(positive)?)
+                }
+                None => map.bind(name.trim(), &parse(bindings)?),
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+const GAMEPAD_BUTTONS: [GamepadButton; 17] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::North,
+    GamepadButton::West,
+    GamepadButton::LeftBumper,
+    GamepadButton::LeftTrigger,
+    GamepadButton::RightBumper,
+    GamepadButton::RightTrigger,
+    GamepadButton::Select,
+    GamepadButton::Start,
+    GamepadButton::Mode,
+    GamepadButton::LeftThumb,
+    GamepadButton::RightThumb,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
+const GAMEPAD_AXES: [GamepadAxis; 6] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+    GamepadAxis::LeftTrigger,
+    GamepadAxis::RightTrigger,
+];
+
+#[rustfmt::skip]
+const KEY_CODES: [KeyCode; 120] = {
+    use KeyCode::*;
+    [
+        Space, Apostrophe, Comma, Minus, Period, Slash,
+        Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9,
+        Semicolon, Equal,
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+        LeftBracket, Backslash, RightBracket, GraveAccent, World1, World2,
+        Escape, Enter, Tab, Backspace, Insert, Delete, Right, Left, Down, Up,
+        PageUp, PageDown, Home, End, CapsLock, ScrollLock, NumLock, PrintScreen, Pause,
+        F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13,
+        F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24, F25,
+        Kp0, Kp1, Kp2, Kp3, Kp4, Kp5, Kp6, Kp7, Kp8, Kp9,
+        KpDecimal, KpDivide, KpMultiply, KpSubtract, KpAdd, KpEnter, KpEqual,
+        LeftShift, LeftControl, LeftAlt, LeftSuper,
+        RightShift, RightControl, RightAlt, RightSuper, Menu,
+    ]
+};
+
+#[test]
+fn input_map_text_roundtrip() {
+    let mut map = InputMap::new();
+    map.bind(
+        "jump",
+        &[
+            Binding::Key(KeyCode::Space),
+            Binding::MouseButton(MouseButton::Right),
+            Binding::GamepadButton(GamepadButton::South),
+        ],
+    );
+    map.bind_axis(
+        "move_x",
+        &[
+            Binding::Key(KeyCode::A),
+            Binding::GamepadAxis {
+                axis: GamepadAxis::LeftStickX,
+                positive: false,
+            },
+        ],
+        &[Binding::Key(KeyCode::KpEnter)],
+    );
+
+    let text = map.to_string();
+    assert_eq!(
+        text,
+        "jump: Key(Space) MouseButton(Right) GamepadButton(South)\n\
+         move_x: Key(A) GamepadAxis(LeftStickX-) | Key(KpEnter)\n"
+    );
+    assert_eq!(text.parse::<InputMap>().unwrap(), map);
+
+    assert!(matches!(
+        "jump: Key(Space)\nfire: Key(Nope)".parse::<InputMap>(),
+        Err(Error::InputMapParseError { line: 2 })
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn input_map_serde_roundtrip() {
+    let mut map = InputMap::new();
+    map.bind("jump", &[Binding::Key(KeyCode::Space)]);
+    map.bind_axis(
+        "move_y",
+        &[Binding::GamepadAxis {
+            axis: GamepadAxis::LeftStickY,
+            positive: false,
+        }],
+        &[Binding::MouseButton(MouseButton::Left)],
+    );
+
+    let json = serde_json::to_string(&map).unwrap();
+    assert!(json.contains("\"GamepadAxis(LeftStickY-)\""));
+    assert_eq!(serde_json::from_str::<InputMap>(&json).unwrap(), map);
+    assert!(serde_json::from_str::<Binding>("\"Key(Nope)\"").is_err());
+}
@@ -55,6 +55,7 @@ pub mod input;
 pub mod material;
 pub mod math;
 pub mod models;
+pub mod rand;
 pub mod shapes;
 pub mod text;
 pub mod texture;
@@ -131,11 +132,6 @@ pub use macroquad_macro::main;
 #[doc(hidden)]
 pub use macroquad_macro::test;
 
-/// Cross platform random generator.
-pub mod rand {
-    pub use quad_rand::*;
-}
-
 #[cfg(not(feature = "log-rs"))]
 /// Logging macros, available with miniquad "log-impl" feature.
 pub mod logging {
@@ -196,18 +192,26 @@ struct Context {
 
     screen_width: f32,
     screen_height: f32,
+    // draws, sizes and positions in physical pixels, see Conf::high_dpi
+    high_dpi: bool,
 
     simulate_mouse_with_touch: bool,
+    simulate_touch_with_mouse: bool,
 
     keys_down: HashSet<KeyCode>,
     keys_pressed: HashSet<KeyCode>,
     keys_released: HashSet<KeyCode>,
+    keys_repeated: HashSet<KeyCode>,
     mouse_down: HashSet<MouseButton>,
     mouse_pressed: HashSet<MouseButton>,
     mouse_released: HashSet<MouseButton>,
     touches: HashMap<u64, input::Touch>,
-    chars_pressed_queue: Vec<char>,
-    chars_pressed_ui_queue: Vec<char>,
+    gamepads: input::GamepadsContext,
+    gestures: input::GesturesContext,
+    chars_pressed_queue: std::collections::VecDeque<char>,
+    chars_pressed_ui_queue: std::collections::VecDeque<char>,
+    /// A root_ui widget had the keyboard focus at the beginning of the frame.
+    keyboard_captured_by_ui: bool,
     mouse_position: Vec2,
     last_mouse_position: Option<Vec2>,
     mouse_wheel: Vec2,
@@ -216,6 +220,13 @@ struct Context {
     quit_requested: bool,
 
     cursor_grabbed: bool,
+    /// Visibility requested with `input::show_mouse`.
+    mouse_shown: bool,
+    /// Drawn over the frame as the cursor, with its hotspot, see `input::set_mouse_cursor_image`.
+    cursor_image: Option<(texture::Texture2D, Vec2)>,
+    /// A [texture::get_screen_image_async] waits for the end of the frame.
+    screenshot_requested: bool,
+    screenshot: Option<texture::Image>,
 
     input_events: Vec<Vec<MiniquadInputEvent>>,
 
@@ -231,12 +242,27 @@ struct Context {
     start_time: f64,
     last_frame_time: f64,
     frame_time: f64,
+    /// Upper limit of the reported frame time, see `time::set_max_frame_time`.
+    max_frame_time: f64,
+    /// Multiplies the reported frame time, see `time::set_time_scale`.
+    time_scale: f64,
+    /// Durations of the last few frames, oldest first.
+    recent_frame_times: std::collections::VecDeque<f64>,
 
     #[cfg(one_screenshot)]
     counter: usize,
 
     camera_stack: Vec<camera::CameraState>,
+    scissor_stack: Vec<(i32, i32, i32, i32)>,
+    fullscreen: bool,
+    /// Physical size and position of the window before going fullscreen.
+    windowed_geometry: Option<((u32, u32), (u32, u32))>,
+    /// Clip rect of the active camera's viewport, in the same coordinates as the scissor.
+    viewport_scissor: Option<(i32, i32, i32, i32)>,
     texture_batcher: texture::Batcher,
+    /// See `Material::watch`.
+    #[cfg(feature = "shader-watch")]
+    watched_materials: Vec<material::WatchedMaterial>,
     unwind: bool,
     recovery_future: Option<Pin<Box<dyn Future<Output = ()>>>>,
 
@@ -328,6 +354,8 @@ impl Context {
         default_filter_mode: crate::FilterMode,
         draw_call_vertex_capacity: usize,
         draw_call_index_capacity: usize,
+        fullscreen: bool,
+        high_dpi: bool,
     ) -> Context {
         let mut ctx: Box<dyn miniquad::RenderingBackend> =
             miniquad::window::new_rendering_backend();
@@ -336,18 +364,24 @@ impl Context {
         Context {
             screen_width,
             screen_height,
+            high_dpi,
 
             simulate_mouse_with_touch: true,
+            simulate_touch_with_mouse: false,
 
             keys_down: HashSet::new(),
             keys_pressed: HashSet::new(),
             keys_released: HashSet::new(),
-            chars_pressed_queue: Vec::new(),
-            chars_pressed_ui_queue: Vec::new(),
+            keys_repeated: HashSet::new(),
+            chars_pressed_queue: std::collections::VecDeque::new(),
+            chars_pressed_ui_queue: std::collections::VecDeque::new(),
+            keyboard_captured_by_ui: false,
             mouse_down: HashSet::new(),
             mouse_pressed: HashSet::new(),
             mouse_released: HashSet::new(),
             touches: HashMap::new(),
+            gamepads: input::GamepadsContext::new(),
+            gestures: input::GesturesContext::default(),
             mouse_position: vec2(0., 0.),
             last_mouse_position: None,
             mouse_wheel: vec2(0., 0.),
@@ -356,6 +390,10 @@ impl Context {
             quit_requested: false,
 
             cursor_grabbed: false,
+            mouse_shown: true,
+            cursor_image: None,
+            screenshot_requested: false,
+            screenshot: None,
 
             input_events: Vec::new(),
 
@@ -368,8 +406,14 @@ impl Context {
 
             ui_context: UiContext::new(&mut *ctx, screen_width, screen_height),
             fonts_storage: text::FontsStorage::new(&mut *ctx),
-            texture_batcher: texture::Batcher::new(&mut *ctx),
+            texture_batcher: texture::Batcher::new(&mut *ctx, default_filter_mode),
             camera_stack: vec![],
+            scissor_stack: vec![],
+            fullscreen,
+            windowed_geometry: None,
+            viewport_scissor: None,
+            #[cfg(feature = "shader-watch")]
+            watched_materials: vec![],
 
             audio_context: audio::AudioContext::new(),
             coroutines_context: experimental::coroutines::CoroutinesContext::new(),
@@ -379,6 +423,11 @@ impl Context {
             start_time: miniquad::date::now(),
             last_frame_time: miniquad::date::now(),
             frame_time: 1. / 60.,
+            max_frame_time: f64::INFINITY,
+            time_scale: 1.,
+            recent_frame_times: std::collections::VecDeque::with_capacity(
+                time::FRAME_TIMES_HISTORY,
+            ),
 
             #[cfg(one_screenshot)]
             counter: 0,
@@ -410,30 +459,50 @@ impl Context {
         }
     }
 
-    /// Returns the files which have been dropped onto the window.
-    pub fn dropped_files(&mut self) -> Vec<DroppedFile> {
-        std::mem::take(&mut self.dropped_files)
+    /// Returns the files which have been dropped onto the window this frame.
+    pub fn dropped_files(&self) -> Vec<DroppedFile> {
+        self.dropped_files.clone()
     }
 
     fn begin_frame(&mut self) {
         telemetry::begin_gpu_query("GPU");
 
         self.ui_context.process_input();
+        self.gamepads.update();
+        self.gestures.update(miniquad::date::now());
 
         let color = Self::DEFAULT_BG_COLOR;
 
         get_quad_context().clear(Some((color.r, color.g, color.b, color.a)), None, None);
         self.gl.reset();
+        self.scissor_stack.clear();
+        // the camera, and so its viewport, outlives the frame
+        self.apply_scissor();
+    }
+
+    /// Clip to both the user's scissor and the camera viewport.
+    pub(crate) fn apply_scissor(&mut self) {
+        let clip = match (self.scissor_stack.last().copied(), self.viewport_scissor) {
+            (Some(scissor), Some(viewport)) => {
+                Some(crate::window::intersect_scissor(scissor, viewport))
+            }
+            (scissor, viewport) => scissor.or(viewport),
+        };
+        self.gl.scissor(clip);
     }
 
     fn end_frame(&mut self) {
         crate::experimental::scene::update();
+        crate::material::reload_watched_materials();
+        crate::audio::update();
 
         self.perform_render_passes();
 
         self.ui_context.draw(get_quad_context(), &mut self.gl);
+        self.draw_cursor_image();
         let screen_mat = self.pixel_perfect_projection_matrix();
         self.gl.draw(get_quad_context(), screen_mat);
+        crate::texture::take_requested_screenshot();
 
         get_quad_context().commit_frame();
 
@@ -451,6 +520,8 @@ impl Context {
         self.mouse_wheel = Vec2::new(0., 0.);
         self.keys_pressed.clear();
         self.keys_released.clear();
+        self.keys_repeated.clear();
+        self.chars_pressed_queue.clear();
         self.mouse_pressed.clear();
         self.mouse_released.clear();
         self.last_mouse_position = Some(crate::prelude::mouse_position_local());
@@ -473,14 +544,65 @@ impl Context {
         }
 
         self.dropped_files.clear();
+        self.gamepads.end_frame();
+        self.gestures.end_frame();
     }
 
-    pub(crate) fn pixel_perfect_projection_matrix(&self) -> glam::Mat4 {
-        let (width, height) = miniquad::window::screen_size();
+    fn draw_cursor_image(&mut self) {
+        let Some((texture, hotspot)) = self.cursor_image.as_ref() else {
+            return;
+        };
+        if !self.mouse_shown {
+            return;
+        }
 
-        let dpi = miniquad::window::dpi_scale();
+        // above everything, on the screen
+        self.gl.render_pass(None);
+        self.gl.viewport(None);
+        self.gl.scissor(None);
+        self.gl.pipeline(None);
+        self.gl.depth_test(false);
+
+        let position = self.mouse_position / self.dpi_scale() - *hotspot;
+        let (w, h) = (texture.width(), texture.height());
+        let color = WHITE;
+        #[rustfmt::skip]
+        let vertices = [
+            models::Vertex::new(position.x,     position.y,     0., 0., 0., color),
+            models::Vertex::new(position.x + w, position.y,     0., 1., 0., color),
+            models::Vertex::new(position.x + w, position.y + h, 0., 1., 1., color),
+            models::Vertex::new(position.x,     position.y + h, 0., 0., 1., color),
+        ];
+        self.gl.texture(Some(texture));
+        self.gl.draw_mode(quad_gl::DrawMode::Triangles);
+        self.gl
+            .blend_mode(get_quad_context(), quad_gl::BlendMode::Alpha);
+        self.gl.geometry(&vertices, &[0, 1, 2, 0, 2, 3]);
+        self.gl.texture(None);
+    }
 
-        glam::Mat4::orthographic_rh_gl(0., width / dpi, height / dpi, 0., -1., 1.)
+    /// Physical pixels per unit of the screen coordinates, see [window::dpi_scale].
+    pub(crate) fn dpi_scale(&self) -> f32 {
+        if self.high_dpi {
+            1.
+        } else {
+            miniquad::window::dpi_scale()
+        }
+    }
+
+    pub(crate) fn pixel_perfect_projection_matrix(&self) -> glam::Mat4 {
+        // the same size screen_width and screen_height report,
+        // so a rectangle of that size fills the window exactly
+        let dpi = self.dpi_scale();
+
+        glam::Mat4::orthographic_rh_gl(
+            0.,
+            self.screen_width / dpi,
+            self.screen_height / dpi,
+            0.,
+            -1.,
+            1.,
+        )
     }
 
     pub(crate) fn projection_matrix(&self) -> glam::Mat4 {
@@ -491,6 +613,45 @@ impl Context {
         }
     }
 
+    fn update_touch(&mut self, id: u64, phase: input::TouchPhase, x: f32, y: f32) {
+        // in the same units as mouse_position
+        let position = Vec2::new(x, y) / self.dpi_scale();
+        self.gestures
+            .touch_event(phase, id, position, miniquad::date::now());
+
+        // a touch that started and moved within one frame is still reported as started
+        let phase = match self.touches.get(&id) {
+            Some(touch)
+                if touch.phase == input::TouchPhase::Started
+                    && phase == input::TouchPhase::Moved =>
+            {
+                input::TouchPhase::Started
+            }
+            _ => phase,
+        };
+        self.touches.insert(
+            id,
+            input::Touch {
+                id,
+                phase,
+                position,
+            },
+        );
+    }
+
+    // the left mouse button as a touch, when `simulate_touch_with_mouse` is on
+    // and no finger is down, fingers raise mouse events of their own.
+    // A mouse touch started before a finger still ends, or it would stay forever.
+    fn mouse_touch(&mut self, phase: input::TouchPhase, x: f32, y: f32) {
+        let finger_down = self.touches.keys().any(|id| *id != input::MOUSE_TOUCH_ID);
+        let ends_own_touch =
+            phase == input::TouchPhase::Ended && self.touches.contains_key(&input::MOUSE_TOUCH_ID);
+
+        if self.simulate_touch_with_mouse && (!finger_down || ends_own_touch) {
+            self.update_touch(input::MOUSE_TOUCH_ID, phase, x, y);
+        }
+    }
+
     pub(crate) fn perform_render_passes(&mut self) {
         let matrix = self.projection_matrix();
 
@@ -614,8 +775,20 @@ struct Stage {
 impl EventHandler for Stage {
     fn resize_event(&mut self, width: f32, height: f32) {
         let _z = telemetry::ZoneGuard::new("Event::resize_event");
-        get_context().screen_width = width;
-        get_context().screen_height = height;
+        let context = get_context();
+        context.screen_width = width;
+        context.screen_height = height;
+
+        // leaving fullscreen through the OS or with Esc in the browser doesn't go
+        // through set_fullscreen, getting the windowed size back is the only sign of it
+        if context.fullscreen
+            && context
+                .windowed_geometry
+                .is_some_and(|((w, h), _)| (w as f32, h as f32) == (width, height))
+        {
+            context.fullscreen = false;
+            context.windowed_geometry = None;
+        }
 
         if miniquad::window::blocking_event_loop() {
             miniquad::window::schedule_update();
@@ -642,6 +815,9 @@ impl EventHandler for Stage {
     fn mouse_motion_event(&mut self, x: f32, y: f32) {
         let context = get_context();
 
+        if context.mouse_down.contains(&MouseButton::Left) {
+            context.mouse_touch(input::TouchPhase::Moved, x, y);
+        }
         if !context.cursor_grabbed {
             context.mouse_position = Vec2::new(x, y);
 
@@ -659,8 +835,9 @@ impl EventHandler for Stage {
     fn mouse_wheel_event(&mut self, x: f32, y: f32) {
         let context = get_context();
 
-        context.mouse_wheel.x = x;
-        context.mouse_wheel.y = y;
+        // high resolution devices may send a lot of small events each frame
+        context.mouse_wheel.x += x;
+        context.mouse_wheel.y += y;
 
         context
             .input_events
@@ -677,6 +854,9 @@ impl EventHandler for Stage {
 
         context.mouse_down.insert(btn);
         context.mouse_pressed.insert(btn);
+        if btn == MouseButton::Left {
+            context.mouse_touch(input::TouchPhase::Started, x, y);
+        }
 
         context
             .input_events
@@ -697,6 +877,9 @@ impl EventHandler for Stage {
 
         context.mouse_down.remove(&btn);
         context.mouse_released.insert(btn);
+        if btn == MouseButton::Left {
+            context.mouse_touch(input::TouchPhase::Ended, x, y);
+        }
 
         context
             .input_events
@@ -714,14 +897,7 @@ impl EventHandler for Stage {
     fn touch_event(&mut self, phase: TouchPhase, id: u64, x: f32, y: f32) {
         let context = get_context();
 
-        context.touches.insert(
-            id,
-            input::Touch {
-                id,
-                phase: phase.into(),
-                position: Vec2::new(x, y),
-            },
-        );
+        context.update_touch(id, phase.into(), x, y);
 
         if context.simulate_mouse_with_touch {
             if phase == TouchPhase::Started {
@@ -748,8 +924,11 @@ impl EventHandler for Stage {
     fn char_event(&mut self, character: char, modifiers: KeyMods, repeat: bool) {
         let context = get_context();
 
-        context.chars_pressed_queue.push(character);
-        context.chars_pressed_ui_queue.push(character);
+        // backspace, enter, tab and the like are read as keys, not as text
+        if !character.is_control() {
+            context.chars_pressed_queue.push_back(character);
+            context.chars_pressed_ui_queue.push_back(character);
+        }
 
         context.input_events.iter_mut().for_each(|arr| {
             arr.push(MiniquadInputEvent::Char {
@@ -763,6 +942,7 @@ impl EventHandler for Stage {
     fn key_down_event(&mut self, keycode: KeyCode, modifiers: KeyMods, repeat: bool) {
         let context = get_context();
         context.keys_down.insert(keycode);
+        context.keys_repeated.insert(keycode);
         if !repeat {
             context.keys_pressed.insert(keycode);
         }
@@ -815,8 +995,16 @@ impl EventHandler for Stage {
     fn files_dropped_event(&mut self) {
         let context = get_context();
         for i in 0..miniquad::window::dropped_file_count() {
+            let path = miniquad::window::dropped_file_path(i);
+            let name = path
+                .as_ref()
+                .and_then(|path| path.file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+
             context.dropped_files.push(DroppedFile {
-                path: miniquad::window::dropped_file_path(i),
+                name,
+                path,
                 bytes: miniquad::window::dropped_file_bytes(i),
             });
         }
@@ -868,6 +1056,13 @@ impl EventHandler for Stage {
             }
             get_context().frame_time = date::now() - get_context().last_frame_time;
             get_context().last_frame_time = date::now();
+            {
+                let context = get_context();
+                if context.recent_frame_times.len() == time::FRAME_TIMES_HISTORY {
+                    context.recent_frame_times.pop_front();
+                }
+                context.recent_frame_times.push_back(context.frame_time);
+            }
 
             #[cfg(any(target_arch = "wasm32", target_os = "linux"))]
             {
@@ -913,6 +1108,7 @@ impl EventHandler for Stage {
         for (_, touch) in context.touches.iter_mut() {
             touch.phase = input::TouchPhase::Ended;
         }
+        context.gestures.cancel();
 
         context
             .input_events
@@ -962,6 +1158,16 @@ pub mod conf {
         ///   buffer size might be reduced to save some memory
         pub draw_call_vertex_capacity: usize,
         pub draw_call_index_capacity: usize,
+        /// Use physical pixels for the screen coordinates instead of logical ones.
+        ///
+        /// By default, on a display scaled by 2, [screen_width](crate::window::screen_width)
+        /// is half the framebuffer width and a 10 pixels wide rectangle covers 20
+        /// physical pixels. With `high_dpi` the coordinates are framebuffer pixels
+        /// and [dpi_scale](crate::window::dpi_scale) is 1. Either way drawing,
+        /// the screen size, the mouse and the touches use the same units.
+        ///
+        /// Turns on `miniquad_conf.high_dpi` for a full-resolution framebuffer.
+        pub high_dpi: bool,
     }
 
     impl Default for Conf {
@@ -972,6 +1178,7 @@ pub mod conf {
                 default_filter_mode: crate::FilterMode::Linear,
                 draw_call_vertex_capacity: 10000,
                 draw_call_index_capacity: 5000,
+                high_dpi: false,
             }
         }
     }
@@ -985,6 +1192,7 @@ impl From<miniquad::conf::Conf> for conf::Conf {
             default_filter_mode: crate::FilterMode::Linear,
             draw_call_vertex_capacity: 10000,
             draw_call_index_capacity: 5000,
+            high_dpi: false,
         }
     }
 }
@@ -1015,7 +1223,13 @@ impl Window {
             default_filter_mode,
             draw_call_vertex_capacity,
             draw_call_index_capacity,
+            high_dpi,
         } = config.into();
+        let miniquad_conf = miniquad::conf::Conf {
+            high_dpi: miniquad_conf.high_dpi || high_dpi,
+            ..miniquad_conf
+        };
+        let fullscreen = miniquad_conf.fullscreen;
         miniquad::start(miniquad_conf, move || {
             thread_assert::set_thread_id();
             let context = Context::new(
@@ -1023,6 +1237,8 @@ impl Window {
                 default_filter_mode,
                 draw_call_vertex_capacity,
                 draw_call_index_capacity,
+                fullscreen,
+                high_dpi,
             );
             CONTEXT.with(|ctx_cell| {
                 *ctx_cell.borrow_mut() = Some(context);
@@ -1042,8 +1258,15 @@ impl Window {
     }
 }
 
-/// Information about a dropped file.
+/// Information about a dropped file, see [get_dropped_files](crate::input::get_dropped_files).
+#[derive(Clone)]
 pub struct DroppedFile {
+    /// The file name, without the directories.
+    pub name: String,
+    /// Where the file is on desktop. On the web the page can't see the paths,
+    /// only the name: it is then the name only.
     pub path: Option<std::path::PathBuf>,
+    /// The content of the file, always there on the web, where it is the only
+    /// way to get to it. Platforms that give a path may leave it out.
     pub bytes: Option<Vec<u8>>,
 }
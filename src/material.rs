@@ -1,8 +1,21 @@
 //! Custom materials - shaders, uniforms.
 
-use crate::{get_context, quad_gl::GlPipeline, texture::Texture2D, tobytes::ToBytes, Error};
+use crate::{
+    camera::{pop_camera_state, push_camera_state, set_camera, Camera2D},
+    color::WHITE,
+    file::load_string,
+    get_context, get_quad_context,
+    math::{vec2, Mat4},
+    quad_gl::GlPipeline,
+    texture::{draw_texture_ex, render_target, DrawTextureParams, RenderTarget, Texture2D},
+    tobytes::ToBytes,
+    window::{screen_height, screen_width},
+    Error,
+};
 use miniquad::{PipelineParams, UniformDesc};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+
+pub use crate::quad_gl::BlendMode;
 
 #[derive(PartialEq)]
 struct GlPipelineGuarded(GlPipeline);
@@ -17,10 +30,114 @@ impl Drop for GlPipelineGuarded {
     }
 }
 
+/// Where a material loaded with [load_material_from_files] reads its shaders from again.
+struct MaterialFiles {
+    vertex_path: String,
+    fragment_path: String,
+    pipeline_params: PipelineParams,
+    uniforms: Vec<UniformDesc>,
+    textures: Vec<String>,
+    /// The error of the last reload, None once a reload succeeds.
+    last_error: Mutex<Option<String>>,
+}
+
+impl MaterialFiles {
+    async fn load(&self) -> Result<(String, String), Error> {
+        Ok((
+            load_string(&self.vertex_path).await?,
+            load_string(&self.fragment_path).await?,
+        ))
+    }
+
+    /// Recompile the pipeline with the given sources, keeping the last working one on errors.
+    fn recompile(&self, pipeline: GlPipeline, vertex: &str, fragment: &str) -> Result<(), Error> {
+        let context = &mut get_context();
+        let result = context
+            .gl
+            .reload_pipeline(
+                &mut *context.quad_context,
+                pipeline,
+                crate::ShaderSource::Glsl { vertex, fragment },
+                self.pipeline_params,
+                self.uniforms.clone(),
+                self.textures.clone(),
+            )
+            .map_err(Error::from);
+
+        *self.last_error.lock().unwrap() = result.as_ref().err().map(ToString::to_string);
+        result
+    }
+
+    /// When either file was last changed.
+    #[cfg(all(feature = "shader-watch", not(target_arch = "wasm32")))]
+    fn modified(&self) -> Option<std::time::SystemTime> {
+        let modified = |path: &str| std::fs::metadata(on_disk(path)).ok()?.modified().ok();
+        Some(modified(&self.vertex_path)?.max(modified(&self.fragment_path)?))
+    }
+}
+
+/// Where [load_string] reads `path` from.
+#[cfg(all(feature = "shader-watch", not(target_arch = "wasm32")))]
+fn on_disk(path: &str) -> String {
+    match &get_context().pc_assets_folder {
+        Some(folder) if cfg!(not(target_os = "android")) => format!("{folder}/{path}"),
+        _ => path.to_owned(),
+    }
+}
+
+/// A material reloaded by [reload_watched_materials], see [Material::watch].
+#[cfg(feature = "shader-watch")]
+pub(crate) struct WatchedMaterial {
+    /// Weak for the material to be deleted when the game drops it.
+    pipeline: std::sync::Weak<GlPipelineGuarded>,
+    files: Arc<MaterialFiles>,
+    #[cfg(not(target_arch = "wasm32"))]
+    modified: Option<std::time::SystemTime>,
+}
+
+/// Reload the watched materials whose files changed since the last frame.
+#[cfg(all(feature = "shader-watch", not(target_arch = "wasm32")))]
+pub(crate) fn reload_watched_materials() {
+    let mut watched = std::mem::take(&mut get_context().watched_materials);
+    watched.retain(|material| material.pipeline.strong_count() > 0);
+
+    for material in &mut watched {
+        let modified = material.files.modified();
+        if modified == material.modified {
+            continue;
+        }
+        let (Some(pipeline), Ok(vertex), Ok(fragment)) = (
+            material.pipeline.upgrade(),
+            std::fs::read_to_string(on_disk(&material.files.vertex_path)),
+            std::fs::read_to_string(on_disk(&material.files.fragment_path)),
+        ) else {
+            // editors may replace the file in several steps, tried again next frame
+            continue;
+        };
+        material.modified = modified;
+
+        if let Err(err) = material.files.recompile(pipeline.0, &vertex, &fragment) {
+            crate::logging::warn!("shader reload: {}", err);
+        }
+    }
+
+    get_context().watched_materials.extend(watched);
+}
+
+#[cfg(not(all(feature = "shader-watch", not(target_arch = "wasm32"))))]
+pub(crate) fn reload_watched_materials() {}
+
 /// Material instance loaded on GPU.
-#[derive(Clone, PartialEq)]
+#[derive(Clone)]
 pub struct Material {
     pipeline: Arc<GlPipelineGuarded>,
+    files: Option<Arc<MaterialFiles>>,
+}
+
+impl PartialEq for Material {
+    fn eq(&self, other: &Material) -> bool {
+        self.pipeline == other.pipeline
+    }
 }
 
 impl std::fmt::Debug for Material {
@@ -37,6 +154,13 @@ impl Material {
         get_context().gl.set_uniform(self.pipeline.0, name, uniform);
     }
 
+    /// Set all the elements of a uniform array at once, like `uniform vec4 lights[16]`,
+    /// declared in "uniforms" with `UniformDesc::new("lights", UniformType::Float4).array(16)`.
+    /// `uniform` should have elements of the declared type. With less elements than the array,
+    /// the rest of the array is set to zeros; with more, or of another type,
+    /// a warning is logged and the uniform is left unchanged.
+    ///
+    /// `mat4` uniforms, arrays or not, take [Mat4](crate::math::Mat4) values.
     pub fn set_uniform_array<T: ToBytes>(&self, name: &str, uniform: &[T]) {
         get_context()
             .gl
@@ -46,6 +170,60 @@ impl Material {
     pub fn set_texture(&self, name: &str, texture: Texture2D) {
         get_context().gl.set_texture(self.pipeline.0, name, texture);
     }
+
+    /// Read the shader files of a material from [load_material_from_files] again and recompile them.
+    ///
+    /// The material stays the same, so all its clones and the uniforms set so far keep working.
+    /// If the new shaders do not compile, the material keeps the last program that did
+    /// and the compiler's message is returned in [Error::ShaderError].
+    /// Materials from [load_material] have no files to reload from and return an error.
+    pub async fn reload(&self) -> Result<(), Error> {
+        let files = self.files.as_ref().ok_or(Error::UnknownError(
+            "Material was not loaded from files, nothing to reload",
+        ))?;
+        let (vertex, fragment) = files.load().await?;
+
+        files.recompile(self.pipeline.0, &vertex, &fragment)
+    }
+
+    /// Reload the material on its own whenever one of its files from [load_material_from_files]
+    /// is saved, checking the modification times at the end of every frame.
+    ///
+    /// A reload that fails keeps the last working shaders and logs a warning,
+    /// [Material::last_error] has the message to show it on the screen.
+    /// The uniforms and textures set so far are kept across reloads.
+    ///
+    /// This is for the development, on native and with the "shader-watch" feature:
+    /// on the web the files can't be watched and the material stays as it was loaded.
+    /// Materials from [load_material] have no files to watch and return an error.
+    #[cfg(feature = "shader-watch")]
+    pub fn watch(&self) -> Result<(), Error> {
+        let files = self.files.as_ref().ok_or(Error::UnknownError(
+            "Material was not loaded from files, nothing to watch",
+        ))?;
+
+        let watched = &mut get_context().watched_materials;
+        if watched
+            .iter()
+            .any(|material| Arc::ptr_eq(&material.files, files))
+        {
+            return Ok(());
+        }
+        watched.push(WatchedMaterial {
+            pipeline: Arc::downgrade(&self.pipeline),
+            files: files.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            modified: files.modified(),
+        });
+
+        Ok(())
+    }
+
+    /// The compilation error of the last [Material::reload], or of the last reload made by
+    /// [Material::watch]. None when the last one succeeded.
+    pub fn last_error(&self) -> Option<String> {
+        self.files.as_ref()?.last_error.lock().unwrap().clone()
+    }
 }
 
 /// Params used for material loading.
@@ -123,25 +301,64 @@ pub struct MaterialParams {
 /// ) {...}
 /// ```
 ///
+/// # Errors
+/// When a shader does not compile, [Error::ShaderError] tells which one and displays
+/// the compiler's log, with the lines and columns of the errors.
 pub fn load_material(
     shader: crate::ShaderSource,
     params: MaterialParams,
 ) -> Result<Material, Error> {
     let context = &mut get_context();
 
-    let pipeline = context
-        .gl
-        .make_pipeline(
-            &mut *context.quad_context,
-            shader,
-            params.pipeline_params,
-            params.uniforms,
-            params.textures,
-        )
-        .map_err(|_| Error::UnknownError("Failed to create pipeline"))?;
+    let pipeline = context.gl.make_pipeline(
+        &mut *context.quad_context,
+        shader,
+        params.pipeline_params,
+        params.uniforms,
+        params.textures,
+    )?;
 
     Ok(Material {
         pipeline: Arc::new(GlPipelineGuarded(pipeline)),
+        files: None,
+    })
+}
+
+/// Creates custom material from GLSL shader files, to be recompiled with [Material::reload]
+/// or [Material::watch] while the game is running.
+///
+/// The shaders are the same as for [load_material], see its docs for the default variables.
+/// Metal shaders can only be loaded with [load_material] for now.
+pub async fn load_material_from_files(
+    vertex_path: &str,
+    fragment_path: &str,
+    params: MaterialParams,
+) -> Result<Material, Error> {
+    let files = MaterialFiles {
+        vertex_path: vertex_path.to_string(),
+        fragment_path: fragment_path.to_string(),
+        pipeline_params: params.pipeline_params,
+        uniforms: params.uniforms,
+        textures: params.textures,
+        last_error: Mutex::new(None),
+    };
+    let (vertex, fragment) = files.load().await?;
+
+    let context = &mut get_context();
+    let pipeline = context.gl.make_pipeline(
+        &mut *context.quad_context,
+        crate::ShaderSource::Glsl {
+            vertex: &vertex,
+            fragment: &fragment,
+        },
+        files.pipeline_params,
+        files.uniforms.clone(),
+        files.textures.clone(),
+    )?;
+
+    Ok(Material {
+        pipeline: Arc::new(GlPipelineGuarded(pipeline)),
+        files: Some(Arc::new(files)),
     })
 }
 
@@ -155,6 +372,171 @@ pub fn gl_use_default_material() {
     get_context().gl.pipeline(None);
 }
 
+/// Blend all the following drawing with the default material with `mode`, until changed again.
+///
+/// A material brings its own blending with [PipelineParams::color_blend] and is not affected.
+/// The blend mode is back to [BlendMode::Alpha] at the beginning of every frame.
+pub fn gl_set_blend_mode(mode: BlendMode) {
+    get_context().gl.blend_mode(get_quad_context(), mode);
+}
+
+/// Like [gl_set_blend_mode], and [gl_pop_blend_mode] goes back to the previous mode.
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// gl_push_blend_mode(BlendMode::Additive);
+/// draw_circle(100., 100., 30., Color::new(1.0, 0.5, 0.1, 0.5));
+/// gl_pop_blend_mode();
+/// ```
+pub fn gl_push_blend_mode(mode: BlendMode) {
+    get_context().gl.push_blend_mode(get_quad_context(), mode);
+}
+
+/// Restore the blend mode active before the last [gl_push_blend_mode].
+///
+/// Logs a warning and does nothing when there is nothing to pop.
+pub fn gl_pop_blend_mode() {
+    if !get_context().gl.pop_blend_mode() {
+        crate::logging::warn!("gl_pop_blend_mode: no blend mode was pushed");
+    }
+}
+
+/// Draws the screen again through a material, for effects like a CRT screen or a blur.
+///
+/// The drawing between [PostProcessing::begin] and [PostProcessing::end_and_draw] goes to
+/// a render target the size of the window, which is then drawn over the whole output with
+/// the material. The material's `Texture` is the drawing, `_Time` is there as for any material,
+/// and a `Resolution` uniform, when the material declares it as a `Float2`,
+/// gets the size of the drawing in pixels.
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # async fn run() {
+/// # let crt = load_material(ShaderSource::Glsl { vertex: "", fragment: "" }, Default::default()).unwrap();
+/// let mut crt = PostProcessing::new(crt);
+/// loop {
+///     crt.begin();
+///     clear_background(BLACK);
+///     draw_circle(100., 100., 50., YELLOW);
+///     crt.end_and_draw();
+///
+///     next_frame().await
+/// }
+/// # }
+/// ```
+///
+/// Passes nest: a pass begun while another one is active draws into the outer one's target
+/// on [PostProcessing::end_and_draw], so the effects are applied from the inner to the outer one.
+pub struct PostProcessing {
+    material: Material,
+    target: Option<RenderTarget>,
+}
+
+impl PostProcessing {
+    pub fn new(material: Material) -> PostProcessing {
+        PostProcessing {
+            material,
+            target: None,
+        }
+    }
+
+    /// Draw into the render target from now on, with the coordinates of the default camera.
+    ///
+    /// The target follows the size of the window. Another camera can be used in between,
+    /// with [PostProcessing::render_target] as its `render_target`.
+    pub fn begin(&mut self) {
+        let (width, height) = miniquad::window::screen_size();
+        let (width, height) = (width as u32, height as u32);
+
+        let resized = self.target.as_ref().is_none_or(|target| {
+            (
+                target.texture.width() as u32,
+                target.texture.height() as u32,
+            ) != (width, height)
+        });
+        if resized {
+            self.target = Some(render_target(width.max(1), height.max(1)));
+        }
+
+        push_camera_state();
+        set_camera(&Camera2D {
+            target: vec2(screen_width() / 2., screen_height() / 2.),
+            zoom: vec2(2. / screen_width(), 2. / screen_height()),
+            render_target: self.target.clone(),
+            ..Default::default()
+        });
+    }
+
+    /// Go back to the camera active before [PostProcessing::begin], and draw the render target
+    /// over all of it with the material.
+    pub fn end_and_draw(&mut self) {
+        let Some(target) = &self.target else {
+            crate::logging::warn!("PostProcessing::end_and_draw called without begin");
+            return;
+        };
+        let texture = &target.texture;
+        let pipeline = self.material.pipeline.0;
+
+        pop_camera_state();
+        // whatever the camera, the texture covers the output: 0..1 is all of it
+        push_camera_state();
+        let previous_pipeline = {
+            let context = get_context();
+            context.perform_render_passes();
+            let (bottom, top) = match context.gl.get_active_render_pass() {
+                // render targets are upside down
+                Some(_) => (0., 1.),
+                None => (1., 0.),
+            };
+            context.camera_matrix = Some(Mat4::orthographic_rh_gl(0., 1., bottom, top, -1., 1.));
+            context.gl.viewport(None);
+            context.gl.depth_test(false);
+            context.viewport_scissor = None;
+            context.apply_scissor();
+
+            if context.gl.has_uniform(pipeline, "Resolution") {
+                let resolution = vec2(texture.width(), texture.height());
+                context.gl.set_uniform(pipeline, "Resolution", resolution);
+            }
+
+            let previous_pipeline = context.gl.get_active_pipeline();
+            context.gl.pipeline(Some(pipeline));
+            previous_pipeline
+        };
+
+        // metal reads the textures from the top row, opengl from the bottom one
+        let flip_y = get_quad_context().info().backend == miniquad::Backend::Metal;
+        draw_texture_ex(
+            texture,
+            0.,
+            0.,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(vec2(1., 1.)),
+                flip_y,
+                ..Default::default()
+            },
+        );
+        get_context().gl.pipeline(previous_pipeline);
+
+        pop_camera_state();
+    }
+
+    /// The target the drawing goes to, None until the first [PostProcessing::begin].
+    pub fn render_target(&self) -> Option<&RenderTarget> {
+        self.target.as_ref()
+    }
+
+    /// The drawing of the last pass, before the material.
+    pub fn texture(&self) -> Option<&Texture2D> {
+        self.target.as_ref().map(|target| &target.texture)
+    }
+
+    pub fn material(&self) -> &Material {
+        &self.material
+    }
+}
+
 #[doc(hidden)]
 pub mod shaders {
     type IncludeFilename = String;
@@ -1,9 +1,14 @@
 //! 3D shapes and models, loading 3d models from files, drawing 3D primitives.
 
-use crate::{color::Color, get_context};
+use crate::{color::Color, get_context, get_quad_context, Error};
 
 use crate::{quad_gl::DrawMode, texture::Texture2D};
 use glam::{vec2, vec3, vec4, Quat, Vec2, Vec3, Vec4};
+use miniquad::{BufferId, BufferSource, BufferType, BufferUsage};
+
+mod obj;
+
+pub use obj::{draw_model, load_model, Model, ModelMaterial};
 
 #[repr(C)]
 #[derive(Clone, Debug, Copy)]
@@ -38,18 +43,213 @@ impl Vertex {
     }
 }
 
+/// Triangles to draw with [draw_mesh]: every three `indices` make a triangle
+/// of the `vertices` they point to.
+///
+/// The indices are `u16`, so a mesh holds up to 65536 vertices: bigger models,
+/// like a large terrain, are made of several meshes.
 pub struct Mesh {
     pub vertices: Vec<Vertex>,
     pub indices: Vec<u16>,
     pub texture: Option<Texture2D>,
 }
 
+impl Mesh {
+    /// Set the `normal` of every vertex to the average of the normals of the triangles
+    /// around it, weighted by their area, for smooth lighting in a material.
+    ///
+    /// The triangles are taken counter-clockwise. `normal.w` is set to 0.
+    pub fn compute_normals(&mut self) {
+        let mut normals = vec![Vec3::ZERO; self.vertices.len()];
+        for triangle in self.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| self.vertices[triangle[i] as usize].position);
+            // twice the area long
+            let normal = (b - a).cross(c - a);
+            for &index in triangle {
+                normals[index as usize] += normal;
+            }
+        }
+
+        for (vertex, normal) in self.vertices.iter_mut().zip(normals) {
+            vertex.normal = normal.normalize_or_zero().extend(0.);
+        }
+    }
+
+    /// Copy the mesh to GPU buffers, to draw it with [draw_gpu_mesh] without sending
+    /// the vertices again every frame.
+    ///
+    /// Fails with [Error::MeshTooBig] past 65536 vertices, the most `u16` indices can point to.
+    pub fn upload(&self) -> Result<GpuMesh, Error> {
+        let (vertex_buffer, index_buffer) = self.new_buffers()?;
+        Ok(GpuMesh {
+            vertex_buffer,
+            index_buffer,
+            indices_count: self.indices.len(),
+            texture: self.texture.clone(),
+            dirty: false,
+        })
+    }
+
+    fn new_buffers(&self) -> Result<(BufferId, BufferId), Error> {
+        if self.vertices.len() > u16::MAX as usize + 1 {
+            return Err(Error::MeshTooBig {
+                vertices: self.vertices.len(),
+            });
+        }
+
+        let ctx = get_quad_context();
+        let vertex_buffer = ctx.new_buffer(
+            BufferType::VertexBuffer,
+            BufferUsage::Immutable,
+            BufferSource::slice(&self.vertices),
+        );
+        let index_buffer = ctx.new_buffer(
+            BufferType::IndexBuffer,
+            BufferUsage::Immutable,
+            BufferSource::slice(&self.indices),
+        );
+        Ok((vertex_buffer, index_buffer))
+    }
+}
+
+/// A [Mesh] kept in GPU buffers, made with [Mesh::upload].
+///
+/// After changing the mesh, [GpuMesh::set_dirty] makes the next [GpuMesh::update]
+/// upload it again, the frames in between are drawn from the buffers as they are.
+pub struct GpuMesh {
+    vertex_buffer: BufferId,
+    index_buffer: BufferId,
+    indices_count: usize,
+    pub texture: Option<Texture2D>,
+    dirty: bool,
+}
+
+impl GpuMesh {
+    pub fn set_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Upload `mesh` in place of the old content when [GpuMesh::set_dirty] was called since
+    /// the last upload, does nothing otherwise. The texture is taken from `mesh` too.
+    pub fn update(&mut self, mesh: &Mesh) -> Result<(), Error> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let (vertex_buffer, index_buffer) = mesh.new_buffers()?;
+        // the old buffers may still be in this frame's draw calls
+        get_context()
+            .gl
+            .retire_buffers(&[self.vertex_buffer, self.index_buffer]);
+
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.indices_count = mesh.indices.len();
+        self.texture = mesh.texture.clone();
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+impl Drop for GpuMesh {
+    fn drop(&mut self) {
+        // no context during the thread-local cleanup, the buffers go with it
+        if let Some(context) = crate::try_get_context() {
+            context
+                .gl
+                .retire_buffers(&[self.vertex_buffer, self.index_buffer]);
+        }
+    }
+}
+
+/// Draw a mesh uploaded with [Mesh::upload] with the current camera and material,
+/// in a draw call of its own.
+pub fn draw_gpu_mesh(mesh: &GpuMesh) {
+    let context = get_context();
+
+    context.gl.texture(mesh.texture.as_ref());
+    context.gl.draw_mode(DrawMode::Triangles);
+    context
+        .gl
+        .draw_buffers(mesh.vertex_buffer, mesh.index_buffer, mesh.indices_count);
+}
+
+/// Draw the triangles of the mesh with the current camera and material,
+/// batched with the other drawing like the shapes are.
+///
+/// Like everything macroquad draws, the vertices are copied to the GPU every frame,
+/// nothing is kept between the frames: the mesh can be changed freely in between.
+/// For a big mesh that rarely changes, [Mesh::upload] it once and use [draw_gpu_mesh].
+///
+/// Meshes bigger than a draw call, see
+/// [gl_set_drawcall_buffer_capacity](crate::window::gl_set_drawcall_buffer_capacity),
+/// are split in several draw calls.
 pub fn draw_mesh(mesh: &Mesh) {
     let context = get_context();
 
     context.gl.texture(mesh.texture.as_ref());
     context.gl.draw_mode(DrawMode::Triangles);
-    context.gl.geometry(&mesh.vertices[..], &mesh.indices[..]);
+
+    let (max_vertices, max_indices) = context.gl.max_geometry_size();
+    split_mesh(
+        &mesh.vertices,
+        &mesh.indices,
+        max_vertices,
+        max_indices,
+        |vertices, indices| context.gl.geometry(vertices, indices),
+    );
+}
+
+/// Calls `f` with parts of the mesh of at most `max_vertices` and `max_indices`,
+/// the whole mesh when it fits.
+fn split_mesh(
+    vertices: &[Vertex],
+    indices: &[u16],
+    max_vertices: usize,
+    max_indices: usize,
+    mut f: impl FnMut(&[Vertex], &[u16]),
+) {
+    if vertices.len() <= max_vertices && indices.len() <= max_indices {
+        f(vertices, indices);
+        return;
+    }
+
+    // triangle by triangle, with the vertices they use copied to the part
+    let mut part_vertices = vec![];
+    let mut part_indices = vec![];
+    let mut remap = vec![u16::MAX; vertices.len()];
+    let max_indices = max_indices - max_indices % 3;
+
+    for triangle in indices.chunks_exact(3) {
+        let new_vertices = triangle
+            .iter()
+            .filter(|&&index| remap[index as usize] == u16::MAX)
+            .count();
+        if part_vertices.len() + new_vertices > max_vertices || part_indices.len() + 3 > max_indices
+        {
+            f(&part_vertices, &part_indices);
+            part_vertices.clear();
+            part_indices.clear();
+            remap.fill(u16::MAX);
+        }
+
+        for &index in triangle {
+            let index = index as usize;
+            if remap[index] == u16::MAX {
+                remap[index] = part_vertices.len() as u16;
+                part_vertices.push(vertices[index]);
+            }
+            part_indices.push(remap[index]);
+        }
+    }
+
+    if !part_indices.is_empty() {
+        f(&part_vertices, &part_indices);
+    }
 }
 
 fn draw_quad(vertices: [Vertex; 4]) {
@@ -728,3 +928,79 @@ pub fn draw_cylinder_ex(
         );
     }
 }
+
+#[test]
+fn split_big_mesh() {
+    // a strip of 10 quads sharing their sides
+    let vertices: Vec<_> = (0..22)
+        .map(|i| Vertex::new((i / 2) as f32, (i % 2) as f32, 0., 0., 0., Color::default()))
+        .collect();
+    let indices: Vec<u16> = (0..10)
+        .flat_map(|quad| [0, 1, 2, 1, 3, 2].map(|i| quad * 2 + i))
+        .collect();
+
+    let mut parts = vec![];
+    split_mesh(&vertices, &indices, 100, 100, |v, i| {
+        parts.push((v.len(), i.len()))
+    });
+    assert_eq!(parts, [(22, 60)]);
+
+    let mut triangles = vec![];
+    split_mesh(&vertices, &indices, 8, 13, |v, i| {
+        assert!(v.len() <= 8 && i.len() <= 12);
+        triangles.extend(i.chunks(3).map(|t| {
+            t.iter()
+                .map(|&i| v[i as usize].position)
+                .collect::<Vec<_>>()
+        }));
+    });
+    // the same triangles, in the same order
+    let expected: Vec<_> = indices
+        .chunks(3)
+        .map(|t| {
+            t.iter()
+                .map(|&i| vertices[i as usize].position)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(triangles, expected);
+}
+
+#[test]
+fn smooth_normals() {
+    // two triangles of a square folded along its diagonal
+    let mut mesh = Mesh {
+        vertices: [
+            vec3(0., 0., 0.),
+            vec3(1., 0., 0.),
+            vec3(0., 0., -1.),
+            vec3(1., 1., -1.),
+        ]
+        .map(|position| Vertex::new2(position, Vec2::ZERO, Color::default()))
+        .to_vec(),
+        indices: vec![0, 1, 2, 1, 3, 2],
+        texture: None,
+    };
+    mesh.compute_normals();
+
+    assert_eq!(mesh.vertices[0].normal, vec4(0., 1., 0., 0.));
+    // the shared vertices are in between the two sides
+    let shared = mesh.vertices[1].normal.truncate();
+    assert!((shared.length() - 1.).abs() < 1e-5);
+    assert!(shared.y > 0. && shared.x < 0.);
+    assert_eq!(mesh.vertices[1].normal, mesh.vertices[2].normal);
+}
+
+#[test]
+fn upload_too_big_mesh() {
+    let mesh = Mesh {
+        vertices: vec![Vertex::new(0., 0., 0., 0., 0., Color::default()); 65537],
+        indices: vec![0, 1, 65535],
+        texture: None,
+    };
+    // rejected before touching the GPU
+    assert!(matches!(
+        mesh.upload(),
+        Err(Error::MeshTooBig { vertices: 65537 })
+    ));
+}
@@ -0,0 +1,457 @@
+//! Loading Wavefront OBJ files, with their MTL materials, into a [Model].
+
+use crate::{
+    color::{colors::WHITE, Color},
+    file::load_string,
+    get_context,
+    math::{vec2, Mat4, Vec2, Vec3},
+    models::{draw_mesh, Mesh, Vertex},
+    texture::{load_texture, Texture2D},
+    Error,
+};
+
+use std::collections::HashMap;
+
+/// The material of a part of a [Model], from the `.mtl` files of the OBJ.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelMaterial {
+    /// The name given by `newmtl`, empty for the faces without a material.
+    pub name: String,
+    /// `Kd`, with `d` as the alpha. Also the color of the vertices of the mesh.
+    pub diffuse: Color,
+    /// `map_Kd`, also the texture of the mesh.
+    pub diffuse_texture: Option<Texture2D>,
+}
+
+impl Default for ModelMaterial {
+    fn default() -> ModelMaterial {
+        ModelMaterial {
+            name: String::new(),
+            diffuse: WHITE,
+            diffuse_texture: None,
+        }
+    }
+}
+
+/// Meshes loaded by [load_model], drawn with [draw_model].
+pub struct Model {
+    /// A mesh for each material the faces use, in the order the file first uses them.
+    /// A material with more than 65536 vertices is split in several meshes.
+    pub meshes: Vec<(Mesh, ModelMaterial)>,
+}
+
+/// Load a Wavefront OBJ file and the materials and diffuse textures it refers to.
+///
+/// The `mtllib` files are looked up next to the OBJ file and the textures next
+/// to the `.mtl` file, with the same [load_file](crate::file::load_file) as everything else.
+///
+/// Faces with more than three vertices are split into triangles, negative indices
+/// count from the last vertex. When some of the faces of a mesh have no normals,
+/// the normals of the whole mesh are computed with [Mesh::compute_normals].
+/// The texture coordinates are flipped to the top-left origin macroquad uses.
+///
+/// Lines, points, groups and smoothing groups are skipped, other unknown
+/// lines too with a warning. Malformed lines are a [Error::ModelParseError]
+/// with the line number.
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # async fn run() {
+/// let model = load_model("examples/cube.obj").await.unwrap();
+/// draw_model(&model, Mat4::from_rotation_y(get_time() as f32));
+/// # }
+/// ```
+pub async fn load_model(path: &str) -> Result<Model, Error> {
+    let source = load_string(path).await?;
+    let obj = parse_obj(&source).map_err(|(line, message)| Error::ModelParseError {
+        path: path.to_owned(),
+        line,
+        message,
+    })?;
+
+    let mut materials = HashMap::new();
+    let mut textures = HashMap::new();
+    for library in &obj.libraries {
+        let library = relative_to(path, library);
+        let source = load_string(&library).await?;
+        let mtl = parse_mtl(&source).map_err(|(line, message)| Error::ModelParseError {
+            path: library.clone(),
+            line,
+            message,
+        })?;
+
+        for (mut material, texture) in mtl {
+            if let Some(texture) = texture {
+                let texture = relative_to(&library, &texture);
+                if !textures.contains_key(&texture) {
+                    let loaded = load_texture(&texture).await?;
+                    textures.insert(texture.clone(), loaded);
+                }
+                material.diffuse_texture = textures.get(&texture).cloned();
+            }
+            materials.insert(material.name.clone(), material);
+        }
+    }
+
+    Ok(Model {
+        meshes: build_meshes(&obj, &materials),
+    })
+}
+
+/// Draw all the meshes of the model with `transform` applied to them,
+/// with the current camera and material.
+pub fn draw_model(model: &Model, transform: Mat4) {
+    get_context().gl.push_model_matrix(transform);
+    for (mesh, _) in &model.meshes {
+        draw_mesh(mesh);
+    }
+    get_context().gl.pop_model_matrix();
+}
+
+// `path` of a file referred to by the file at `from`, exporters from windows use `\`
+fn relative_to(from: &str, path: &str) -> String {
+    let path = path.replace('\\', "/");
+    match from.rsplit_once('/') {
+        Some((dir, _)) => format!("{dir}/{path}"),
+        None => path,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct Corner {
+    position: usize,
+    uv: Option<usize>,
+    normal: Option<usize>,
+}
+
+#[derive(Default)]
+struct Obj {
+    positions: Vec<Vec3>,
+    uvs: Vec<Vec2>,
+    normals: Vec<Vec3>,
+    libraries: Vec<String>,
+    // the triangles of each material, in the order of their first use
+    groups: Vec<(Option<String>, Vec<[Corner; 3]>)>,
+}
+
+// errors are the line, from 1, and what is wrong with it
+type ParseResult<T> = Result<T, (usize, String)>;
+
+fn numbers<const N: usize>(
+    keyword: &str,
+    args: &[&str],
+    required: usize,
+) -> Result<[f32; N], String> {
+    if args.len() < required {
+        return Err(format!("`{keyword}` needs at least {required} numbers"));
+    }
+
+    let mut numbers = [0.; N];
+    for (number, arg) in numbers.iter_mut().zip(args) {
+        *number = arg
+            .parse()
+            .map_err(|_| format!("`{arg}` is not a number"))?;
+    }
+    Ok(numbers)
+}
+
+fn index(arg: &str, count: usize, kind: &str) -> Result<usize, String> {
+    let index: isize = arg
+        .parse()
+        .map_err(|_| format!("`{arg}` is not a {kind} index"))?;
+    let resolved = if index < 0 {
+        count as isize + index
+    } else {
+        index - 1
+    };
+
+    if index == 0 || resolved < 0 || resolved >= count as isize {
+        return Err(format!(
+            "there is no {kind} {index}, {count} are defined before this line"
+        ));
+    }
+    Ok(resolved as usize)
+}
+
+// the file name after a keyword, which may have spaces
+fn file_name<'a>(keyword: &str, rest: &'a str) -> Result<&'a str, String> {
+    match rest.trim() {
+        "" => Err(format!("`{keyword}` needs a file name")),
+        name => Ok(name),
+    }
+}
+
+fn parse_obj(source: &str) -> ParseResult<Obj> {
+    let mut obj = Obj::default();
+    let mut group = None;
+
+    for (line, text) in source.lines().enumerate() {
+        let line = line + 1;
+        let text = text.split('#').next().unwrap_or("").trim();
+        let (keyword, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let args: Vec<&str> = rest.split_whitespace().collect();
+        let error = |message: String| (line, message);
+
+        match keyword {
+            "" => {}
+            "v" => {
+                let [x, y, z] = numbers(keyword, &args, 3).map_err(error)?;
+                obj.positions.push(Vec3::new(x, y, z));
+            }
+            "vt" => {
+                let [u, v] = numbers(keyword, &args, 1).map_err(error)?;
+                obj.uvs.push(vec2(u, 1. - v));
+            }
+            "vn" => {
+                let [x, y, z] = numbers(keyword, &args, 3).map_err(error)?;
+                obj.normals.push(Vec3::new(x, y, z));
+            }
+            "f" => {
+                if args.len() < 3 {
+                    return Err(error("a face needs at least 3 vertices".to_owned()));
+                }
+                let corners = args
+                    .iter()
+                    .map(|arg| {
+                        let mut parts = arg.split('/');
+                        let position =
+                            index(parts.next().unwrap_or(""), obj.positions.len(), "vertex")?;
+                        let mut optional = |count, kind| match parts.next() {
+                            None | Some("") => Ok(None),
+                            Some(part) => index(part, count, kind).map(Some),
+                        };
+                        Ok(Corner {
+                            position,
+                            uv: optional(obj.uvs.len(), "texture coordinate")?,
+                            normal: optional(obj.normals.len(), "normal")?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, String>>()
+                    .map_err(error)?;
+
+                let group = *group.get_or_insert_with(|| group_index(&mut obj.groups, None));
+                let triangles = &mut obj.groups[group].1;
+                // a fan around the first corner
+                for pair in corners[1..].windows(2) {
+                    triangles.push([corners[0], pair[0], pair[1]]);
+                }
+            }
+            "usemtl" => {
+                let name = file_name(keyword, rest).map_err(error)?;
+                group = Some(group_index(&mut obj.groups, Some(name)));
+            }
+            "mtllib" => {
+                let name = file_name(keyword, rest).map_err(error)?;
+                obj.libraries.push(name.to_owned());
+            }
+            "o" | "g" | "s" | "l" | "p" => {}
+            _ => crate::logging::warn!("load_model: line {}: unknown `{}`", line, keyword),
+        }
+    }
+
+    Ok(obj)
+}
+
+fn group_index(groups: &mut Vec<(Option<String>, Vec<[Corner; 3]>)>, name: Option<&str>) -> usize {
+    if let Some(index) = groups
+        .iter()
+        .position(|(group, _)| group.as_deref() == name)
+    {
+        return index;
+    }
+    groups.push((name.map(str::to_owned), vec![]));
+    groups.len() - 1
+}
+
+// the materials, and the paths of their diffuse textures
+fn parse_mtl(source: &str) -> ParseResult<Vec<(ModelMaterial, Option<String>)>> {
+    let mut materials: Vec<(ModelMaterial, Option<String>)> = vec![];
+
+    for (line, text) in source.lines().enumerate() {
+        let line = line + 1;
+        let text = text.split('#').next().unwrap_or("").trim();
+        let (keyword, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let args: Vec<&str> = rest.split_whitespace().collect();
+        let error = |message: String| (line, message);
+
+        if keyword == "newmtl" {
+            let name = file_name(keyword, rest).map_err(error)?;
+            materials.push((
+                ModelMaterial {
+                    name: name.to_owned(),
+                    ..Default::default()
+                },
+                None,
+            ));
+            continue;
+        }
+        if keyword.is_empty() {
+            continue;
+        }
+        let Some((material, texture)) = materials.last_mut() else {
+            return Err(error(format!("`{keyword}` before any `newmtl`")));
+        };
+
+        match keyword {
+            "Kd" => {
+                let [r, g, b] = numbers(keyword, &args, 3).map_err(error)?;
+                material.diffuse = Color::new(r, g, b, material.diffuse.a);
+            }
+            "d" => {
+                let [d] = numbers(keyword, &args, 1).map_err(error)?;
+                material.diffuse.a = d;
+            }
+            "Tr" => {
+                let [tr] = numbers(keyword, &args, 1).map_err(error)?;
+                material.diffuse.a = 1. - tr;
+            }
+            "map_Kd" => {
+                // options like `-s 1 1 1` come before the file name
+                let name = if rest.trim_start().starts_with('-') {
+                    args.last().copied().unwrap_or("")
+                } else {
+                    rest
+                };
+                *texture = Some(file_name(keyword, name).map_err(error)?.to_owned());
+            }
+            // the other colors and maps are not used by macroquad
+            _ => {}
+        }
+    }
+
+    Ok(materials)
+}
+
+fn build_meshes(
+    obj: &Obj,
+    materials: &HashMap<String, ModelMaterial>,
+) -> Vec<(Mesh, ModelMaterial)> {
+    let mut meshes = vec![];
+
+    for (name, triangles) in &obj.groups {
+        let material = match name {
+            None => ModelMaterial::default(),
+            Some(name) => materials.get(name).cloned().unwrap_or_else(|| {
+                crate::logging::warn!("load_model: no material `{}` in the mtl files", name);
+                ModelMaterial {
+                    name: name.clone(),
+                    ..Default::default()
+                }
+            }),
+        };
+
+        let new_mesh = || Mesh {
+            vertices: vec![],
+            indices: vec![],
+            texture: material.diffuse_texture.clone(),
+        };
+        let finish = |mut mesh: Mesh, missing_normals: bool| {
+            if missing_normals {
+                mesh.compute_normals();
+            }
+            (mesh, material.clone())
+        };
+
+        let mut mesh = new_mesh();
+        let mut missing_normals = false;
+        let mut vertices = HashMap::new();
+        for triangle in triangles {
+            if mesh.vertices.len() + 3 > u16::MAX as usize + 1 {
+                meshes.push(finish(
+                    std::mem::replace(&mut mesh, new_mesh()),
+                    missing_normals,
+                ));
+                missing_normals = false;
+                vertices.clear();
+            }
+
+            for corner in triangle {
+                let index = *vertices.entry(*corner).or_insert_with(|| {
+                    mesh.vertices.push(Vertex {
+                        position: obj.positions[corner.position],
+                        uv: corner.uv.map_or(Vec2::ZERO, |uv| obj.uvs[uv]),
+                        color: material.diffuse.into(),
+                        normal: corner
+                            .normal
+                            .map_or(Vec3::ZERO, |normal| obj.normals[normal])
+                            .extend(0.),
+                    });
+                    mesh.vertices.len() - 1
+                });
+                missing_normals |= corner.normal.is_none();
+                mesh.indices.push(index as u16);
+            }
+        }
+        if !mesh.indices.is_empty() {
+            meshes.push(finish(mesh, missing_normals));
+        }
+    }
+
+    meshes
+}
+
+#[test]
+fn parse_and_build() {
+    let obj = parse_obj(
+        "# a quad and a triangle\n\
+         mtllib quad.mtl\n\
+         v 0 0 0\n\
+         v 1 0 0\n\
+         v 1 1 0\n\
+         v 0 1 0\n\
+         vt 0 0\n\
+         vt 1 1\n\
+         usemtl red\n\
+         f 1/1 2 3/2 4\n\
+         usemtl\tblue\n\
+         f -3 -2 -1\n\
+         usemtl red\n\
+         f 1 2 3\n",
+    )
+    .unwrap();
+    assert_eq!(obj.libraries, ["quad.mtl"]);
+    assert_eq!(obj.uvs[1], vec2(1., 0.));
+
+    let mtl =
+        parse_mtl("newmtl red\nKd 1 0 0\nd 0.5\nmap_Kd -s 1 1 1 textures\\red.png\n").unwrap();
+    assert_eq!(mtl[0].0.diffuse, Color::new(1., 0., 0., 0.5));
+    assert_eq!(mtl[0].1.as_deref(), Some("textures\\red.png"));
+    assert_eq!(
+        relative_to("models/quad.mtl", "textures\\red.png"),
+        "models/textures/red.png"
+    );
+
+    let materials = mtl
+        .into_iter()
+        .map(|(material, _)| (material.name.clone(), material))
+        .collect();
+    let meshes = build_meshes(&obj, &materials);
+    assert_eq!(meshes.len(), 2);
+
+    let (red, material) = &meshes[0];
+    assert_eq!(material.name, "red");
+    // the quad as two triangles, and the last face reusing its vertices but the uvs
+    assert_eq!(red.indices.len(), 9);
+    assert_eq!(red.vertices.len(), 6);
+    assert_eq!(red.vertices[0].color, [255, 0, 0, 127]);
+    assert_eq!(red.vertices[0].normal, Vec3::Z.extend(0.));
+
+    let (blue, material) = &meshes[1];
+    assert_eq!(material.name, "blue");
+    assert_eq!(material.diffuse, WHITE);
+    assert_eq!(blue.indices, [0, 1, 2]);
+    assert_eq!(blue.vertices[0].position, Vec3::new(1., 0., 0.));
+}
+
+#[test]
+fn parse_errors() {
+    let line = |source: &str| parse_obj(source).err().unwrap().0;
+
+    assert_eq!(line("v 0 0 0\nv 0 0\n"), 2);
+    assert_eq!(line("v 0 0 0\n\nf 1 1 2\n"), 3);
+    assert_eq!(line("v 0 0 0\nf 1 1 -2\n"), 2);
+    assert_eq!(line("v 0 0 0\nf 1 1 1/1\n"), 2);
+    assert_eq!(line("f 0 0 0\n"), 1);
+    assert_eq!(line("v 0 0 0\nv 0 0 0\nf 1 2\n"), 3);
+    assert_eq!(parse_mtl("Kd 1 1 1\n").err().unwrap().0, 1);
+}
@@ -14,11 +14,11 @@ pub use crate::window::*;
 
 pub use crate::color::{colors::*, Color};
 pub use crate::quad_gl::{DrawMode, GlPipeline, QuadGl};
+pub use crate::rand;
 pub use glam;
 pub use miniquad::{
     conf::Conf, Comparison, PipelineParams, ShaderError, ShaderSource, UniformDesc, UniformType,
 };
-pub use quad_rand as rand;
 
 pub use crate::experimental::*;
 
@@ -19,6 +19,43 @@ pub enum DrawMode {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct GlPipeline(usize);
 
+/// How the drawing is combined with what is already drawn under it,
+/// see [gl_set_blend_mode](crate::material::gl_set_blend_mode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Transparent pixels let what is under them show through.
+    #[default]
+    Alpha,
+    /// Adds up the colors, for glows, fire and particles: black draws nothing.
+    Additive,
+    /// Multiplies the colors, for shadows and tinting: white draws nothing.
+    /// The alpha is not used, fade the color towards white instead.
+    Multiply,
+    /// Overwrites what is under, alpha included.
+    Replace,
+}
+
+impl BlendMode {
+    fn blend_state(self) -> Option<BlendState> {
+        let (source, destination) = match self {
+            BlendMode::Alpha => (
+                BlendFactor::Value(BlendValue::SourceAlpha),
+                BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+            ),
+            BlendMode::Additive => (
+                BlendFactor::Value(BlendValue::SourceAlpha),
+                BlendFactor::One,
+            ),
+            BlendMode::Multiply => (
+                BlendFactor::Value(BlendValue::DestinationColor),
+                BlendFactor::Zero,
+            ),
+            BlendMode::Replace => return None,
+        };
+        Some(BlendState::new(Equation::Add, source, destination))
+    }
+}
+
 struct DrawCall {
     vertices_count: usize,
     indices_count: usize,
@@ -36,6 +73,8 @@ struct DrawCall {
     uniforms: Option<Vec<u8>>,
     render_pass: Option<RenderPass>,
     capture: bool,
+    /// Vertex and index buffers of its own, from [QuadGl::draw_buffers], instead of the batch.
+    buffers: Option<(BufferId, BufferId)>,
 }
 
 impl DrawCall {
@@ -61,6 +100,7 @@ impl DrawCall {
             uniforms,
             render_pass,
             capture: false,
+            buffers: None,
         }
     }
 }
@@ -261,6 +301,9 @@ struct GlState {
     model_stack: Vec<glam::Mat4>,
     pipeline: Option<GlPipeline>,
     depth_test_enable: bool,
+    blend_mode: BlendMode,
+    /// The modes to go back to with [QuadGl::pop_blend_mode].
+    blend_stack: Vec<BlendMode>,
 
     break_batching: bool,
     snapshotter: MagicSnapshotter,
@@ -275,10 +318,11 @@ impl GlState {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 struct Uniform {
     name: String,
     uniform_type: UniformType,
+    array_count: usize,
     byte_offset: usize,
     byte_size: usize,
 }
@@ -286,6 +330,7 @@ struct Uniform {
 #[derive(Clone)]
 struct PipelineExt {
     pipeline: miniquad::Pipeline,
+    shader: ShaderId,
     wants_screen_texture: bool,
     uniforms: Vec<Uniform>,
     uniforms_data: Vec<u8>,
@@ -319,7 +364,10 @@ impl PipelineExt {
             return;
         }
         if uniform_byte_size != uniform_meta.byte_size {
-            warn!("set_uniform do not support uniform arrays");
+            warn!(
+                "Trying to set uniform array {} with set_uniform, use set_uniform_array",
+                name
+            );
             return;
         }
         macro_rules! transmute_uniform {
@@ -354,32 +402,58 @@ impl PipelineExt {
         let uniform_byte_size = uniform_meta.byte_size;
         let uniform_byte_offset = uniform_meta.byte_offset;
 
-        let data = uniform.to_bytes();
-        if data.len() != uniform_byte_size {
+        if size_of::<T>() != uniform_meta.uniform_type.size() {
             warn!(
-                "Trying to set uniform {} sized {} bytes value of {} bytes",
+                "Trying to set uniform array {} of {:?} with {} bytes elements",
                 name,
-                uniform_byte_size,
+                uniform_meta.uniform_type,
                 size_of::<T>()
             );
             return;
         }
-        self.uniforms_data[uniform_byte_offset..(uniform_byte_size + uniform_byte_offset)]
-            .copy_from_slice(&data[..uniform_byte_size]);
+        if uniform.len() > uniform_meta.array_count {
+            warn!(
+                "Trying to set uniform array {} of {} elements with {} elements",
+                name,
+                uniform_meta.array_count,
+                uniform.len()
+            );
+            return;
+        }
+
+        // the elements past the given ones are zeroed, not left from the previous call
+        let data = uniform.to_bytes();
+        let target =
+            &mut self.uniforms_data[uniform_byte_offset..(uniform_byte_size + uniform_byte_offset)];
+        target[..data.len()].copy_from_slice(data);
+        target[data.len()..].fill(0);
     }
 }
 
 struct PipelinesStorage {
     pipelines: [Option<PipelineExt>; Self::MAX_PIPELINES],
     pipelines_amount: usize,
+    /// The shader of the default material.
+    shader: ShaderId,
+    /// The default pipelines of the blend modes other than [BlendMode::Alpha],
+    /// made on their first use.
+    blended: Vec<(BlendMode, [GlPipeline; 4])>,
 }
 
 impl PipelinesStorage {
-    const MAX_PIPELINES: usize = 32;
+    /// 32, and room for the default pipelines of the 3 blend modes made on demand,
+    /// so using them leaves as many pipelines to the user materials.
+    const MAX_PIPELINES: usize = 32 + 3 * 4;
     const TRIANGLES_PIPELINE: GlPipeline = GlPipeline(0);
     const LINES_PIPELINE: GlPipeline = GlPipeline(1);
     const TRIANGLES_DEPTH_PIPELINE: GlPipeline = GlPipeline(2);
     const LINES_DEPTH_PIPELINE: GlPipeline = GlPipeline(3);
+    const DEFAULT_PIPELINES: [GlPipeline; 4] = [
+        Self::TRIANGLES_PIPELINE,
+        Self::LINES_PIPELINE,
+        Self::TRIANGLES_DEPTH_PIPELINE,
+        Self::LINES_DEPTH_PIPELINE,
+    ];
 
     fn new(ctx: &mut dyn RenderingBackend) -> PipelinesStorage {
         let shader = ctx
@@ -397,88 +471,134 @@ impl PipelinesStorage {
             )
             .unwrap_or_else(|e| panic!("Failed to load shader: {e}"));
 
+        let mut storage = PipelinesStorage {
+            pipelines: std::array::from_fn(|_| None),
+            pipelines_amount: 0,
+            shader,
+            blended: vec![],
+        };
+
+        let pipelines = storage.make_default_pipelines(ctx, BlendMode::Alpha);
+        assert_eq!(pipelines, Self::DEFAULT_PIPELINES);
+
+        storage
+    }
+
+    /// The pipelines of the default material blending with `blend_mode`,
+    /// in the order of [PipelinesStorage::get].
+    fn make_default_pipelines(
+        &mut self,
+        ctx: &mut dyn RenderingBackend,
+        blend_mode: BlendMode,
+    ) -> [GlPipeline; 4] {
         let params = PipelineParams {
-            color_blend: Some(BlendState::new(
-                Equation::Add,
-                BlendFactor::Value(BlendValue::SourceAlpha),
-                BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
-            )),
+            color_blend: blend_mode.blend_state(),
             ..Default::default()
         };
 
-        let mut storage = PipelinesStorage {
-            pipelines: Default::default(),
-            pipelines_amount: 0,
-        };
+        [
+            (PrimitiveType::Triangles, false),
+            (PrimitiveType::Lines, false),
+            (PrimitiveType::Triangles, true),
+            (PrimitiveType::Lines, true),
+        ]
+        .map(|(primitive_type, depth)| {
+            let params = if depth {
+                PipelineParams {
+                    depth_write: true,
+                    depth_test: Comparison::LessOrEqual,
+                    primitive_type,
+                    ..params
+                }
+            } else {
+                PipelineParams {
+                    primitive_type,
+                    ..params
+                }
+            };
+            self.make_pipeline(ctx, self.shader, params, false, vec![], vec![])
+        })
+    }
 
-        let triangles_pipeline = storage.make_pipeline(
-            ctx,
-            shader,
-            PipelineParams {
-                primitive_type: PrimitiveType::Triangles,
-                ..params
-            },
-            false,
-            vec![],
-            vec![],
-        );
-        assert_eq!(triangles_pipeline, Self::TRIANGLES_PIPELINE);
+    /// Make the default pipelines for `blend_mode` unless they are there already.
+    fn prepare_blend_mode(&mut self, ctx: &mut dyn RenderingBackend, blend_mode: BlendMode) {
+        if blend_mode == BlendMode::Alpha
+            || self.blended.iter().any(|(mode, _)| *mode == blend_mode)
+        {
+            return;
+        }
 
-        let lines_pipeline = storage.make_pipeline(
-            ctx,
-            shader,
-            PipelineParams {
-                primitive_type: PrimitiveType::Lines,
-                ..params
-            },
-            false,
-            vec![],
-            vec![],
-        );
-        assert_eq!(lines_pipeline, Self::LINES_PIPELINE);
+        let pipelines = self.make_default_pipelines(ctx, blend_mode);
+        self.blended.push((blend_mode, pipelines));
+    }
 
-        let triangles_depth_pipeline = storage.make_pipeline(
+    fn make_pipeline(
+        &mut self,
+        ctx: &mut dyn RenderingBackend,
+        shader: ShaderId,
+        params: PipelineParams,
+        wants_screen_texture: bool,
+        uniforms: Vec<UniformDesc>,
+        textures: Vec<String>,
+    ) -> GlPipeline {
+        let id = self
+            .pipelines
+            .iter()
+            .position(|p| p.is_none())
+            .unwrap_or_else(|| panic!("Pipelines amount exceeded"));
+
+        self.pipelines[id] = Some(Self::new_pipeline_ext(
             ctx,
             shader,
-            PipelineParams {
-                depth_write: true,
-                depth_test: Comparison::LessOrEqual,
-                primitive_type: PrimitiveType::Triangles,
-                ..params
-            },
-            false,
-            vec![],
-            vec![],
-        );
-        assert_eq!(triangles_depth_pipeline, Self::TRIANGLES_DEPTH_PIPELINE);
+            params,
+            wants_screen_texture,
+            uniforms,
+            textures,
+        ));
+        self.pipelines_amount += 1;
+
+        GlPipeline(id)
+    }
 
-        let lines_depth_pipeline = storage.make_pipeline(
+    /// Swaps the shader of an existing pipeline and deletes the old one, the uniform
+    /// and texture values set so far are kept when the list of uniforms did not change.
+    fn replace_pipeline(
+        &mut self,
+        ctx: &mut dyn RenderingBackend,
+        pip: GlPipeline,
+        shader: ShaderId,
+        params: PipelineParams,
+        wants_screen_texture: bool,
+        uniforms: Vec<UniformDesc>,
+        textures: Vec<String>,
+    ) {
+        let mut pipeline = Self::new_pipeline_ext(
             ctx,
             shader,
-            PipelineParams {
-                depth_write: true,
-                depth_test: Comparison::LessOrEqual,
-                primitive_type: PrimitiveType::Lines,
-                ..params
-            },
-            false,
-            vec![],
-            vec![],
+            params,
+            wants_screen_texture,
+            uniforms,
+            textures,
         );
-        assert_eq!(lines_depth_pipeline, Self::LINES_DEPTH_PIPELINE);
+        let old = self.get_quad_pipeline_mut(pip);
+        if old.uniforms == pipeline.uniforms {
+            pipeline.uniforms_data = std::mem::take(&mut old.uniforms_data);
+        }
+        pipeline.textures_data = std::mem::take(&mut old.textures_data);
+        let old = std::mem::replace(old, pipeline);
 
-        storage
+        ctx.delete_pipeline(old.pipeline);
+        ctx.delete_shader(old.shader);
     }
 
-    fn make_pipeline(
-        &mut self,
+    fn new_pipeline_ext(
         ctx: &mut dyn RenderingBackend,
         shader: ShaderId,
         params: PipelineParams,
         wants_screen_texture: bool,
         mut uniforms: Vec<UniformDesc>,
         textures: Vec<String>,
-    ) -> GlPipeline {
+    ) -> PipelineExt {
         let pipeline = ctx.new_pipeline(
             &[BufferLayout::default()],
             &[
@@ -491,12 +611,6 @@ impl PipelinesStorage {
             params,
         );
 
-        let id = self
-            .pipelines
-            .iter()
-            .position(|p| p.is_none())
-            .unwrap_or_else(|| panic!("Pipelines amount exceeded"));
-
         let mut max_offset = 0;
 
         for (name, kind) in shader::uniforms().into_iter().rev() {
@@ -510,6 +624,7 @@ impl PipelinesStorage {
                 let uniform = Uniform {
                     name: uniform.name.clone(),
                     uniform_type: uniform.uniform_type,
+                    array_count: uniform.array_count,
                     byte_size,
                     byte_offset: *offset,
                 };
@@ -520,25 +635,29 @@ impl PipelinesStorage {
             })
             .collect();
 
-        self.pipelines[id] = Some(PipelineExt {
+        PipelineExt {
             pipeline,
+            shader,
             wants_screen_texture,
             uniforms,
             uniforms_data: vec![0; max_offset],
             textures,
             textures_data: BTreeMap::new(),
-        });
-        self.pipelines_amount += 1;
-
-        GlPipeline(id)
+        }
     }
 
-    const fn get(&self, draw_mode: DrawMode, depth_enabled: bool) -> GlPipeline {
+    fn get(&self, draw_mode: DrawMode, depth_enabled: bool, blend_mode: BlendMode) -> GlPipeline {
+        let pipelines = self
+            .blended
+            .iter()
+            .find(|(mode, _)| *mode == blend_mode)
+            .map_or(Self::DEFAULT_PIPELINES, |(_, pipelines)| *pipelines);
+
         match (draw_mode, depth_enabled) {
-            (DrawMode::Triangles, false) => Self::TRIANGLES_PIPELINE,
-            (DrawMode::Triangles, true) => Self::TRIANGLES_DEPTH_PIPELINE,
-            (DrawMode::Lines, false) => Self::LINES_PIPELINE,
-            (DrawMode::Lines, true) => Self::LINES_DEPTH_PIPELINE,
+            (DrawMode::Triangles, false) => pipelines[0],
+            (DrawMode::Lines, false) => pipelines[1],
+            (DrawMode::Triangles, true) => pipelines[2],
+            (DrawMode::Lines, true) => pipelines[3],
         }
     }
 
@@ -566,6 +685,8 @@ pub struct QuadGl {
 
     batch_vertex_buffer: Vec<Vertex>,
     batch_index_buffer: Vec<u16>,
+    /// Buffers dropped by their owners, deleted once the draw calls using them are done.
+    retired_buffers: Vec<BufferId>,
 }
 
 impl QuadGl {
@@ -587,6 +708,8 @@ impl QuadGl {
                 pipeline: None,
                 break_batching: false,
                 depth_test_enable: false,
+                blend_mode: BlendMode::Alpha,
+                blend_stack: vec![],
                 snapshotter: MagicSnapshotter::new(ctx),
                 render_pass: None,
                 capture: false,
@@ -601,6 +724,7 @@ impl QuadGl {
             batch_index_buffer: Vec::with_capacity(max_indices),
             max_vertices,
             max_indices,
+            retired_buffers: vec![],
         }
     }
 
@@ -612,13 +736,55 @@ impl QuadGl {
         uniforms: Vec<UniformDesc>,
         textures: Vec<String>,
     ) -> miniquad::Result<GlPipeline> {
+        let (shader, wants_screen_texture) = Self::new_shader(ctx, shader, &uniforms, &textures)?;
+        Ok(self.pipelines.make_pipeline(
+            ctx,
+            shader,
+            params,
+            wants_screen_texture,
+            uniforms,
+            textures,
+        ))
+    }
+
+    /// Recompiles the shader of an existing pipeline, keeping the same `GlPipeline`.
+    /// On a compilation error the pipeline is left as it was.
+    pub fn reload_pipeline(
+        &mut self,
+        ctx: &mut dyn miniquad::RenderingBackend,
+        pipeline: GlPipeline,
+        shader: miniquad::ShaderSource,
+        params: PipelineParams,
+        uniforms: Vec<UniformDesc>,
+        textures: Vec<String>,
+    ) -> miniquad::Result<()> {
+        let (shader, wants_screen_texture) = Self::new_shader(ctx, shader, &uniforms, &textures)?;
+        self.pipelines.replace_pipeline(
+            ctx,
+            pipeline,
+            shader,
+            params,
+            wants_screen_texture,
+            uniforms,
+            textures,
+        );
+        self.state.break_batching = true;
+        Ok(())
+    }
+
+    fn new_shader(
+        ctx: &mut dyn miniquad::RenderingBackend,
+        shader: miniquad::ShaderSource,
+        uniforms: &[UniformDesc],
+        textures: &[String],
+    ) -> miniquad::Result<(ShaderId, bool)> {
         let mut shader_meta: ShaderMeta = shader::meta();
 
-        for uniform in &uniforms {
+        for uniform in uniforms {
             shader_meta.uniforms.uniforms.push(uniform.clone());
         }
 
-        for texture in &textures {
+        for texture in textures {
             if texture == "Texture" {
                 panic!(
                     "you can't use name `Texture` for your texture. This name is reserved for the texture that will be drawn with that material"
@@ -638,14 +804,7 @@ impl QuadGl {
         };
         let wants_screen_texture = source.contains("_ScreenTexture");
         let shader = ctx.new_shader(shader, shader_meta)?;
-        Ok(self.pipelines.make_pipeline(
-            ctx,
-            shader,
-            params,
-            wants_screen_texture,
-            uniforms,
-            textures,
-        ))
+        Ok((shader, wants_screen_texture))
     }
 
     pub(crate) fn clear(&mut self, ctx: &mut dyn miniquad::RenderingBackend, color: Color) {
@@ -671,6 +830,8 @@ impl QuadGl {
         self.state.clip = None;
         self.state.texture = None;
         self.state.model_stack = vec![glam::Mat4::IDENTITY];
+        self.state.blend_mode = BlendMode::Alpha;
+        self.state.blend_stack.clear();
 
         self.draw_calls_count = 0;
     }
@@ -727,20 +888,26 @@ impl QuadGl {
                 ctx.begin_default_pass(PassAction::Nothing);
             }
 
-            ctx.buffer_update(
-                bindings.vertex_buffers[0],
-                BufferSource::slice(
-                    &self.batch_vertex_buffer
-                        [dc.vertices_start..(dc.vertices_start + dc.vertices_count)],
-                ),
-            );
-            ctx.buffer_update(
-                bindings.index_buffer,
-                BufferSource::slice(
-                    &self.batch_index_buffer
-                        [dc.indices_start..(dc.indices_start + dc.indices_count)],
-                ),
-            );
+            let batch_buffers = (bindings.vertex_buffers[0], bindings.index_buffer);
+            if let Some((vertex_buffer, index_buffer)) = dc.buffers {
+                bindings.vertex_buffers[0] = vertex_buffer;
+                bindings.index_buffer = index_buffer;
+            } else {
+                ctx.buffer_update(
+                    bindings.vertex_buffers[0],
+                    BufferSource::slice(
+                        &self.batch_vertex_buffer
+                            [dc.vertices_start..(dc.vertices_start + dc.vertices_count)],
+                    ),
+                );
+                ctx.buffer_update(
+                    bindings.index_buffer,
+                    BufferSource::slice(
+                        &self.batch_index_buffer
+                            [dc.indices_start..(dc.indices_start + dc.indices_count)],
+                    ),
+                );
+            }
 
             bindings.images[0] = dc.texture.unwrap_or(white_texture);
             bindings.images[1] = self
@@ -787,6 +954,7 @@ impl QuadGl {
             if dc.capture {
                 telemetry::track_drawcall(&pipeline.pipeline, bindings, dc.indices_count);
             }
+            (bindings.vertex_buffers[0], bindings.index_buffer) = batch_buffers;
 
             dc.vertices_count = 0;
             dc.indices_count = 0;
@@ -797,6 +965,10 @@ impl QuadGl {
         self.draw_calls_count = 0;
         self.batch_index_buffer.clear();
         self.batch_vertex_buffer.clear();
+
+        for buffer in self.retired_buffers.drain(..) {
+            ctx.delete_buffer(buffer);
+        }
     }
 
     pub(crate) fn capture(&mut self, capture: bool) {
@@ -815,6 +987,14 @@ impl QuadGl {
         self.state.render_pass
     }
 
+    pub const fn get_active_viewport(&self) -> Option<(i32, i32, i32, i32)> {
+        self.state.viewport
+    }
+
+    pub const fn get_active_pipeline(&self) -> Option<GlPipeline> {
+        self.state.pipeline
+    }
+
     pub const fn is_depth_test_enabled(&self) -> bool {
         self.state.depth_test_enable
     }
@@ -872,6 +1052,34 @@ impl QuadGl {
         self.state.draw_mode = mode;
     }
 
+    pub const fn get_blend_mode(&self) -> BlendMode {
+        self.state.blend_mode
+    }
+
+    /// Blend the following geometry of the default material with `mode`.
+    ///
+    /// Geometry of another blend mode goes to other draw calls, so the drawing order is kept.
+    pub fn blend_mode(&mut self, ctx: &mut dyn RenderingBackend, mode: BlendMode) {
+        self.pipelines.prepare_blend_mode(ctx, mode);
+        self.state.blend_mode = mode;
+    }
+
+    pub fn push_blend_mode(&mut self, ctx: &mut dyn RenderingBackend, mode: BlendMode) {
+        self.state.blend_stack.push(self.state.blend_mode);
+        self.blend_mode(ctx, mode);
+    }
+
+    /// Go back to the blend mode before the last [QuadGl::push_blend_mode].
+    /// Returns false when there is nothing to pop.
+    pub fn pop_blend_mode(&mut self) -> bool {
+        let Some(mode) = self.state.blend_stack.pop() else {
+            return false;
+        };
+        // pushed before, so already prepared
+        self.state.blend_mode = mode;
+        true
+    }
+
     pub fn geometry(&mut self, vertices: &[Vertex], indices: &[u16]) {
         if vertices.len() >= self.max_vertices || indices.len() >= self.max_indices {
             warn!("geometry() exceeded max drawcall size, clamping");
@@ -880,10 +1088,7 @@ impl QuadGl {
         let vertices = &vertices[0..self.max_vertices.min(vertices.len())];
         let indices = &indices[0..self.max_indices.min(indices.len())];
 
-        let pip = self.state.pipeline.unwrap_or(
-            self.pipelines
-                .get(self.state.draw_mode, self.state.depth_test_enable),
-        );
+        let pip = self.current_pipeline();
 
         let previous_dc_ix = if self.draw_calls_count == 0 {
             None
@@ -903,40 +1108,10 @@ impl QuadGl {
                 || draw_call.vertices_count >= self.max_vertices - vertices.len()
                 || draw_call.indices_count >= self.max_indices - indices.len()
                 || draw_call.capture != self.state.capture
+                || draw_call.buffers.is_some()
                 || self.state.break_batching
         }) {
-            let uniforms = self.state.pipeline.map(|pipeline| {
-                self.pipelines
-                    .get_quad_pipeline_mut(pipeline)
-                    .uniforms_data
-                    .clone()
-            });
-
-            if self.draw_calls_count >= self.draw_calls.len() {
-                self.draw_calls.push(DrawCall::new(
-                    self.state.texture,
-                    self.state.model(),
-                    self.state.draw_mode,
-                    pip,
-                    uniforms.clone(),
-                    self.state.render_pass,
-                ));
-            }
-            self.draw_calls[self.draw_calls_count].texture = self.state.texture;
-            self.draw_calls[self.draw_calls_count].uniforms = uniforms;
-            self.draw_calls[self.draw_calls_count].vertices_count = 0;
-            self.draw_calls[self.draw_calls_count].indices_count = 0;
-            self.draw_calls[self.draw_calls_count].clip = self.state.clip;
-            self.draw_calls[self.draw_calls_count].viewport = self.state.viewport;
-            self.draw_calls[self.draw_calls_count].model = self.state.model();
-            self.draw_calls[self.draw_calls_count].pipeline = pip;
-            self.draw_calls[self.draw_calls_count].render_pass = self.state.render_pass;
-            self.draw_calls[self.draw_calls_count].capture = self.state.capture;
-            self.draw_calls[self.draw_calls_count].indices_start = self.batch_index_buffer.len();
-            self.draw_calls[self.draw_calls_count].vertices_start = self.batch_vertex_buffer.len();
-
-            self.draw_calls_count += 1;
-            self.state.break_batching = false;
+            self.new_draw_call(pip);
         };
         let dc = &mut self.draw_calls[self.draw_calls_count - 1];
 
@@ -950,6 +1125,76 @@ impl QuadGl {
         dc.texture = self.state.texture;
     }
 
+    /// Draw `indices_count` indices of geometry uploaded to buffers of its own,
+    /// in a draw call of its own with the current state.
+    ///
+    /// The buffers have to stay alive until the end of the frame.
+    pub fn draw_buffers(
+        &mut self,
+        vertex_buffer: BufferId,
+        index_buffer: BufferId,
+        indices_count: usize,
+    ) {
+        let pip = self.current_pipeline();
+        self.new_draw_call(pip);
+
+        let dc = &mut self.draw_calls[self.draw_calls_count - 1];
+        dc.buffers = Some((vertex_buffer, index_buffer));
+        dc.indices_count = indices_count;
+
+        // the following geometry goes to the batch again
+        self.state.break_batching = true;
+    }
+
+    /// Delete the buffers after the draw calls already made with them.
+    pub(crate) fn retire_buffers(&mut self, buffers: &[BufferId]) {
+        self.retired_buffers.extend_from_slice(buffers);
+    }
+
+    fn current_pipeline(&self) -> GlPipeline {
+        self.state.pipeline.unwrap_or(self.pipelines.get(
+            self.state.draw_mode,
+            self.state.depth_test_enable,
+            self.state.blend_mode,
+        ))
+    }
+
+    fn new_draw_call(&mut self, pip: GlPipeline) {
+        let uniforms = self.state.pipeline.map(|pipeline| {
+            self.pipelines
+                .get_quad_pipeline_mut(pipeline)
+                .uniforms_data
+                .clone()
+        });
+
+        if self.draw_calls_count >= self.draw_calls.len() {
+            self.draw_calls.push(DrawCall::new(
+                self.state.texture,
+                self.state.model(),
+                self.state.draw_mode,
+                pip,
+                uniforms.clone(),
+                self.state.render_pass,
+            ));
+        }
+        self.draw_calls[self.draw_calls_count].texture = self.state.texture;
+        self.draw_calls[self.draw_calls_count].uniforms = uniforms;
+        self.draw_calls[self.draw_calls_count].vertices_count = 0;
+        self.draw_calls[self.draw_calls_count].indices_count = 0;
+        self.draw_calls[self.draw_calls_count].clip = self.state.clip;
+        self.draw_calls[self.draw_calls_count].viewport = self.state.viewport;
+        self.draw_calls[self.draw_calls_count].model = self.state.model();
+        self.draw_calls[self.draw_calls_count].pipeline = pip;
+        self.draw_calls[self.draw_calls_count].render_pass = self.state.render_pass;
+        self.draw_calls[self.draw_calls_count].capture = self.state.capture;
+        self.draw_calls[self.draw_calls_count].buffers = None;
+        self.draw_calls[self.draw_calls_count].indices_start = self.batch_index_buffer.len();
+        self.draw_calls[self.draw_calls_count].vertices_start = self.batch_vertex_buffer.len();
+
+        self.draw_calls_count += 1;
+        self.state.break_batching = false;
+    }
+
     pub fn delete_pipeline(&mut self, pipeline: GlPipeline) {
         self.pipelines.delete_pipeline(pipeline);
     }
@@ -961,6 +1206,14 @@ impl QuadGl {
             .get_quad_pipeline_mut(pipeline)
             .set_uniform(name, uniform);
     }
+    pub(crate) fn has_uniform(&mut self, pipeline: GlPipeline, name: &str) -> bool {
+        self.pipelines
+            .get_quad_pipeline_mut(pipeline)
+            .uniforms
+            .iter()
+            .any(|uniform| uniform.name == name)
+    }
+
     pub fn set_uniform_array<T: ToBytes>(
         &mut self,
         pipeline: GlPipeline,
@@ -993,6 +1246,11 @@ impl QuadGl {
             .or_insert(quad_texture) = quad_texture;
     }
 
+    /// The vertices and indices a single [QuadGl::geometry] call fits without clamping.
+    pub(crate) const fn max_geometry_size(&self) -> (usize, usize) {
+        (self.max_vertices - 1, self.max_indices - 1)
+    }
+
     pub(crate) fn update_drawcall_capacity(
         &mut self,
         ctx: &mut dyn miniquad::RenderingBackend,
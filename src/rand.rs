@@ -0,0 +1,268 @@
+//! Cross platform random generator.
+//!
+//! The global functions, like [gen_range], share one generator per thread.
+//! For a stream of numbers that can be replayed, like in a networked lockstep game,
+//! give the game its own [Rng] instead:
+//! ```
+//! use macroquad::rand::Rng;
+//!
+//! let mut rng = Rng::with_seed(42);
+//! let snapshot = rng.clone();
+//! let roll: i32 = rng.gen_range(1, 7);
+//!
+//! // the clone continues from where it was taken
+//! assert_eq!(snapshot.clone().gen_range(1, 7), roll);
+//! ```
+
+use std::cell::RefCell;
+
+pub use quad_rand::*;
+
+/// Random number generator with its own state, the same seed gives the same numbers
+/// on every platform.
+///
+/// Cloning forks the stream: the clone produces the numbers the original is going to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub const fn with_seed(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// Restart the stream, same as replacing it with `Rng::with_seed(seed)`.
+    pub fn seed(&mut self, seed: u64) {
+        self.state = seed;
+    }
+
+    // SplitMix64
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// A random value of any type implementing [Random], floats are in `0.0..1.0`.
+    pub fn gen<T: Random>(&mut self) -> T {
+        T::random(self)
+    }
+
+    /// A random value in `low..high`, `low` if the range is empty.
+    pub fn gen_range<T: RngRange>(&mut self, low: T, high: T) -> T {
+        T::gen_range_with(self, low, high)
+    }
+
+    /// A random element of `slice`, None if it is empty.
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        (!slice.is_empty()).then(|| &slice[self.gen_range(0, slice.len())])
+    }
+
+    pub fn choose_mut<'a, T>(&mut self, slice: &'a mut [T]) -> Option<&'a mut T> {
+        if slice.is_empty() {
+            return None;
+        }
+        let index = self.gen_range(0, slice.len());
+        Some(&mut slice[index])
+    }
+
+    /// `amount` different elements of `slice` in a random order, all of them if it has less.
+    pub fn choose_multiple<'a, T>(
+        &mut self,
+        slice: &'a [T],
+        amount: usize,
+    ) -> ChooseMultiple<'a, T> {
+        let amount = amount.min(slice.len());
+        let mut indices: Vec<usize> = (0..slice.len()).collect();
+        for i in 0..amount {
+            indices.swap(i, self.gen_range(i, slice.len()));
+        }
+        indices.truncate(amount);
+
+        ChooseMultiple {
+            slice,
+            indices: indices.into_iter(),
+        }
+    }
+
+    /// Put the elements of `slice` in a random order.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        // Fisher-Yates
+        for i in (1..slice.len()).rev() {
+            slice.swap(i, self.gen_range(0, i + 1));
+        }
+    }
+}
+
+/// Types [Rng::gen] can produce.
+pub trait Random {
+    fn random(rng: &mut Rng) -> Self;
+}
+
+impl Random for f32 {
+    fn random(rng: &mut Rng) -> f32 {
+        (rng.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+impl Random for f64 {
+    fn random(rng: &mut Rng) -> f64 {
+        (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl Random for bool {
+    fn random(rng: &mut Rng) -> bool {
+        rng.next_u64() >> 63 == 1
+    }
+}
+
+macro_rules! random_int {
+    ($($t:ty),*) => {
+        $(
+            impl Random for $t {
+                fn random(rng: &mut Rng) -> $t {
+                    rng.next_u64() as $t
+                }
+            }
+        )*
+    };
+}
+
+random_int!(u8, u16, u32, usize, i8, i16, i32, i64, isize);
+
+impl Random for u64 {
+    fn random(rng: &mut Rng) -> u64 {
+        rng.next_u64()
+    }
+}
+
+/// Types [Rng::gen_range] and the global [gen_range] can produce.
+///
+/// Types implementing quad_rand's [RandomRange] outside of macroquad
+/// are still sampled by `quad_rand::gen_range`, from quad_rand's own generator.
+pub trait RngRange: Sized {
+    fn gen_range_with(rng: &mut Rng, low: Self, high: Self) -> Self;
+}
+
+macro_rules! random_range_float {
+    ($($t:ty),*) => {
+        $(
+            impl RngRange for $t {
+                fn gen_range_with(rng: &mut Rng, low: $t, high: $t) -> $t {
+                    if high <= low {
+                        return low;
+                    }
+                    low + (high - low) * rng.gen::<$t>()
+                }
+            }
+        )*
+    };
+}
+
+random_range_float!(f32, f64);
+
+macro_rules! random_range_int {
+    ($($t:ty),*) => {
+        $(
+            impl RngRange for $t {
+                fn gen_range_with(rng: &mut Rng, low: $t, high: $t) -> $t {
+                    if high <= low {
+                        return low;
+                    }
+                    let range = (high as i128 - low as i128) as u128;
+                    // the high bits of the product are evenly spread over 0..range
+                    let offset = (rng.next_u64() as u128 * range) >> 64;
+                    (low as i128 + offset as i128) as $t
+                }
+            }
+        )*
+    };
+}
+
+random_range_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+std::thread_local! {
+    static RNG: RefCell<Rng> = const { RefCell::new(Rng::with_seed(0)) };
+}
+
+fn with_rng<R>(f: impl FnOnce(&mut Rng) -> R) -> R {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+/// Seed the generator behind the global functions and [ChooseRandom].
+pub fn srand(seed: u64) {
+    RNG.with(|rng| rng.borrow_mut().seed(seed));
+    quad_rand::srand(seed);
+}
+
+pub fn rand() -> u32 {
+    with_rng(Rng::next_u32)
+}
+
+/// A random value in `low..high`, from the global generator.
+pub fn gen_range<T: RngRange>(low: T, high: T) -> T {
+    with_rng(|rng| rng.gen_range(low, high))
+}
+
+/// A snapshot of the global generator, to be restored with [set_rng].
+pub fn get_rng() -> Rng {
+    RNG.with(|rng| rng.borrow().clone())
+}
+
+/// Replace the global generator, sequences continue from where `rng` is.
+pub fn set_rng(rng: Rng) {
+    RNG.with(|global| *global.borrow_mut() = rng);
+}
+
+/// Picking from slices with the global generator, see [Rng::choose] for a generator of its own.
+pub trait ChooseRandom<T> {
+    fn shuffle(&mut self);
+    fn choose(&self) -> Option<&T>;
+    fn choose_mut(&mut self) -> Option<&mut T>;
+    fn choose_multiple(&self, amount: usize) -> ChooseMultiple<'_, T>;
+}
+
+impl<T> ChooseRandom<T> for [T] {
+    fn shuffle(&mut self) {
+        with_rng(|rng| rng.shuffle(self))
+    }
+
+    fn choose(&self) -> Option<&T> {
+        with_rng(|rng| rng.choose(self))
+    }
+
+    fn choose_mut(&mut self) -> Option<&mut T> {
+        with_rng(|rng| rng.choose_mut(self))
+    }
+
+    fn choose_multiple(&self, amount: usize) -> ChooseMultiple<'_, T> {
+        with_rng(|rng| rng.choose_multiple(self, amount))
+    }
+}
+
+/// The elements picked by [Rng::choose_multiple].
+pub struct ChooseMultiple<'a, T> {
+    slice: &'a [T],
+    indices: std::vec::IntoIter<usize>,
+}
+
+impl<'a, T> Iterator for ChooseMultiple<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.indices.next().map(|index| &self.slice[index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
@@ -187,6 +187,265 @@ pub fn draw_rectangle_ex(x: f32, y: f32, w: f32, h: f32, params: DrawRectanglePa
     context.gl.geometry(&vertices, &indices);
 }
 
+/// Draws a rectangle with its top-left corner at `[x, y]` with size `[w, h]`,
+/// blending between the corner `colors`, clockwise starting from the top left one.
+pub fn draw_rectangle_gradient(x: f32, y: f32, w: f32, h: f32, colors: [Color; 4]) {
+    let context = get_context();
+    let [tl, tr, br, bl] = colors;
+
+    #[rustfmt::skip]
+    let vertices = [
+        Vertex::new(x    , y    , 0., 0.0, 0.0, tl),
+        Vertex::new(x + w, y    , 0., 1.0, 0.0, tr),
+        Vertex::new(x + w, y + h, 0., 1.0, 1.0, br),
+        Vertex::new(x    , y + h, 0., 0.0, 1.0, bl),
+    ];
+    let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
+/// Draws a rectangle with a horizontal gradient from the `left` to the `right` color.
+pub fn draw_rectangle_gradient_h(x: f32, y: f32, w: f32, h: f32, left: Color, right: Color) {
+    draw_rectangle_gradient(x, y, w, h, [left, right, right, left]);
+}
+
+/// Draws a rectangle with a vertical gradient from the `top` to the `bottom` color.
+pub fn draw_rectangle_gradient_v(x: f32, y: f32, w: f32, h: f32, top: Color, bottom: Color) {
+    draw_rectangle_gradient(x, y, w, h, [top, top, bottom, bottom]);
+}
+
+/// Corner radii of a rounded rectangle, clockwise from the top left corner.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RectRadii {
+    pub tl: f32,
+    pub tr: f32,
+    pub br: f32,
+    pub bl: f32,
+}
+
+impl RectRadii {
+    /// The same `radius` for all four corners.
+    pub const fn all(radius: f32) -> RectRadii {
+        RectRadii {
+            tl: radius,
+            tr: radius,
+            br: radius,
+            bl: radius,
+        }
+    }
+}
+
+impl From<f32> for RectRadii {
+    fn from(radius: f32) -> RectRadii {
+        RectRadii::all(radius)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DrawRectangleRoundedParams {
+    /// Radius of each corner.
+    /// Radii bigger than half of the smaller rectangle side are clamped.
+    pub radii: RectRadii,
+
+    /// Amount of segments used for every corner
+    pub segments: u8,
+
+    pub color: Color,
+}
+
+impl Default for DrawRectangleRoundedParams {
+    fn default() -> Self {
+        Self {
+            radii: RectRadii::default(),
+            segments: 8,
+            color: Color::from_rgba(255, 255, 255, 255),
+        }
+    }
+}
+
+/// Draws a solid rectangle with rounded corners with its top-left corner at `[x, y]`
+/// with size `[w, h]`, corner `radius` and `color`.
+pub fn draw_rectangle_rounded(x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+    draw_rectangle_rounded_ex(
+        x,
+        y,
+        w,
+        h,
+        DrawRectangleRoundedParams {
+            radii: radius.into(),
+            color,
+            ..Default::default()
+        },
+    );
+}
+
+/// Draws a solid rectangle with rounded corners with its top-left corner at `[x, y]`
+/// with size `[w, h]`, with parameters.
+pub fn draw_rectangle_rounded_ex(
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    params: DrawRectangleRoundedParams,
+) {
+    if w <= 0. || h <= 0. {
+        return;
+    }
+
+    let context = get_context();
+    let outline = rounded_rectangle_path(x, y, w, h, params.radii, params.segments);
+    let color = params.color;
+
+    let mut vertices = Vec::<Vertex>::with_capacity(outline.len() + 1);
+    vertices.push(Vertex::new(x + w / 2., y + h / 2., 0., 0., 0., color));
+    vertices.extend(
+        outline
+            .iter()
+            .map(|p| Vertex::new(p.x, p.y, 0., 0., 0., color)),
+    );
+
+    let n = outline.len() as u16;
+    let mut indices = Vec::<u16>::with_capacity(outline.len() * 3);
+    for i in 0..n {
+        indices.extend([0, i + 1, (i + 1) % n + 1]);
+    }
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
+/// Draws an outline of a rectangle with rounded corners with its top-left corner at `[x, y]`
+/// with size `[w, h]`, corner `radius`, line `thickness` and `color`.
+/// The outline is drawn inside of the rectangle.
+pub fn draw_rectangle_rounded_lines(
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    radius: f32,
+    thickness: f32,
+    color: Color,
+) {
+    draw_rectangle_rounded_lines_ex(
+        x,
+        y,
+        w,
+        h,
+        thickness,
+        DrawRectangleRoundedParams {
+            radii: radius.into(),
+            color,
+            ..Default::default()
+        },
+    );
+}
+
+/// Draws an outline of a rectangle with rounded corners with its top-left corner at `[x, y]`
+/// with size `[w, h]` and line `thickness`, with parameters.
+/// The outline is drawn inside of the rectangle.
+pub fn draw_rectangle_rounded_lines_ex(
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    thickness: f32,
+    params: DrawRectangleRoundedParams,
+) {
+    if w <= 0. || h <= 0. || thickness <= 0. {
+        return;
+    }
+
+    let context = get_context();
+    let t = thickness.min(w.min(h) / 2.);
+    let radii = clamp_radii(params.radii, w, h);
+    let inner_radii = RectRadii {
+        tl: (radii.tl - t).max(0.),
+        tr: (radii.tr - t).max(0.),
+        br: (radii.br - t).max(0.),
+        bl: (radii.bl - t).max(0.),
+    };
+
+    // both paths have the same amount of points, so they can be stitched point by point
+    let outer = rounded_rectangle_path(x, y, w, h, radii, params.segments);
+    let inner = rounded_rectangle_path(
+        x + t,
+        y + t,
+        w - t * 2.,
+        h - t * 2.,
+        inner_radii,
+        params.segments,
+    );
+    let color = params.color;
+
+    let vertices: Vec<Vertex> = outer
+        .iter()
+        .chain(inner.iter())
+        .map(|p| Vertex::new(p.x, p.y, 0., 0., 0., color))
+        .collect();
+
+    let n = outer.len() as u16;
+    let mut indices = Vec::<u16>::with_capacity(outer.len() * 6);
+    for i in 0..n {
+        let next = (i + 1) % n;
+        indices.extend([i, next, i + n, i + n, next, next + n]);
+    }
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
+fn clamp_radii(radii: RectRadii, w: f32, h: f32) -> RectRadii {
+    let max = w.min(h).max(0.) / 2.;
+
+    RectRadii {
+        tl: radii.tl.clamp(0., max),
+        tr: radii.tr.clamp(0., max),
+        br: radii.br.clamp(0., max),
+        bl: radii.bl.clamp(0., max),
+    }
+}
+
+/// Clockwise outline of a rounded rectangle, always `segments + 1` points per corner.
+fn rounded_rectangle_path(
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    radii: RectRadii,
+    segments: u8,
+) -> Vec<Vec2> {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    let radii = clamp_radii(radii, w, h);
+    let segments = segments.max(1) as usize;
+
+    let corners = [
+        (vec2(x + radii.tl, y + radii.tl), radii.tl, PI),
+        (
+            vec2(x + w - radii.tr, y + radii.tr),
+            radii.tr,
+            PI + FRAC_PI_2,
+        ),
+        (vec2(x + w - radii.br, y + h - radii.br), radii.br, 0.),
+        (vec2(x + radii.bl, y + h - radii.bl), radii.bl, FRAC_PI_2),
+    ];
+
+    let mut points = Vec::with_capacity(4 * (segments + 1));
+    for (center, radius, start_angle) in corners {
+        for i in 0..=segments {
+            let angle = start_angle + FRAC_PI_2 * i as f32 / segments as f32;
+            points.push(center + radius * Vec2::from_angle(angle));
+        }
+    }
+
+    points
+}
+
 /// Draws an outlined solid hexagon centered at `[x, y]` with a radius `size`, outline thickness
 /// defined by `border`, orientation defined by `vertical` (when `true`, the hexagon points along
 /// the `y` axis), and colors for outline given by `border_color` and fill by `fill_color`.
@@ -256,6 +515,28 @@ pub fn draw_circle(x: f32, y: f32, r: f32, color: Color) {
     draw_poly(x, y, 20, r, 0., color);
 }
 
+/// Draws a solid circle centered at `[x, y]` with a given radius `r`, blending from
+/// the `inner` color in the center to the `outer` color at the edge.
+pub fn draw_circle_gradient(x: f32, y: f32, r: f32, inner: Color, outer: Color) {
+    const SIDES: u16 = 40;
+
+    let context = get_context();
+
+    let mut vertices = Vec::<Vertex>::with_capacity(SIDES as usize + 1);
+    let mut indices = Vec::<u16>::with_capacity(SIDES as usize * 3);
+
+    vertices.push(Vertex::new(x, y, 0., 0., 0., inner));
+    for i in 0..SIDES {
+        let (ry, rx) = (i as f32 / SIDES as f32 * std::f32::consts::TAU).sin_cos();
+        vertices.push(Vertex::new(x + r * rx, y + r * ry, 0., rx, ry, outer));
+        indices.extend_from_slice(&[0, i + 1, (i + 1) % SIDES + 1]);
+    }
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
 /// Draws a circle outline centered at `[x, y]` with a given radius, line `thickness` and `color`.
 ///
 /// This is not a perfect circle, but only a polygon approximation.
@@ -369,6 +650,454 @@ pub fn draw_line(x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Colo
     );
 }
 
+/// Draws a solid polygon with a given `color`.
+///
+/// `points` may describe any simple polygon, convex or concave, in either winding order.
+/// It is triangulated with ear clipping, self-intersecting polygons are not supported
+/// and may be drawn partially. Fewer than 3 points draw nothing.
+pub fn draw_polygon(points: &[Vec2], color: Color) {
+    let indices = triangulate_polygon(points);
+    if indices.is_empty() {
+        return;
+    }
+
+    let context = get_context();
+
+    let vertices: Vec<Vertex> = points
+        .iter()
+        .map(|p| Vertex::new(p.x, p.y, 0., 0., 0., color))
+        .collect();
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
+/// Ear clipping triangulation, returns indices into `points`.
+fn triangulate_polygon(points: &[Vec2]) -> Vec<u16> {
+    let n = points.len();
+    if n < 3 {
+        return vec![];
+    }
+
+    // twice the signed area, its sign tells the winding order
+    let area: f32 = (0..n)
+        .map(|i| points[i].perp_dot(points[(i + 1) % n]))
+        .sum();
+    if area.abs() < f32::EPSILON {
+        return vec![];
+    }
+    let winding = area.signum();
+
+    let mut remaining: Vec<usize> = (0..n).collect();
+    let mut indices = Vec::with_capacity((n - 2) * 3);
+
+    let corner = |remaining: &[usize], i: usize| {
+        let len = remaining.len();
+        (
+            remaining[(i + len - 1) % len],
+            remaining[i],
+            remaining[(i + 1) % len],
+        )
+    };
+
+    while remaining.len() > 3 {
+        let len = remaining.len();
+
+        let ear = (0..len).find(|&i| {
+            let (a, b, c) = corner(&remaining, i);
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            if (pb - pa).perp_dot(pc - pb) * winding <= 0. {
+                return false;
+            }
+
+            // no other vertex may be inside of the ear
+            remaining.iter().all(|&j| {
+                let p = points[j];
+                j == a
+                    || j == b
+                    || j == c
+                    || p == pa
+                    || p == pb
+                    || p == pc
+                    || !point_in_triangle(p, pa, pb, pc, winding)
+            })
+        });
+
+        match ear {
+            Some(i) => {
+                let (a, b, c) = corner(&remaining, i);
+                indices.extend([a as u16, b as u16, c as u16]);
+                remaining.remove(i);
+            }
+            None => {
+                // no ears left: either collinear points, which can be dropped,
+                // or a self-intersecting polygon, which can't be triangulated
+                let flat = (0..len).find(|&i| {
+                    let (a, b, c) = corner(&remaining, i);
+                    (points[b] - points[a])
+                        .perp_dot(points[c] - points[b])
+                        .abs()
+                        < f32::EPSILON
+                });
+                match flat {
+                    Some(i) => {
+                        remaining.remove(i);
+                    }
+                    None => return indices,
+                }
+            }
+        }
+    }
+
+    let (a, b, c) = (remaining[0], remaining[1], remaining[2]);
+    if (points[b] - points[a])
+        .perp_dot(points[c] - points[b])
+        .abs()
+        >= f32::EPSILON
+    {
+        indices.extend([a as u16, b as u16, c as u16]);
+    }
+
+    indices
+}
+
+fn point_in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2, winding: f32) -> bool {
+    (b - a).perp_dot(p - a) * winding >= 0.
+        && (c - b).perp_dot(p - b) * winding >= 0.
+        && (a - c).perp_dot(p - c) * winding >= 0.
+}
+
+/// How the segments of a thick polyline are connected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet.
+    /// Very sharp corners fall back to [LineJoin::Bevel] to avoid long spikes.
+    #[default]
+    Miter,
+    /// The outer corner is cut flat.
+    Bevel,
+    /// The outer corner is rounded.
+    Round,
+}
+
+/// Draws a path through `points` with a given line `thickness` and `color`.
+///
+/// Segments are connected with miter joins, so there are no gaps or overlaps at the corners.
+/// Very sharp corners fall back to bevel joins. The ends of the path are cut flat.
+pub fn draw_polyline(points: &[Vec2], thickness: f32, color: Color) {
+    draw_polyline_ex(points, thickness, color, LineJoin::Miter, false);
+}
+
+/// Draws a polygon outline with a given line `thickness` and `color`.
+///
+/// Same as [`draw_polyline`], but the last point is joined back to the first one.
+pub fn draw_polygon_lines(points: &[Vec2], thickness: f32, color: Color) {
+    draw_polyline_ex(points, thickness, color, LineJoin::Miter, true);
+}
+
+/// Draws a path through `points` with a given line `thickness`, `color` and `join` style.
+/// With `closed` the last point is joined back to the first one.
+pub fn draw_polyline_ex(
+    points: &[Vec2],
+    thickness: f32,
+    color: Color,
+    join: LineJoin,
+    closed: bool,
+) {
+    let (vertices, indices) = polyline_geometry(points, thickness, color, join, closed);
+    if indices.is_empty() {
+        return;
+    }
+
+    let context = get_context();
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
+/// Draws a cubic Bézier curve from `p0` to `p1` with control points `c0` and `c1`,
+/// with a given line `thickness` and `color`.
+///
+/// The curve is split into line segments based on how big it is on the screen,
+/// so it stays smooth when zoomed in with a camera.
+pub fn draw_bezier(p0: Vec2, c0: Vec2, c1: Vec2, p1: Vec2, thickness: f32, color: Color) {
+    draw_bezier_ex(p0, c0, c1, p1, thickness, color, None);
+}
+
+/// Same as [draw_bezier], with an optional fixed amount of line `segments`.
+/// With `None` the amount of segments is chosen automatically.
+pub fn draw_bezier_ex(
+    p0: Vec2,
+    c0: Vec2,
+    c1: Vec2,
+    p1: Vec2,
+    thickness: f32,
+    color: Color,
+    segments: Option<u16>,
+) {
+    let segments = segments
+        .unwrap_or_else(|| bezier_segments(p0, c0, c1, p1))
+        .max(1);
+
+    let points: Vec<Vec2> = (0..=segments)
+        .map(|i| {
+            let t = i as f32 / segments as f32;
+            let u = 1. - t;
+            p0 * (u * u * u) + c0 * (3. * u * u * t) + c1 * (3. * u * t * t) + p1 * (t * t * t)
+        })
+        .collect();
+
+    draw_polyline(&points, thickness, color);
+}
+
+/// Draws a quadratic Bézier curve from `p0` to `p1` with control point `c`,
+/// with a given line `thickness` and `color`.
+pub fn draw_bezier_quadratic(p0: Vec2, c: Vec2, p1: Vec2, thickness: f32, color: Color) {
+    // any quadratic curve is also a cubic one with these control points
+    let c0 = p0 + (c - p0) * (2. / 3.);
+    let c1 = p1 + (c - p1) * (2. / 3.);
+
+    draw_bezier(p0, c0, c1, p1, thickness, color);
+}
+
+/// Amount of segments keeping a cubic curve within a quarter of a pixel from its flattened
+/// version, with the Wang's formula.
+fn bezier_segments(p0: Vec2, c0: Vec2, c1: Vec2, p1: Vec2) -> u16 {
+    const TOLERANCE: f32 = 0.25;
+
+    let context = get_context();
+    let (_, _, width, height) = context.gl.get_viewport();
+    let projection = context.projection_matrix();
+    // how many pixels one world unit takes on the screen
+    let pixels_per_unit = vec2(
+        projection.x_axis.x * width as f32,
+        projection.x_axis.y * height as f32,
+    )
+    .length()
+    .max(
+        vec2(
+            projection.y_axis.x * width as f32,
+            projection.y_axis.y * height as f32,
+        )
+        .length(),
+    ) / 2.;
+
+    let m = (p0 - c0 * 2. + c1)
+        .length()
+        .max((c0 - c1 * 2. + p1).length())
+        * pixels_per_unit;
+    let segments = (0.75 * m / TOLERANCE).sqrt().ceil();
+
+    segments.clamp(1., 1024.) as u16
+}
+
+fn polyline_geometry(
+    points: &[Vec2],
+    thickness: f32,
+    color: Color,
+    join: LineJoin,
+    closed: bool,
+) -> (Vec<Vertex>, Vec<u16>) {
+    // miters longer than this many half-thicknesses are replaced with a bevel
+    const MITER_LIMIT: f32 = 4.0;
+    // max angle covered by one triangle of a round join
+    const ROUND_STEP: f32 = std::f32::consts::PI / 8.;
+
+    let mut points = points.to_vec();
+    points.dedup_by(|a, b| a.distance_squared(*b) < f32::EPSILON);
+    if closed
+        && points.len() > 2
+        && points[0].distance_squared(points[points.len() - 1]) < f32::EPSILON
+    {
+        points.pop();
+    }
+    // two points can't enclose anything, draw them as a single segment
+    let closed = closed && points.len() > 2;
+
+    if points.len() < 2 || thickness.is_nan() || thickness <= 0. {
+        return (vec![], vec![]);
+    }
+
+    let half = thickness * 0.5;
+    let n = points.len();
+    let segments = if closed { n } else { n - 1 };
+    let normals: Vec<Vec2> = (0..segments)
+        .map(|i| (points[(i + 1) % n] - points[i]).normalize().perp())
+        .collect();
+
+    let mut vertices = Vec::<Vertex>::with_capacity(segments * 4 + n * 3);
+    let mut indices = Vec::<u16>::with_capacity(segments * 6 + n * 3);
+
+    // offsets from the segment's end points to its left edge, the right edge is mirrored
+    let mut starts = vec![Vec2::ZERO; segments];
+    let mut ends = vec![Vec2::ZERO; segments];
+
+    for (i, &point) in points.iter().enumerate() {
+        let incoming = match i {
+            0 if closed => Some(segments - 1),
+            0 => None,
+            _ => Some(i - 1),
+        };
+        let outgoing = (i < segments).then_some(i);
+
+        match (incoming, outgoing) {
+            (Some(a), Some(b)) => {
+                let (na, nb) = (normals[a], normals[b]);
+                // almost straight joins look the same with any style
+                let straight = na.dot(nb) > 0.9999;
+                let miter = (na + nb)
+                    .try_normalize()
+                    .filter(|miter| miter.dot(na) * MITER_LIMIT > 1.)
+                    .filter(|_| join == LineJoin::Miter || straight);
+
+                if let Some(miter) = miter {
+                    let offset = miter * (half / miter.dot(na));
+                    ends[a] = offset;
+                    starts[b] = offset;
+                } else {
+                    ends[a] = na * half;
+                    starts[b] = nb * half;
+
+                    // fill the wedge on the outer side of the turn
+                    let side = if na.perp_dot(nb) > 0. { -half } else { half };
+                    // the outer side always turns towards the direction of travel,
+                    // this also picks the right way around for U-turns
+                    let angle = -side.signum() * na.dot(nb).clamp(-1., 1.).acos();
+                    let steps = match join {
+                        LineJoin::Round => (angle.abs() / ROUND_STEP).ceil().max(1.) as u16,
+                        _ => 1,
+                    };
+
+                    let base = vertices.len() as u16;
+                    vertices.push(Vertex::new(point.x, point.y, 0., 0., 0., color));
+                    for k in 0..=steps {
+                        let p = point
+                            + Vec2::from_angle(angle * k as f32 / steps as f32).rotate(na * side);
+                        vertices.push(Vertex::new(p.x, p.y, 0., 0., 0., color));
+                    }
+                    for k in 0..steps {
+                        indices.extend([base, base + 1 + k, base + 2 + k]);
+                    }
+                }
+            }
+            (None, Some(b)) => starts[b] = normals[b] * half,
+            (Some(a), None) => ends[a] = normals[a] * half,
+            (None, None) => unreachable!(),
+        }
+    }
+
+    for i in 0..segments {
+        let (p0, p1) = (points[i], points[(i + 1) % n]);
+        let base = vertices.len() as u16;
+        for p in [p0 + starts[i], p0 - starts[i], p1 + ends[i], p1 - ends[i]] {
+            vertices.push(Vertex::new(p.x, p.y, 0., 0., 0., color));
+        }
+        indices.extend([0, 1, 2, 2, 1, 3].map(|k| k + base));
+    }
+
+    (vertices, indices)
+}
+
+#[test]
+fn polygon_triangulation() {
+    let area = |points: &[Vec2], indices: &[u16]| -> f32 {
+        indices
+            .chunks(3)
+            .map(|t| {
+                let (a, b, c) = (
+                    points[t[0] as usize],
+                    points[t[1] as usize],
+                    points[t[2] as usize],
+                );
+                (b - a).perp_dot(c - a).abs() / 2.
+            })
+            .sum()
+    };
+
+    // concave "L" shape in both winding orders
+    let mut l_shape = vec![
+        vec2(0., 0.),
+        vec2(2., 0.),
+        vec2(2., 1.),
+        vec2(1., 1.),
+        vec2(1., 2.),
+        vec2(0., 2.),
+    ];
+    for _ in 0..2 {
+        let indices = triangulate_polygon(&l_shape);
+        assert_eq!(indices.len(), 4 * 3);
+        assert!((area(&l_shape, &indices) - 3.).abs() < 0.001);
+        l_shape.reverse();
+    }
+
+    // a point in the middle of an edge
+    let square = [
+        vec2(0., 0.),
+        vec2(1., 0.),
+        vec2(2., 0.),
+        vec2(2., 2.),
+        vec2(0., 2.),
+    ];
+    assert!((area(&square, &triangulate_polygon(&square)) - 4.).abs() < 0.001);
+
+    assert!(triangulate_polygon(&square[..2]).is_empty());
+    assert!(triangulate_polygon(&[vec2(0., 0.), vec2(1., 1.), vec2(2., 2.)]).is_empty());
+}
+
+#[test]
+fn polyline_degenerate_input() {
+    use crate::color::WHITE;
+
+    let square = [vec2(0., 0.), vec2(10., 0.), vec2(10., 10.), vec2(0., 10.)];
+
+    assert!(polyline_geometry(&[], 2., WHITE, LineJoin::Miter, false)
+        .1
+        .is_empty());
+    assert!(
+        polyline_geometry(&square[..1], 2., WHITE, LineJoin::Miter, false)
+            .1
+            .is_empty()
+    );
+    assert!(
+        polyline_geometry(&[square[0]; 4], 2., WHITE, LineJoin::Miter, true)
+            .1
+            .is_empty()
+    );
+    assert!(polyline_geometry(&square, 0., WHITE, LineJoin::Miter, true)
+        .1
+        .is_empty());
+
+    // straight joins need no extra geometry, a U-turn gets a (zero area) bevel
+    let collinear = [vec2(0., 0.), vec2(5., 0.), vec2(10., 0.), vec2(0., 0.)];
+    let (vertices, indices) = polyline_geometry(&collinear, 2., WHITE, LineJoin::Miter, false);
+    assert_eq!(vertices.len(), 3 * 4 + 3);
+    assert_eq!(indices.len(), 3 * 6 + 3);
+    assert!(vertices.iter().all(|v| v.position.is_finite()));
+
+    // a closed square has 4 mitered corners, one of them shared by the first and last points
+    let (vertices, _) = polyline_geometry(&square, 2., WHITE, LineJoin::Miter, true);
+    assert_eq!(vertices.len(), 4 * 4);
+    assert!(vertices
+        .iter()
+        .any(|v| v.position.truncate().distance(vec2(-1., -1.)) < 0.001));
+
+    // bevel and round joins fill the corners with one and four triangles
+    let (vertices, indices) = polyline_geometry(&square, 2., WHITE, LineJoin::Bevel, true);
+    assert_eq!(
+        (vertices.len(), indices.len()),
+        (4 * 4 + 4 * 3, 4 * 6 + 4 * 3)
+    );
+    let (vertices, indices) = polyline_geometry(&square, 2., WHITE, LineJoin::Round, true);
+    assert_eq!(
+        (vertices.len(), indices.len()),
+        (4 * 4 + 4 * 6, 4 * 6 + 4 * 12)
+    );
+    assert!(vertices
+        .iter()
+        .all(|v| v.position.truncate().distance(vec2(5., 5.)) < 5. * 2f32.sqrt() + 1.01));
+}
+
 /// Parameters for drawing an arc
 #[derive(Debug, Clone, Copy)]
 pub struct ArcParams {
@@ -455,3 +1184,15 @@ pub fn draw_arc(
         color,
     );
 }
+
+#[test]
+fn rounded_rectangle_radius_clamp() {
+    let path = rounded_rectangle_path(0., 0., 100., 20., RectRadii::all(50.), 4);
+
+    assert_eq!(path.len(), 4 * 5);
+    assert!(path
+        .iter()
+        .all(|p| (-0.001..=100.001).contains(&p.x) && (-0.001..=20.001).contains(&p.y)));
+    // clamped to a 10px radius the left side becomes a half circle
+    assert!(path.iter().any(|p| p.distance(vec2(0., 10.)) < 0.001));
+}
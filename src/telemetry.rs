@@ -126,7 +126,7 @@ pub(crate) fn reset() {
         "New frame started with unpaired begin/end zones."
     );
 
-    profiler.frame.full_frame_time = crate::time::get_frame_time();
+    profiler.frame.full_frame_time = crate::time::get_unscaled_frame_time();
 
     std::mem::swap(&mut profiler.prev_frame, &mut profiler.frame);
     profiler.frame = Frame::new();
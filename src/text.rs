@@ -5,16 +5,18 @@ use std::collections::HashMap;
 use crate::{
     color::Color,
     get_context, get_quad_context,
+    material::{gl_use_material, Material},
     math::{vec3, Rect},
     texture::{Image, TextureHandle},
     Error,
 };
 
-use crate::color::WHITE;
+use crate::color::{BLACK, WHITE};
 use glam::vec2;
 
 use std::sync::{Arc, Mutex};
 pub(crate) mod atlas;
+mod sdf;
 
 use atlas::{Atlas, SpriteKey};
 
@@ -32,6 +34,40 @@ pub struct Font {
     font: Arc<fontdue::Font>,
     atlas: Arc<Mutex<Atlas>>,
     characters: Arc<Mutex<HashMap<(char, u16), CharacterInfo>>>,
+    fallbacks: Arc<Mutex<Vec<Font>>>,
+    /// Rasterized size and spread of the distance field glyphs, see [FontParams].
+    sdf: Option<(u16, u16)>,
+}
+
+/// Font loading options for [load_ttf_font_from_bytes_ex].
+#[derive(Debug, Clone, Copy)]
+pub struct FontParams {
+    /// Rasterize the glyphs as signed distance fields, once at `sdf_size`,
+    /// and draw them with a thresholding shader.
+    /// The text stays sharp at any size and camera zoom,
+    /// but small text is not as pixel-exact as the bitmap glyphs.
+    ///
+    /// Distance field fonts are meant for the `draw_text` functions,
+    /// the UI draws fonts as bitmaps.
+    /// Default is false.
+    pub sdf: bool,
+    /// Pixel size the distance field glyphs are rasterized at.
+    /// Default is 48.
+    pub sdf_size: u16,
+    /// How far from the glyph edge the distance field goes, in pixels of `sdf_size`.
+    /// Limits the widest outline, see [TextParams::outline].
+    /// Default is 8.
+    pub sdf_spread: u16,
+}
+
+impl Default for FontParams {
+    fn default() -> FontParams {
+        FontParams {
+            sdf: false,
+            sdf_size: 48,
+            sdf_spread: 8,
+        }
+    }
 }
 
 /// World space dimensions of the text, measured by "measure_text" function
@@ -69,6 +105,8 @@ impl Font {
                 fontdue::FontSettings::default(),
             )?),
             characters: Arc::new(Mutex::new(HashMap::new())),
+            fallbacks: Arc::new(Mutex::new(vec![])),
+            sdf: None,
             atlas,
         })
     }
@@ -100,15 +138,30 @@ impl Font {
             return;
         }
 
-        let (metrics, bitmap) = self.font.rasterize(character, size as f32);
-
-        let (width, height) = (metrics.width as u16, metrics.height as u16);
+        // the glyph is cached in this font's atlas even when it comes from a fallback
+        let face = self.face_for(character);
+        let (metrics, bitmap) = face.rasterize(character, size as f32);
+
+        let (mut width, mut height) = (metrics.width as u16, metrics.height as u16);
+        let (mut offset_x, mut offset_y) = (metrics.xmin, metrics.ymin);
+
+        let alpha = match self.sdf {
+            Some((_, spread)) => {
+                // the distance field grows the glyph by the spread on each side
+                width += spread * 2;
+                height += spread * 2;
+                offset_x -= spread as i32;
+                offset_y -= spread as i32;
+                sdf::distance_field(&bitmap, metrics.width, metrics.height, spread as usize)
+            }
+            None => bitmap,
+        };
 
         let sprite = self.atlas.lock().unwrap().new_unique_id();
         self.atlas.lock().unwrap().cache_sprite(
             sprite,
             Image {
-                bytes: bitmap
+                bytes: alpha
                     .iter()
                     .flat_map(|coverage| vec![255, 255, 255, *coverage])
                     .collect(),
@@ -118,8 +171,6 @@ impl Font {
         );
         let advance = metrics.advance_width;
 
-        let (offset_x, offset_y) = (metrics.xmin, metrics.ymin);
-
         let character_info = CharacterInfo {
             advance,
             offset_x,
@@ -133,6 +184,21 @@ impl Font {
             .insert((character, size), character_info);
     }
 
+    /// The first font in the fallback chain that has the character,
+    /// or this font if none of them do.
+    fn face_for(&self, character: char) -> Arc<fontdue::Font> {
+        if self.font.lookup_glyph_index(character) != 0 {
+            return self.font.clone();
+        }
+
+        self.fallbacks
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|fallback| fallback.font.lookup_glyph_index(character) != 0)
+            .map_or_else(|| self.font.clone(), |fallback| fallback.font.clone())
+    }
+
     pub(crate) fn get(&self, character: char, size: u16) -> Option<CharacterInfo> {
         self.characters
             .lock()
@@ -148,6 +214,20 @@ impl Font {
             .contains_key(&(character, size))
     }
 
+    /// The size glyphs for `font_size` pixels are cached at,
+    /// and the scale to draw them with.
+    fn raster_size(&self, font_size: f32) -> (u16, f32) {
+        match self.sdf {
+            Some((size, _)) => (size, font_size / size as f32),
+            None => (font_size.ceil() as u16, 1.),
+        }
+    }
+
+    /// Empty space around the glyph in its atlas rect, in rasterized pixels.
+    fn glyph_padding(&self) -> f32 {
+        self.sdf.map_or(0., |(_, spread)| spread as f32)
+    }
+
     pub(crate) fn measure_text(
         &self,
         text: impl AsRef<str>,
@@ -158,8 +238,11 @@ impl Font {
     ) -> TextDimensions {
         let text = text.as_ref();
 
-        let dpi_scaling = miniquad::window::dpi_scale();
-        let font_size = (font_size as f32 * dpi_scaling).ceil() as u16;
+        let dpi_scaling = crate::window::dpi_scale();
+        let (font_size, raster_scale) = self.raster_size(font_size as f32 * dpi_scaling);
+        let font_scale_x = font_scale_x * raster_scale;
+        let font_scale_y = font_scale_y * raster_scale;
+        let padding = self.glyph_padding();
 
         let mut width = 0.0;
         let mut min_y = f32::MAX;
@@ -171,15 +254,16 @@ impl Font {
             }
 
             let font_data = &self.characters.lock().unwrap()[&(character, font_size)];
-            let offset_y = font_data.offset_y as f32 * font_scale_y;
+            let offset_y = (font_data.offset_y as f32 + padding) * font_scale_y;
 
             let atlas = self.atlas.lock().unwrap();
             let glyph = atlas.get(font_data.sprite).unwrap().rect;
             let advance = font_data.advance * font_scale_x;
-            glyph_callback(advance);
+            // same units as the returned dimensions
+            glyph_callback(advance / dpi_scaling);
             width += advance;
             min_y = min_y.min(offset_y);
-            max_y = max_y.max(glyph.h * font_scale_y + offset_y);
+            max_y = max_y.max((glyph.h - padding * 2.) * font_scale_y + offset_y);
         }
 
         TextDimensions {
@@ -204,11 +288,23 @@ impl Font {
     }
 
     pub fn populate_font_cache(&self, characters: &[char], size: u16) {
+        // distance field glyphs are cached once for all the sizes
+        let (size, _) = self.raster_size(size as f32);
         for character in characters {
             self.cache_glyph(*character, size);
         }
     }
 
+    /// Caches all the characters of `text` for each of the `sizes`, to not do it mid-game.
+    ///
+    /// Sizes are the rasterized sizes, `font_size` multiplied by [dpi_scale](crate::window::dpi_scale).
+    pub fn populate_font_cache_sizes(&self, text: &str, sizes: impl IntoIterator<Item = u16>) {
+        let characters: Vec<char> = text.chars().collect();
+        for size in sizes {
+            self.populate_font_cache(&characters, size);
+        }
+    }
+
     /// Sets the [FilterMode](https://docs.rs/miniquad/latest/miniquad/graphics/enum.FilterMode.html#) of this font's texture atlas.
     ///
     /// Use Nearest if you need integer-ratio scaling for pixel art, for example.
@@ -226,6 +322,56 @@ impl Font {
         self.atlas.lock().unwrap().set_filter(filter_mode);
     }
 
+    /// Fonts to take the glyphs missing in this font from, tried in order.
+    ///
+    /// Only the fallbacks' own glyphs are used, their fallbacks are not searched.
+    /// The change is shared by all the clones of this font.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use macroquad::prelude::*;
+    /// # async fn f() -> Result<(), macroquad::Error> {
+    /// let mut font = load_ttf_font("latin.ttf").await?;
+    /// let cjk = load_ttf_font("cjk.ttf").await?;
+    /// font.set_fallbacks(&[cjk]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_fallbacks(&mut self, fallbacks: &[Font]) {
+        *self.fallbacks.lock().unwrap() = fallbacks.to_vec();
+        self.forget_missing_glyphs();
+    }
+
+    /// Adds a font to the end of the fallback chain, see [Font::set_fallbacks].
+    ///
+    /// Line metrics always come from this font, fallback glyphs are rasterized
+    /// at the same pixel size as the font's own ones.
+    ///
+    /// ```no_run
+    /// # use macroquad::prelude::*;
+    /// # async fn f() -> Result<(), macroquad::Error> {
+    /// let font = load_ttf_font("display.ttf")
+    ///     .await?
+    ///     .with_fallback(load_ttf_font("cjk.ttf").await?)
+    ///     .with_fallback(load_ttf_font("emoji.ttf").await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_fallback(self, fallback: Font) -> Font {
+        self.fallbacks.lock().unwrap().push(fallback);
+        self.forget_missing_glyphs();
+        self
+    }
+
+    /// Drops the cached glyphs that may now come from a different font.
+    fn forget_missing_glyphs(&self) {
+        let font = &self.font;
+        self.characters
+            .lock()
+            .unwrap()
+            .retain(|&(character, _), _| font.lookup_glyph_index(character) != 0);
+    }
+
     // pub fn texture(&self) -> Texture2D {
     //     let font = get_context().fonts_storage.get_font(*self);
 
@@ -256,6 +402,16 @@ pub struct TextParams<'a> {
     /// Default is 0.0
     pub rotation: f32,
     pub color: Color,
+    /// Outline width for distance field fonts, from 0.0 to 0.5 where 0.5 is
+    /// the font's whole `sdf_spread`. Ignored for bitmap fonts.
+    /// Default is 0.0
+    pub outline: f32,
+    /// Default is BLACK
+    pub outline_color: Color,
+    /// Extra blur of the distance field glyph edges, 0.0 is the sharpest.
+    /// Ignored for bitmap fonts.
+    /// Default is 0.0
+    pub softness: f32,
 }
 
 impl<'a> Default for TextParams<'a> {
@@ -267,6 +423,9 @@ impl<'a> Default for TextParams<'a> {
             font_scale_aspect: 1.0,
             color: WHITE,
             rotation: 0.0,
+            outline: 0.0,
+            outline_color: BLACK,
+            softness: 0.0,
         }
     }
 }
@@ -285,19 +444,35 @@ pub async fn load_ttf_font(path: &str) -> Result<Font, Error> {
 /// let font = load_ttf_font_from_bytes(include_bytes!("font.ttf"));
 /// ```
 pub fn load_ttf_font_from_bytes(bytes: &[u8]) -> Result<Font, Error> {
-    let atlas = Arc::new(Mutex::new(Atlas::new(
-        get_quad_context(),
-        miniquad::FilterMode::Linear,
-    )));
+    load_ttf_font_from_bytes_ex(bytes, FontParams::default())
+}
+
+/// Load font from bytes array with the given [FontParams].
+/// ```ignore
+/// let font = load_ttf_font_from_bytes_ex(
+///     include_bytes!("font.ttf"),
+///     FontParams {
+///         sdf: true,
+///         ..Default::default()
+///     },
+/// );
+/// ```
+pub fn load_ttf_font_from_bytes_ex(bytes: &[u8], params: FontParams) -> Result<Font, Error> {
+    // distance fields are only smooth between the texels with linear filtering
+    let filter = if params.sdf {
+        miniquad::FilterMode::Linear
+    } else {
+        get_context().default_filter_mode
+    };
+    let atlas = Arc::new(Mutex::new(Atlas::new(get_quad_context(), filter)));
 
     let mut font = Font::load_from_bytes(atlas.clone(), bytes)?;
+    if params.sdf {
+        font.sdf = Some((params.sdf_size, params.sdf_spread.max(1)));
+    }
 
     font.populate_font_cache(&Font::ascii_character_list(), 15);
 
-    let ctx = get_context();
-
-    font.set_filter(ctx.default_filter_mode);
-
     Ok(font)
 }
 
@@ -336,12 +511,26 @@ pub fn draw_text_ex(text: impl AsRef<str>, x: f32, y: f32, params: TextParams) -
         .font
         .unwrap_or(&get_context().fonts_storage.default_font);
 
-    let dpi_scaling = miniquad::window::dpi_scale();
+    let dpi_scaling = crate::window::dpi_scale();
 
     let rot = params.rotation;
-    let font_scale_x = params.font_scale * params.font_scale_aspect;
-    let font_scale_y = params.font_scale;
-    let font_size = (params.font_size as f32 * dpi_scaling).ceil() as u16;
+    let (font_size, raster_scale) = font.raster_size(params.font_size as f32 * dpi_scaling);
+    let font_scale_x = params.font_scale * params.font_scale_aspect * raster_scale;
+    let font_scale_y = params.font_scale * raster_scale;
+    let padding = font.glyph_padding() * font_scale_y;
+
+    let previous_pipeline = get_context().gl.get_active_pipeline();
+    if font.sdf.is_some() {
+        let material = get_context()
+            .fonts_storage
+            .sdf_material
+            .get_or_insert_with(sdf::material)
+            .clone();
+        gl_use_material(&material);
+        material.set_uniform("OutlineColor", params.outline_color.to_vec());
+        material.set_uniform("Outline", params.outline.clamp(0., 0.5));
+        material.set_uniform("Softness", params.softness.max(0.));
+    }
 
     let mut total_width = 0.0;
     let mut max_offset_y = f32::MIN;
@@ -360,8 +549,8 @@ pub fn draw_text_ex(text: impl AsRef<str>, x: f32, y: f32, params: TextParams) -
         let glyph = atlas.get(char_data.sprite).unwrap().rect;
         let glyph_scaled_h = glyph.h * font_scale_y;
 
-        min_offset_y = min_offset_y.min(offset_y);
-        max_offset_y = max_offset_y.max(glyph_scaled_h + offset_y);
+        min_offset_y = min_offset_y.min(offset_y + padding);
+        max_offset_y = max_offset_y.max(glyph_scaled_h + offset_y - padding);
 
         let rot_cos = rot.cos();
         let rot_sin = rot.sin();
@@ -394,6 +583,8 @@ pub fn draw_text_ex(text: impl AsRef<str>, x: f32, y: f32, params: TextParams) -
         );
     }
 
+    get_context().gl.pipeline(previous_pipeline);
+
     TextDimensions {
         width: total_width / dpi_scaling,
         height: (max_offset_y - min_offset_y) / dpi_scaling,
@@ -497,6 +688,21 @@ pub fn measure_text(
     font.measure_text(text, font_size, font_scale, font_scale, |_| {})
 }
 
+/// Dimensions of each line of the text, the lines are split on `'\n'`.
+/// The total size is given by [measure_multiline_text].
+pub fn measure_text_lines(
+    text: &str,
+    font: Option<&Font>,
+    font_size: u16,
+    font_scale: f32,
+) -> Vec<TextDimensions> {
+    let font = font.unwrap_or_else(|| &get_context().fonts_storage.default_font);
+
+    text.split('\n')
+        .map(|line| font.measure_text(line, font_size, font_scale, font_scale, |_| {}))
+        .collect()
+}
+
 pub fn measure_multiline_text(
     text: &str,
     font: Option<&Font>,
@@ -599,16 +805,109 @@ pub fn wrap_text(
     new_text
 }
 
+/// Horizontal alignment of the lines in [draw_text_wrapped].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Draws the text word wrapped to the width of `rect`, starting from its top.
+///
+/// Honors `'\n'`, words too long for a line are split. The text is not clipped and
+/// may go past the bottom of `rect`, use [measure_multiline_text] on the [wrap_text]
+/// result to find out the height beforehand. `params.rotation` is ignored.
+/// Returns the size of the drawn text.
+pub fn draw_text_wrapped(
+    text: &str,
+    rect: Rect,
+    align: TextAlign,
+    params: TextParams,
+) -> TextDimensions {
+    let font = params
+        .font
+        .unwrap_or(&get_context().fonts_storage.default_font);
+
+    let (ascent, line_distance) = match font.font.horizontal_line_metrics(1.0) {
+        Some(metrics) => (metrics.ascent, metrics.new_line_size),
+        None => (1.0, 1.0),
+    };
+    let size = params.font_size as f32 * params.font_scale;
+
+    let wrapped = wrap_text(
+        text,
+        Some(font),
+        params.font_size,
+        params.font_scale,
+        rect.w,
+    );
+
+    let mut dimensions = TextDimensions::default();
+    let mut y = rect.y + ascent * size;
+    for line in wrapped.split('\n') {
+        // the whitespace the line was broken at should not shift the alignment
+        let line = line.trim_end();
+        let width = measure_text(line, Some(font), params.font_size, params.font_scale).width;
+        let x = match align {
+            TextAlign::Left => rect.x,
+            TextAlign::Center => rect.x + (rect.w - width) / 2.,
+            TextAlign::Right => rect.x + rect.w - width,
+        };
+
+        let line_dimensions = draw_text_ex(
+            line,
+            x,
+            y,
+            TextParams {
+                rotation: 0.,
+                ..params.clone()
+            },
+        );
+        y += line_distance * size;
+
+        dimensions.width = dimensions.width.max(line_dimensions.width);
+        dimensions.height += line_distance * size;
+        if dimensions.offset_y == 0.0 {
+            dimensions.offset_y = line_dimensions.offset_y;
+        }
+    }
+
+    dimensions
+}
+
+/// Same as [wrap_text], but returns each line separately.
+///
+/// An empty text gives a single empty line.
+/// The lines may be drawn with [draw_multiline_text] after joining them with `'\n'`.
+pub fn wrap_text_lines(
+    text: &str,
+    font: Option<&Font>,
+    font_size: u16,
+    max_width: f32,
+) -> Vec<String> {
+    wrap_text(text, font, font_size, 1.0, max_width)
+        .split('\n')
+        .map(String::from)
+        .collect()
+}
+
 pub(crate) struct FontsStorage {
     default_font: Font,
+    sdf_material: Option<Material>,
 }
 
 impl FontsStorage {
     pub(crate) fn new(ctx: &mut dyn miniquad::RenderingBackend) -> FontsStorage {
-        let atlas = Arc::new(Mutex::new(Atlas::new(ctx, miniquad::FilterMode::Linear)));
+        // the built in font is a pixel font, it stays sharp with any default filter
+        let atlas = Arc::new(Mutex::new(Atlas::new(ctx, miniquad::FilterMode::Nearest)));
 
         let default_font = Font::load_from_bytes(atlas, include_bytes!("ProggyClean.ttf")).unwrap();
-        FontsStorage { default_font }
+        FontsStorage {
+            default_font,
+            sdf_material: None,
+        }
     }
 }
 
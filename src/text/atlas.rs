@@ -46,11 +46,7 @@ impl Atlas {
     pub fn new(ctx: &mut dyn miniquad::RenderingBackend, filter: miniquad::FilterMode) -> Atlas {
         let image = Image::gen_image_color(512, 512, Color::new(0.0, 0.0, 0.0, 0.0));
         let texture = ctx.new_texture_from_rgba8(image.width, image.height, &image.bytes);
-        ctx.texture_set_filter(
-            texture,
-            miniquad::FilterMode::Nearest,
-            miniquad::MipmapFilterMode::None,
-        );
+        ctx.texture_set_filter(texture, filter, miniquad::MipmapFilterMode::None);
 
         Atlas {
             image,
@@ -95,7 +91,8 @@ impl Atlas {
             self.dirty = false;
             let (texture_width, texture_height) = ctx.texture_size(self.texture);
             if texture_width != self.image.width as _ || texture_height != self.image.height as _ {
-                ctx.delete_texture(self.texture);
+                // glyphs drawn earlier this frame still point to the old texture
+                crate::get_context().textures.retire(self.texture);
 
                 self.texture = ctx.new_texture_from_rgba8(
                     self.image.width,
@@ -125,71 +122,107 @@ impl Atlas {
         })
     }
 
-    pub fn cache_sprite(&mut self, key: SpriteKey, sprite: Image) {
-        let (width, height) = (sprite.width as usize, sprite.height as usize);
+    /// Finds a place for a `width`x`height` sprite and moves the cursor past it.
+    fn allocate(&mut self, width: u16, height: u16) -> Option<(u16, u16)> {
+        let (atlas_width, atlas_height) = (self.image.width as u32, self.image.height as u32);
+        let (width, height) = (width as u32, height as u32);
+        let gap = Self::GAP as u32;
+
+        let (mut cursor_x, mut cursor_y) = (self.cursor_x as u32, self.cursor_y as u32);
+        let mut line_height = self.max_line_height as u32;
+        if cursor_x + gap + width > atlas_width {
+            cursor_x = 0;
+            cursor_y += line_height + gap * 2;
+            line_height = 0;
+        }
+
+        let (x, y) = (cursor_x + gap, cursor_y);
+        if x + width > atlas_width || y + height > atlas_height {
+            return None;
+        }
 
-        let x = if self.cursor_x + (width as u16) < self.image.width {
-            if height as u16 > self.max_line_height {
-                self.max_line_height = height as u16;
+        self.cursor_x = (x + width + gap) as u16;
+        self.cursor_y = y as u16;
+        self.max_line_height = line_height.max(height) as u16;
+
+        Some((x as u16, y as u16))
+    }
+
+    pub fn cache_sprite(&mut self, key: SpriteKey, sprite: Image) {
+        let (x, y) = match self.allocate(sprite.width, sprite.height) {
+            Some(position) => position,
+            None if self.grow() => return self.cache_sprite(key, sprite),
+            None => {
+                crate::logging::warn!(
+                    "Atlas is full at {}x{}, a {}x{} sprite is skipped",
+                    self.image.width,
+                    self.image.height,
+                    sprite.width,
+                    sprite.height
+                );
+                // keep the key valid, the sprite is just not drawn
+                self.sprites.insert(
+                    key,
+                    Sprite {
+                        rect: Rect::new(0., 0., 0., 0.),
+                    },
+                );
+                return;
             }
-            let res = self.cursor_x + Self::GAP;
-            self.cursor_x += width as u16 + Self::GAP * 2;
-            res
-        } else {
-            self.cursor_y += self.max_line_height + Self::GAP * 2;
-            self.cursor_x = width as u16 + Self::GAP;
-            self.max_line_height = height as u16;
-            Self::GAP
         };
-        let y = self.cursor_y;
-
-        // texture bounds exceeded
-        if y + sprite.height > self.image.height || x + sprite.width > self.image.width {
-            // reset glyph cache state
-            let sprites = self.sprites.drain().collect::<Vec<_>>();
-            self.cursor_x = 0;
-            self.cursor_y = 0;
-            self.max_line_height = 0;
-
-            let old_image = self.image.clone();
-
-            // increase font texture size
-            // note: if we tried to fit gigantic texture into a small atlas,
-            // new_width will still be not enough. But its fine, it will
-            // be regenerated on the recursion call.
-            let new_width = self.image.width * 2;
-            let new_height = self.image.height * 2;
-
-            self.image =
-                Image::gen_image_color(new_width, new_height, Color::new(0.0, 0.0, 0.0, 0.0));
-
-            // recache all previously cached symbols
-            for (key, sprite) in sprites {
-                let image = old_image.sub_image(sprite.rect);
-                self.cache_sprite(key, image);
-            }
 
-            // cache the new sprite
-            self.cache_sprite(key, sprite);
-        } else {
-            self.dirty = true;
-
-            for j in 0..height {
-                for i in 0..width {
-                    self.image.set_pixel(
-                        x as u32 + i as u32,
-                        y as u32 + j as u32,
-                        sprite.get_pixel(i as u32, j as u32),
-                    );
-                }
+        self.dirty = true;
+
+        let (width, height) = (sprite.width as usize, sprite.height as usize);
+        for j in 0..height {
+            for i in 0..width {
+                self.image.set_pixel(
+                    x as u32 + i as u32,
+                    y as u32 + j as u32,
+                    sprite.get_pixel(i as u32, j as u32),
+                );
             }
+        }
+
+        self.sprites.insert(
+            key,
+            Sprite {
+                rect: Rect::new(x as f32, y as f32, width as f32, height as f32),
+            },
+        );
+    }
 
-            self.sprites.insert(
-                key,
-                Sprite {
-                    rect: Rect::new(x as f32, y as f32, width as f32, height as f32),
-                },
-            );
+    /// Doubles the atlas size and re-packs all the sprites.
+    /// Returns false if the atlas is already as big as the GPU allows.
+    fn grow(&mut self) -> bool {
+        let new_size = self.image.width as u32 * 2;
+        if new_size > crate::texture::max_texture_size().min(u16::MAX as u32) {
+            return false;
         }
+
+        let sprites = self.sprites.drain().collect::<Vec<_>>();
+        self.cursor_x = 0;
+        self.cursor_y = 0;
+        self.max_line_height = 0;
+
+        let old_image = std::mem::replace(
+            &mut self.image,
+            Image::gen_image_color(
+                new_size as u16,
+                new_size as u16,
+                Color::new(0.0, 0.0, 0.0, 0.0),
+            ),
+        );
+
+        // tallest first, packs a bit tighter
+        let mut sprites = sprites;
+        sprites.sort_by(|a, b| b.1.rect.h.total_cmp(&a.1.rect.h));
+        for (key, sprite) in sprites {
+            let image = old_image.sub_image(sprite.rect);
+            self.cache_sprite(key, image);
+        }
+        self.dirty = true;
+
+        true
     }
 }
@@ -0,0 +1,280 @@
+//! Signed distance field glyphs, rendered once and drawn sharp at any scale.
+
+use crate::{
+    material::{load_material, Material, MaterialParams},
+    window::miniquad::*,
+};
+
+/// Converts a glyph coverage bitmap into a distance field, padded by `spread` pixels.
+///
+/// 0.5 (128) is the glyph edge, the value grows inside and drops to 0
+/// `spread` pixels outside of the glyph.
+pub(crate) fn distance_field(
+    coverage: &[u8],
+    width: usize,
+    height: usize,
+    spread: usize,
+) -> Vec<u8> {
+    let (w, h) = (width + spread * 2, height + spread * 2);
+
+    let inside = |x: usize, y: usize| {
+        x >= spread
+            && y >= spread
+            && x < width + spread
+            && y < height + spread
+            && coverage[(y - spread) * width + x - spread] >= 128
+    };
+
+    // squared distances to the nearest pixel outside and inside of the glyph
+    let mut to_outside = vec![0.; w * h];
+    let mut to_inside = vec![0.; w * h];
+    for y in 0..h {
+        for x in 0..w {
+            let i = y * w + x;
+            if inside(x, y) {
+                to_outside[i] = f32::INFINITY;
+            } else {
+                to_inside[i] = f32::INFINITY;
+            }
+        }
+    }
+    distance_transform(&mut to_outside, w, h);
+    distance_transform(&mut to_inside, w, h);
+
+    to_outside
+        .iter()
+        .zip(&to_inside)
+        .map(|(&to_outside, &to_inside)| {
+            // distance from the pixel center to the edge, positive inside
+            let distance = if to_inside == 0. {
+                to_outside.sqrt() - 0.5
+            } else {
+                0.5 - to_inside.sqrt()
+            };
+            let value = 0.5 + distance / (spread as f32 * 2.);
+            (value.clamp(0., 1.) * 255.) as u8
+        })
+        .collect()
+}
+
+/// In place squared euclidean distance transform, Felzenszwalb & Huttenlocher.
+/// Zeros are the sources, infinities get the squared distance to the nearest source.
+fn distance_transform(grid: &mut [f32], width: usize, height: usize) {
+    let size = width.max(height);
+    let mut f = vec![0.; size];
+    let mut d = vec![0.; size];
+    let mut v = vec![0; size];
+    let mut z = vec![0.; size + 1];
+
+    for x in 0..width {
+        for y in 0..height {
+            f[y] = grid[y * width + x];
+        }
+        distance_transform_1d(&f[..height], &mut d, &mut v, &mut z);
+        for y in 0..height {
+            grid[y * width + x] = d[y];
+        }
+    }
+    for y in 0..height {
+        f[..width].copy_from_slice(&grid[y * width..(y + 1) * width]);
+        distance_transform_1d(&f[..width], &mut d, &mut v, &mut z);
+        grid[y * width..(y + 1) * width].copy_from_slice(&d[..width]);
+    }
+}
+
+fn distance_transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    let n = f.len();
+    let Some(first) = (0..n).find(|&q| f[q].is_finite()) else {
+        d[..n].fill(f32::INFINITY);
+        return;
+    };
+
+    // lower envelope of the parabolas rooted at the finite samples
+    let mut k = 0;
+    v[0] = first;
+    z[0] = f32::NEG_INFINITY;
+    z[1] = f32::INFINITY;
+    for q in first + 1..n {
+        if !f[q].is_finite() {
+            continue;
+        }
+        loop {
+            let p = v[k];
+            let s = ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2 * (q - p)) as f32;
+            if s <= z[k] && k > 0 {
+                k -= 1;
+                continue;
+            }
+            if s <= z[k] {
+                // k == 0, the new parabola takes over the whole envelope
+                v[0] = q;
+                z[1] = f32::INFINITY;
+            } else {
+                k += 1;
+                v[k] = q;
+                z[k] = s;
+                z[k + 1] = f32::INFINITY;
+            }
+            break;
+        }
+    }
+
+    k = 0;
+    for q in 0..n {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let p = v[k];
+        d[q] = (q as f32 - p as f32).powi(2) + f[p];
+    }
+}
+
+pub(crate) fn material() -> Material {
+    load_material(
+        match crate::get_quad_context().info().backend {
+            Backend::OpenGl => ShaderSource::Glsl {
+                vertex: VERTEX_SHADER,
+                fragment: FRAGMENT_SHADER,
+            },
+            Backend::Metal => ShaderSource::Msl {
+                program: METAL_SHADER,
+            },
+        },
+        MaterialParams {
+            pipeline_params: PipelineParams {
+                color_blend: Some(BlendState::new(
+                    Equation::Add,
+                    BlendFactor::Value(BlendValue::SourceAlpha),
+                    BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+                )),
+                ..Default::default()
+            },
+            uniforms: vec![
+                UniformDesc::new("OutlineColor", UniformType::Float4),
+                UniformDesc::new("Outline", UniformType::Float1),
+                UniformDesc::new("Softness", UniformType::Float1),
+            ],
+            ..Default::default()
+        },
+    )
+    .unwrap()
+}
+
+const VERTEX_SHADER: &str = "#version 100
+attribute vec3 position;
+attribute vec4 color0;
+attribute vec2 texcoord;
+
+varying mediump vec2 uv;
+varying lowp vec4 color;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    uv = texcoord;
+    color = color0 / 255.0;
+}
+";
+
+const FRAGMENT_SHADER: &str = "#version 100
+#extension GL_OES_standard_derivatives : enable
+varying mediump vec2 uv;
+varying lowp vec4 color;
+
+uniform sampler2D Texture;
+uniform lowp vec4 OutlineColor;
+uniform mediump float Outline;
+uniform mediump float Softness;
+
+void main() {
+    mediump float dist = texture2D(Texture, uv).a;
+    // about one screen pixel wide edge, whatever the scale is
+    mediump float width = fwidth(dist) * 0.7 + Softness;
+
+    mediump float fill = smoothstep(0.5 - width, 0.5 + width, dist);
+    mediump float shape = smoothstep(0.5 - Outline - width, 0.5 - Outline + width, dist);
+
+    lowp vec4 base = Outline > 0.0 ? mix(OutlineColor, color, fill) : color;
+    gl_FragColor = vec4(base.rgb, base.a * shape);
+}
+";
+
+const METAL_SHADER: &str = r#"
+#include <metal_stdlib>
+    using namespace metal;
+
+    struct Uniforms
+    {
+        float4x4 Model;
+        float4x4 Projection;
+        float4 _Time;
+
+        float4 OutlineColor;
+        float Outline;
+        float Softness;
+    };
+
+    struct Vertex
+    {
+        float3 position    [[attribute(0)]];
+        float2 texcoord    [[attribute(1)]];
+        float4 color0      [[attribute(2)]];
+    };
+
+    struct RasterizerData
+    {
+        float4 position [[position]];
+        float4 color [[user(locn0)]];
+        float2 uv [[user(locn1)]];
+    };
+
+    vertex RasterizerData vertexShader(Vertex v [[stage_in]], constant Uniforms& uniforms [[buffer(0)]])
+    {
+        RasterizerData out;
+
+        out.position = uniforms.Projection * uniforms.Model * float4(v.position, 1);
+        out.color = v.color0 / 255.0;
+        out.uv = v.texcoord;
+
+        return out;
+    }
+
+    fragment float4 fragmentShader(RasterizerData in [[stage_in]], constant Uniforms& u [[buffer(0)]], texture2d<float> tex [[texture(0)]], sampler texSmplr [[sampler(0)]])
+    {
+        float dist = tex.sample(texSmplr, in.uv).a;
+        float width = fwidth(dist) * 0.7 + u.Softness;
+
+        float fill = smoothstep(0.5 - width, 0.5 + width, dist);
+        float shape = smoothstep(0.5 - u.Outline - width, 0.5 - u.Outline + width, dist);
+
+        float4 base = u.Outline > 0.0 ? mix(u.OutlineColor, in.color, fill) : in.color;
+        return float4(base.rgb, base.a * shape);
+    }"#;
+
+#[test]
+fn glyph_distance_field() {
+    // 4x4 filled square in a 6x6 bitmap
+    let coverage: Vec<u8> = (0..36)
+        .map(|i| {
+            if (1..5).contains(&(i % 6)) && (1..5).contains(&(i / 6)) {
+                255
+            } else {
+                0
+            }
+        })
+        .collect();
+    let field = distance_field(&coverage, 6, 6, 4);
+    let w = 6 + 8;
+    assert_eq!(field.len(), w * w);
+
+    let at = |x: usize, y: usize| field[(y + 4) * w + x + 4];
+    // inside is above the edge value, outside below, far outside is 0
+    assert!(at(2, 2) > 128 && at(1, 1) > 128);
+    assert!(at(0, 2) < 128);
+    assert_eq!(field[0], 0);
+    // symmetric around the square
+    assert_eq!(at(0, 2), at(5, 2));
+    assert_eq!(at(2, 0), at(2, 5));
+}
@@ -1,18 +1,26 @@
 //! Loading and rendering textures. Also render textures, per-pixel image manipulations.
 
 use crate::{
-    color::Color, file::load_file, get_context, get_quad_context, math::Rect,
-    text::atlas::SpriteKey, Error,
+    color::{Color, WHITE},
+    file::{load_file, load_file_with_progress, FileProgress},
+    get_context, get_quad_context,
+    logging::warn,
+    math::{Rect, RectOffset},
+    text::atlas::SpriteKey,
+    Error,
 };
 
 pub use crate::quad_gl::FilterMode;
 use crate::quad_gl::{DrawMode, Vertex};
 use glam::{vec2, Vec2};
 use slotmap::{TextureIdSlotMap, TextureSlotId};
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
+mod atlas;
 mod slotmap;
 
+pub use atlas::{draw_sprite, TextureAtlas, TextureAtlasBuilder};
+
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct TextureSlotGuarded(pub TextureSlotId);
 
@@ -28,17 +36,24 @@ pub(crate) enum TextureHandle {
 pub(crate) struct TexturesContext {
     textures: TextureIdSlotMap,
     removed: Vec<TextureSlotId>,
+    // unmanaged textures that may still be used by the current frame's draw calls
+    retired: Vec<miniquad::TextureId>,
 }
 impl TexturesContext {
     pub fn new() -> TexturesContext {
         TexturesContext {
             textures: TextureIdSlotMap::new(),
             removed: Vec::with_capacity(200),
+            retired: vec![],
         }
     }
     fn schedule_removed(&mut self, texture: TextureSlotId) {
         self.removed.push(texture);
     }
+    /// Deletes the texture at the end of the frame, after all the draw calls are done.
+    pub(crate) fn retire(&mut self, texture: miniquad::TextureId) {
+        self.retired.push(texture);
+    }
     fn store_texture(&mut self, texture: miniquad::TextureId) -> TextureHandle {
         TextureHandle::Managed(Arc::new(TextureSlotGuarded(self.textures.insert(texture))))
     }
@@ -58,6 +73,9 @@ impl TexturesContext {
             }
             self.textures.remove(texture);
         }
+        for texture in self.retired.drain(..) {
+            ctx.delete_texture(texture);
+        }
     }
 }
 
@@ -303,9 +321,158 @@ impl Image {
         }
     }
 
+    /// Draws `src` on top of this image with its top left corner at `[x, y]`,
+    /// using the source alpha for blending.
+    /// Parts of `src` outside of this image are skipped.
+    pub fn blit(&mut self, src: &Image, x: i32, y: i32) {
+        let src_rect = Rect::new(0., 0., src.width as f32, src.height as f32);
+        self.blit_ex(src, src_rect, x, y, BlitMode::AlphaBlend);
+    }
+
+    /// Draws the `src_rect` part of `src` with its top left corner at `[x, y]`,
+    /// like a sprite from a sprite sheet.
+    /// Parts of `src_rect` outside of `src` or landing outside of this image are skipped.
+    pub fn blit_ex(&mut self, src: &Image, src_rect: Rect, x: i32, y: i32, mode: BlitMode) {
+        // the rect saturated to i32 first, so that no sum below overflows in i64
+        let (x, y) = (x as i64, y as i64);
+        let (rx, ry) = (src_rect.x as i32 as i64, src_rect.y as i32 as i64);
+        let (rw, rh) = (src_rect.w as i32 as i64, src_rect.h as i32 as i64);
+
+        // the part of src that lands on this image, in src coordinates
+        let sx0 = rx.max(0).max(rx - x);
+        let sy0 = ry.max(0).max(ry - y);
+        let sx1 = (rx + rw)
+            .min(src.width as i64)
+            .min(rx - x + self.width as i64);
+        let sy1 = (ry + rh)
+            .min(src.height as i64)
+            .min(ry - y + self.height as i64);
+        if sx0 >= sx1 || sy0 >= sy1 {
+            return;
+        }
+
+        let width = self.width as usize;
+        let src_width = src.width as usize;
+        let src_pixels = src.get_image_data();
+        let dst_pixels = self.get_image_data_mut();
+        for sy in sy0..sy1 {
+            let src_start = sy as usize * src_width;
+            let src_row = &src_pixels[src_start + sx0 as usize..src_start + sx1 as usize];
+            let dst_start = (y + sy - ry) as usize * width + (x + sx0 - rx) as usize;
+            let dst_row = &mut dst_pixels[dst_start..dst_start + src_row.len()];
+
+            match mode {
+                BlitMode::Copy => dst_row.copy_from_slice(src_row),
+                BlitMode::AlphaBlend => {
+                    for (d, s) in dst_row.iter_mut().zip(src_row) {
+                        *d = blend_over(*d, *s);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns a copy of this image scaled to `width` x `height`.
+    ///
+    /// `FilterMode::Nearest` keeps the pixels sharp, `FilterMode::Linear` interpolates
+    /// between the neighbouring pixels. The two axes are scaled independently.
+    ///
+    /// Resizing to or from a zero sized image gives a transparent image of the requested size.
+    pub fn resize(&self, width: u16, height: u16, filter: FilterMode) -> Image {
+        let mut resized = Image::gen_image_color(width, height, Color::new(0., 0., 0., 0.));
+        if self.width == 0 || self.height == 0 || width == 0 || height == 0 {
+            return resized;
+        }
+
+        let (src_w, src_h) = (self.width as usize, self.height as usize);
+        let scale_x = src_w as f32 / width as f32;
+        let scale_y = src_h as f32 / height as f32;
+        let src = self.get_image_data();
+        let dst = resized.get_image_data_mut();
+
+        for y in 0..height as usize {
+            for x in 0..width as usize {
+                // sample at the center of the destination pixel
+                let sx = (x as f32 + 0.5) * scale_x;
+                let sy = (y as f32 + 0.5) * scale_y;
+
+                dst[y * width as usize + x] = match filter {
+                    FilterMode::Nearest => {
+                        let sx = (sx as usize).min(src_w - 1);
+                        let sy = (sy as usize).min(src_h - 1);
+                        src[sy * src_w + sx]
+                    }
+                    FilterMode::Linear => {
+                        let sx = (sx - 0.5).clamp(0., (src_w - 1) as f32);
+                        let sy = (sy - 0.5).clamp(0., (src_h - 1) as f32);
+                        let (x0, y0) = (sx as usize, sy as usize);
+                        let (x1, y1) = ((x0 + 1).min(src_w - 1), (y0 + 1).min(src_h - 1));
+                        let (tx, ty) = (sx - x0 as f32, sy - y0 as f32);
+
+                        let mut pixel = [0; 4];
+                        for (i, channel) in pixel.iter_mut().enumerate() {
+                            let top = src[y0 * src_w + x0][i] as f32 * (1. - tx)
+                                + src[y0 * src_w + x1][i] as f32 * tx;
+                            let bottom = src[y1 * src_w + x0][i] as f32 * (1. - tx)
+                                + src[y1 * src_w + x1][i] as f32 * tx;
+                            *channel = (top * (1. - ty) + bottom * ty).round() as u8;
+                        }
+                        pixel
+                    }
+                };
+            }
+        }
+
+        resized
+    }
+
+    /// Mirrors this image horizontally.
+    pub fn flip_x(&mut self) {
+        let width = self.width as usize;
+        if width == 0 {
+            return;
+        }
+        for row in self.get_image_data_mut().chunks_exact_mut(width) {
+            row.reverse();
+        }
+    }
+
+    /// Mirrors this image vertically.
+    pub fn flip_y(&mut self) {
+        let row = self.width as usize * 4;
+        let height = self.height as usize;
+        for y in 0..height / 2 {
+            let (top, bottom) = self.bytes.split_at_mut((height - y - 1) * row);
+            top[y * row..(y + 1) * row].swap_with_slice(&mut bottom[..row]);
+        }
+    }
+
+    /// Rotates this image by 90 degrees clockwise, swapping its width and height.
+    pub fn rotate90(&mut self) {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let src = self.get_image_data();
+        let mut bytes = Vec::with_capacity(self.bytes.len());
+
+        // row `y` of the rotated image is column `y` of the original one, bottom to top
+        for y in 0..width {
+            for x in 0..height {
+                bytes.extend_from_slice(&src[(height - x - 1) * width + y]);
+            }
+        }
+
+        self.bytes = bytes;
+        self.width = height as u16;
+        self.height = width as u16;
+    }
+
     /// Saves this image as a PNG file.
     /// This method is not supported on web and will panic.
     pub fn export_png(&self, path: &str) {
+        self.save_png_flipped(path).unwrap();
+    }
+
+    /// Shared by [Image::export_png] and [screenshot_to_png].
+    fn save_png_flipped(&self, path: &str) -> Result<(), Error> {
         let mut bytes = vec![0; self.width as usize * self.height as usize * 4];
 
         // flip the image before saving
@@ -322,8 +489,217 @@ impl Image {
             self.width as _,
             self.height as _,
             image::ColorType::Rgba8,
-        )
-        .unwrap();
+        )?;
+        Ok(())
+    }
+}
+
+/// How [Image::blit_ex] puts the source pixels onto the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlitMode {
+    /// Blend using the source alpha, transparent source pixels keep the image as is.
+    #[default]
+    AlphaBlend,
+    /// Replace the pixels, alpha included.
+    Copy,
+}
+
+/// "Source over" blending of two non-premultiplied RGBA pixels.
+fn blend_over(dst: [u8; 4], src: [u8; 4]) -> [u8; 4] {
+    let src_alpha = src[3] as f32 / 255.;
+    let dst_alpha = dst[3] as f32 / 255. * (1. - src_alpha);
+    let alpha = src_alpha + dst_alpha;
+    if alpha <= 0. {
+        return [0; 4];
+    }
+
+    let mut pixel = [0; 4];
+    for i in 0..3 {
+        pixel[i] = ((src[i] as f32 * src_alpha + dst[i] as f32 * dst_alpha) / alpha).round() as u8;
+    }
+    pixel[3] = (alpha * 255.).round() as u8;
+    pixel
+}
+
+#[test]
+fn image_blit_clips_and_blends() {
+    let mut image = Image::gen_image_color(4, 4, Color::new(0., 0., 1., 1.));
+    let mut src = Image::gen_image_color(3, 3, Color::new(1., 0., 0., 1.));
+    src.set_pixel(2, 2, Color::new(1., 1., 1., 0.));
+
+    // hangs over the top left corner, only the bottom right 2x2 of src lands on the image
+    image.blit(&src, -1, -1);
+    assert_eq!(image.get_image_data()[0], [255, 0, 0, 255]);
+    assert_eq!(image.get_image_data()[1], [255, 0, 0, 255]);
+    assert_eq!(image.get_image_data()[4], [255, 0, 0, 255]);
+    // fully transparent source pixel keeps the destination
+    assert_eq!(image.get_image_data()[5], [0, 0, 255, 255]);
+    assert_eq!(image.get_image_data()[2], [0, 0, 255, 255]);
+
+    // completely outside
+    let before = image.bytes.clone();
+    image.blit(&src, 4, 0);
+    image.blit(&src, -3, 0);
+    assert_eq!(image.bytes, before);
+
+    let mut half = Image::gen_image_color(1, 1, Color::new(0., 0., 0., 1.));
+    let white = Image {
+        bytes: vec![255, 255, 255, 128],
+        width: 1,
+        height: 1,
+    };
+    half.blit(&white, 0, 0);
+    assert_eq!(half.get_image_data()[0], [128, 128, 128, 255]);
+}
+
+#[test]
+fn image_blit_ex_src_rect() {
+    // left half red, right half transparent
+    let mut src = Image::gen_image_color(4, 2, Color::new(1., 0., 0., 1.));
+    for y in 0..2 {
+        for x in 2..4 {
+            src.set_pixel(x, y, Color::new(0., 0., 0., 0.));
+        }
+    }
+    let blue = [0, 0, 255, 255];
+
+    let mut image = Image::gen_image_color(3, 3, Color::new(0., 0., 1., 1.));
+    // the rect sticks out of src and the image, only src (1..3, 1..2) lands, at (1..3, 2..3)
+    image.blit_ex(&src, Rect::new(1., 1., 5., 5.), 1, 2, BlitMode::AlphaBlend);
+    assert_eq!(image.get_image_data()[7], [255, 0, 0, 255]);
+    assert_eq!(image.get_image_data()[8], blue);
+    assert_eq!(image.get_image_data()[6], blue);
+
+    image.blit_ex(&src, Rect::new(1., 1., 5., 5.), 1, 2, BlitMode::Copy);
+    assert_eq!(image.get_image_data()[7], [255, 0, 0, 255]);
+    assert_eq!(image.get_image_data()[8], [0, 0, 0, 0]);
+
+    // negative rect origin, nothing to draw
+    let before = image.bytes.clone();
+    image.blit_ex(&src, Rect::new(-4., 0., 4., 2.), 0, 0, BlitMode::Copy);
+    assert_eq!(image.bytes, before);
+
+    // far away positions and huge rects clip instead of overflowing
+    image.blit_ex(
+        &src,
+        Rect::new(0., 0., 4., 2.),
+        i32::MAX,
+        i32::MIN,
+        BlitMode::Copy,
+    );
+    image.blit_ex(
+        &src,
+        Rect::new(1e30, 0., 1e30, 2.),
+        i32::MIN,
+        0,
+        BlitMode::Copy,
+    );
+    assert_eq!(image.bytes, before);
+    image.blit_ex(
+        &src,
+        Rect::new(0., 0., f32::MAX, f32::MAX),
+        0,
+        0,
+        BlitMode::Copy,
+    );
+    assert_eq!(image.get_image_data()[0], [255, 0, 0, 255]);
+}
+
+#[test]
+fn image_resize() {
+    let mut image = Image::gen_image_color(2, 1, Color::new(0., 0., 0., 1.));
+    image.set_pixel(1, 0, Color::new(1., 1., 1., 1.));
+
+    let nearest = image.resize(4, 2, FilterMode::Nearest);
+    assert_eq!(
+        nearest.get_image_data()[..4],
+        [
+            [0, 0, 0, 255],
+            [0, 0, 0, 255],
+            [255, 255, 255, 255],
+            [255, 255, 255, 255]
+        ]
+    );
+    assert_eq!(nearest.get_image_data()[..4], nearest.get_image_data()[4..]);
+
+    let linear = image.resize(4, 1, FilterMode::Linear);
+    assert_eq!(
+        linear.get_image_data(),
+        [
+            [0, 0, 0, 255],
+            [64, 64, 64, 255],
+            [191, 191, 191, 255],
+            [255, 255, 255, 255]
+        ]
+    );
+
+    assert_eq!(
+        Image::empty().resize(2, 2, FilterMode::Linear).bytes,
+        vec![0; 16]
+    );
+    let flat = image.resize(0, 3, FilterMode::Nearest);
+    assert_eq!((flat.width, flat.height, flat.bytes.len()), (0, 3, 0));
+}
+
+#[test]
+fn image_flip_and_rotate() {
+    // 3x2 image with pixels numbered in the red channel
+    let mut image = Image::gen_image_color(3, 2, Color::new(0., 0., 0., 1.));
+    for (i, pixel) in image.get_image_data_mut().iter_mut().enumerate() {
+        pixel[0] = i as u8;
+    }
+    let reds = |image: &Image| {
+        image
+            .get_image_data()
+            .iter()
+            .map(|p| p[0])
+            .collect::<Vec<_>>()
+    };
+
+    let mut flipped = image.clone();
+    flipped.flip_x();
+    assert_eq!(reds(&flipped), [2, 1, 0, 5, 4, 3]);
+
+    let mut flipped = image.clone();
+    flipped.flip_y();
+    assert_eq!(reds(&flipped), [3, 4, 5, 0, 1, 2]);
+
+    let mut rotated = image.clone();
+    rotated.rotate90();
+    assert_eq!((rotated.width, rotated.height), (2, 3));
+    assert_eq!(reds(&rotated), [3, 0, 4, 1, 5, 2]);
+
+    for _ in 0..3 {
+        rotated.rotate90();
+    }
+    assert_eq!(rotated.bytes, image.bytes);
+}
+
+/// Decodes an image without blocking the main thread.
+///
+/// On native platforms the decoding runs on a separate thread. On web there are no threads,
+/// so the image is decoded right away, but one frame is skipped before returning, to let a
+/// loading screen draw between the decoding and the GPU upload.
+async fn decode_image(bytes: Vec<u8>, format: Option<image::ImageFormat>) -> Result<Image, Error> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let result = Arc::new(std::sync::Mutex::new(None));
+        {
+            let result = result.clone();
+            std::thread::spawn(move || {
+                *result.lock().unwrap() = Some(Image::from_file_with_format(&bytes, format));
+            });
+        }
+
+        crate::exec::ThreadFuture { result }.await
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let image = Image::from_file_with_format(&bytes, format);
+        crate::exec::FrameFuture::default().await;
+
+        image
     }
 }
 
@@ -331,14 +707,53 @@ impl Image {
 pub async fn load_image(path: &str) -> Result<Image, Error> {
     let bytes = load_file(path).await?;
 
-    Image::from_file_with_format(&bytes, None)
+    decode_image(bytes, None).await
 }
 
 /// Loads a [Texture2D] from a file into GPU memory.
+///
+/// The image is decoded off the main thread, only the GPU upload happens on the main thread.
 pub async fn load_texture(path: &str) -> Result<Texture2D, Error> {
+    load_texture_with_format(path, None).await
+}
+
+/// Same as [load_texture], but with a known `format` the decoder does not need to guess it.
+/// If `format` is None, it will make an educated guess on the [ImageFormat][image::ImageFormat].
+pub async fn load_texture_with_format(
+    path: &str,
+    format: Option<image::ImageFormat>,
+) -> Result<Texture2D, Error> {
     let bytes = load_file(path).await?;
+    let image = decode_image(bytes, format).await?;
+
+    Ok(Texture2D::from_image(&image))
+}
+
+/// Loads a [Texture2D] from a file into GPU memory, with the given filter.
+/// See [Texture2D::from_image_with_filter].
+pub async fn load_texture_with_filter(path: &str, filter: FilterMode) -> Result<Texture2D, Error> {
+    let image = load_image(path).await?;
 
-    Ok(Texture2D::from_file_with_format(&bytes[..], None))
+    Ok(Texture2D::from_image_with_filter(&image, filter))
+}
+
+/// Same as [load_texture], but also returns a [FileProgress] to display a loading bar with.
+/// See [load_file_with_progress](crate::file::load_file_with_progress) for platform details.
+pub fn load_texture_with_progress(
+    path: &str,
+) -> (
+    impl Future<Output = Result<Texture2D, Error>> + 'static,
+    FileProgress,
+) {
+    let (bytes, progress) = load_file_with_progress(path);
+    let texture = async move {
+        let bytes = bytes.await?;
+        let image = decode_image(bytes, None).await?;
+
+        Ok(Texture2D::from_image(&image))
+    };
+
+    (texture, progress)
 }
 
 #[derive(Debug, Clone)]
@@ -357,12 +772,16 @@ pub struct RenderTargetParams {
     /// depth: true creates a depth render target attachment and allows
     /// such a render target being used for a depth-testing cameras
     pub depth: bool,
+
+    /// Filter used when the render target's texture is drawn.
+    pub filter: FilterMode,
 }
 impl Default for RenderTargetParams {
     fn default() -> RenderTargetParams {
         RenderTargetParams {
             sample_count: 1,
             depth: false,
+            filter: FilterMode::Linear,
         }
     }
 }
@@ -397,7 +816,8 @@ pub fn render_target(width: u32, height: u32) -> RenderTarget {
     render_target_ex(width, height, RenderTargetParams::default())
 }
 
-/// A shortcut to create a render target with no depth buffer and `sample_count: 4`
+/// A shortcut to create a render target with no depth buffer and `sample_count: 4`.
+/// Use [render_target_ex] for a different amount of samples.
 pub fn render_target_msaa(width: u32, height: u32) -> RenderTarget {
     render_target_ex(
         width,
@@ -409,9 +829,24 @@ pub fn render_target_msaa(width: u32, height: u32) -> RenderTarget {
     )
 }
 
-pub fn render_target_ex(width: u32, height: u32, params: RenderTargetParams) -> RenderTarget {
+/// Creates a render target with the given parameters.
+///
+/// A `sample_count` above what the driver supports is clamped down to the supported maximum.
+pub fn render_target_ex(width: u32, height: u32, mut params: RenderTargetParams) -> RenderTarget {
     let context = get_context();
 
+    if params.sample_count > 1 {
+        if let Some(max) = max_sample_count() {
+            if params.sample_count > max {
+                warn!(
+                    "render target: {} samples requested, but only {} are supported, using {}",
+                    params.sample_count, max, max
+                );
+                params.sample_count = max;
+            }
+        }
+    }
+
     let color_texture = get_quad_context().new_render_texture(miniquad::TextureParams {
         width,
         height,
@@ -454,10 +889,14 @@ pub fn render_target_ex(width: u32, height: u32, params: RenderTargetParams) ->
     let texture = Texture2D {
         texture: context.textures.store_texture(texture),
     };
+    texture.set_filter(params.filter);
+    let depth_texture = depth_texture.map(|depth_texture| Texture2D {
+        texture: context.textures.store_texture(depth_texture),
+    });
 
     let render_pass = RenderPass {
         color_texture: texture.clone(),
-        depth_texture: None,
+        depth_texture,
         render_pass: Arc::new(render_pass),
     };
     RenderTarget {
@@ -466,6 +905,38 @@ pub fn render_target_ex(width: u32, height: u32, params: RenderTargetParams) ->
     }
 }
 
+/// Largest texture width and height supported by the driver.
+pub(crate) fn max_texture_size() -> u32 {
+    #[cfg(not(target_arch = "wasm32"))]
+    if matches!(get_quad_context().info().backend, miniquad::Backend::OpenGl) {
+        const GL_MAX_TEXTURE_SIZE: u32 = 0x0D33;
+
+        let mut max = 0;
+        unsafe { miniquad::gl::glGetIntegerv(GL_MAX_TEXTURE_SIZE, &mut max) };
+        if max > 0 {
+            return max as u32;
+        }
+    }
+
+    // supported by practically everything
+    4096
+}
+
+/// Maximum MSAA sample count of the driver, None if it can't be queried.
+fn max_sample_count() -> Option<i32> {
+    #[cfg(not(target_arch = "wasm32"))]
+    if matches!(get_quad_context().info().backend, miniquad::Backend::OpenGl) {
+        const GL_MAX_SAMPLES: u32 = 0x8D57;
+
+        let mut max = 0;
+        unsafe { miniquad::gl::glGetIntegerv(GL_MAX_SAMPLES, &mut max) };
+        // 0 means the query itself is not supported, GLES2 for example
+        return (max > 0).then_some(max);
+    }
+
+    None
+}
+
 #[derive(Debug, Clone)]
 pub struct DrawTextureParams {
     pub dest_size: Option<Vec2>,
@@ -490,6 +961,11 @@ pub struct DrawTextureParams {
     /// E.g. pivot (0,0) rotates around the top left corner of the screen, not of the
     /// texture.
     pub pivot: Option<Vec2>,
+
+    /// Border sizes in texture pixels to draw the texture as a 9-slice (nine-patch).
+    /// Corners keep their size while the edges and the center stretch to `dest_size`.
+    /// See [draw_texture_nine_slice].
+    pub slice: Option<RectOffset>,
 }
 
 impl Default for DrawTextureParams {
@@ -501,6 +977,7 @@ impl Default for DrawTextureParams {
             pivot: None,
             flip_x: false,
             flip_y: false,
+            slice: None,
         }
     }
 }
@@ -516,6 +993,11 @@ pub fn draw_texture_ex(
     color: Color,
     params: DrawTextureParams,
 ) {
+    if let Some(margins) = params.slice {
+        draw_texture_nine_slice(texture, x, y, color, margins, params);
+        return;
+    }
+
     let context = get_context();
 
     let [mut width, mut height] = texture.size().to_array();
@@ -605,7 +1087,204 @@ pub fn draw_texture_ex(
     context.gl.geometry(&vertices, &indices);
 }
 
+/// One copy of the texture drawn by [draw_texture_instanced].
+#[derive(Debug, Clone, Copy)]
+pub struct InstanceParams {
+    /// Center of the copy, unlike [draw_texture] that takes the top left corner.
+    pub position: Vec2,
+    /// Rotation in radians around `position`.
+    pub rotation: f32,
+    /// Multiplies the size of `source`, negative values flip the copy.
+    pub scale: Vec2,
+    pub color: Color,
+    /// Part of texture to draw. If None - draw the whole texture.
+    pub source: Option<Rect>,
+}
+
+impl Default for InstanceParams {
+    fn default() -> InstanceParams {
+        InstanceParams {
+            position: vec2(0., 0.),
+            rotation: 0.,
+            scale: vec2(1., 1.),
+            color: WHITE,
+            source: None,
+        }
+    }
+}
+
+/// Draw many copies of a texture, like particles, with as few draw calls as possible.
+///
+/// The copies are drawn in order, with the current camera and material,
+/// and each one costs about as much as a single [draw_rectangle](crate::shapes::draw_rectangle)
+/// instead of a [draw_texture_ex] call.
+pub fn draw_texture_instanced(texture: &Texture2D, instances: &[InstanceParams]) {
+    let context = get_context();
+
+    let [width, height] = texture.size().to_array();
+    // uv of the whole texture, inside of the batcher's atlas if it is in there
+    let (texture_opt, uv) = match context.texture_batcher.get(texture) {
+        Some((batched_texture, uv)) => (Some(batched_texture), uv),
+        None => (None, Rect::new(0., 0., 1., 1.)),
+    };
+    let texture = texture_opt.as_ref().unwrap_or(texture);
+
+    let (max_vertices, max_indices) = context.gl.max_geometry_size();
+    // indices are u16, relative to the start of the geometry
+    let chunk_size = (max_vertices / 4)
+        .min(max_indices / 6)
+        .min(u16::MAX as usize / 4)
+        .max(1);
+    let mut vertices = Vec::with_capacity(chunk_size.min(instances.len()) * 4);
+    let mut indices = Vec::with_capacity(chunk_size.min(instances.len()) * 6);
+
+    context.gl.texture(Some(texture));
+    context.gl.draw_mode(DrawMode::Triangles);
+
+    for chunk in instances.chunks(chunk_size) {
+        vertices.clear();
+        indices.clear();
+
+        for instance in chunk {
+            let source = instance.source.unwrap_or(Rect::new(0., 0., width, height));
+            let u0 = source.x / width * uv.w + uv.x;
+            let v0 = source.y / height * uv.h + uv.y;
+            let u1 = source.right() / width * uv.w + uv.x;
+            let v1 = source.bottom() / height * uv.h + uv.y;
+
+            let half = source.size() * instance.scale / 2.;
+            let (sin, cos) = instance.rotation.sin_cos();
+            let corner =
+                |x: f32, y: f32| instance.position + vec2(x * cos - y * sin, x * sin + y * cos);
+            let p = [
+                corner(-half.x, -half.y),
+                corner(half.x, -half.y),
+                corner(half.x, half.y),
+                corner(-half.x, half.y),
+            ];
+
+            let base = vertices.len() as u16;
+            let color = instance.color;
+            vertices.extend([
+                Vertex::new(p[0].x, p[0].y, 0., u0, v0, color),
+                Vertex::new(p[1].x, p[1].y, 0., u1, v0, color),
+                Vertex::new(p[2].x, p[2].y, 0., u1, v1, color),
+                Vertex::new(p[3].x, p[3].y, 0., u0, v1, color),
+            ]);
+            indices.extend([0, 1, 2, 0, 2, 3].map(|index| base + index));
+        }
+
+        context.gl.geometry(&vertices, &indices);
+    }
+}
+
+/// Draw a texture split into 9 regions, so its frame does not stretch
+/// when the texture is drawn bigger than its native size.
+///
+/// `margins` are the sizes of the frame borders in texture pixels, measured from
+/// the edges of `params.source` (or the whole texture).
+/// Corners are drawn unscaled, edges are stretched along one axis and the center along
+/// both axes to fill `params.dest_size`.
+/// If the destination is smaller than the borders, the borders are shrunk proportionally.
+pub fn draw_texture_nine_slice(
+    texture: &Texture2D,
+    x: f32,
+    y: f32,
+    color: Color,
+    margins: RectOffset,
+    params: DrawTextureParams,
+) {
+    let source = params
+        .source
+        .unwrap_or_else(|| Rect::new(0., 0., texture.width(), texture.height()));
+    let dest = params.dest_size.unwrap_or(source.size());
+
+    let border_scale = |start: f32, end: f32, size: f32| {
+        if start + end > size {
+            size / (start + end)
+        } else {
+            1.
+        }
+    };
+    let scale_x = border_scale(margins.left, margins.right, dest.x);
+    let scale_y = border_scale(margins.top, margins.bottom, dest.y);
+
+    let source_x = [
+        source.x,
+        source.x + margins.left,
+        source.right() - margins.right,
+        source.right(),
+    ];
+    let source_y = [
+        source.y,
+        source.y + margins.top,
+        source.bottom() - margins.bottom,
+        source.bottom(),
+    ];
+    let dest_x = [
+        0.,
+        margins.left * scale_x,
+        dest.x - margins.right * scale_x,
+        dest.x,
+    ];
+    let dest_y = [
+        0.,
+        margins.top * scale_y,
+        dest.y - margins.bottom * scale_y,
+        dest.y,
+    ];
+
+    // all the slices rotate around the same point to stay together
+    let pivot = params
+        .pivot
+        .unwrap_or(vec2(x + dest.x / 2., y + dest.y / 2.));
+
+    for row in 0..3 {
+        for column in 0..3 {
+            let sw = source_x[column + 1] - source_x[column];
+            let sh = source_y[row + 1] - source_y[row];
+            let w = dest_x[column + 1] - dest_x[column];
+            let h = dest_y[row + 1] - dest_y[row];
+            if sw <= 0. || sh <= 0. || w <= 0. || h <= 0. {
+                continue;
+            }
+
+            // flipped slices swap their places, not only their content
+            let slice_x = if params.flip_x {
+                dest.x - dest_x[column + 1]
+            } else {
+                dest_x[column]
+            };
+            let slice_y = if params.flip_y {
+                dest.y - dest_y[row + 1]
+            } else {
+                dest_y[row]
+            };
+
+            draw_texture_ex(
+                texture,
+                x + slice_x,
+                y + slice_y,
+                color,
+                DrawTextureParams {
+                    dest_size: Some(vec2(w, h)),
+                    source: Some(Rect::new(source_x[column], source_y[row], sw, sh)),
+                    pivot: Some(pivot),
+                    slice: None,
+                    ..params.clone()
+                },
+            );
+        }
+    }
+}
+
 /// Get pixel data from screen buffer and return an Image (screenshot)
+///
+/// The image has the size of the window in physical pixels and holds what was drawn
+/// so far this frame, so it is best taken right before [next_frame](crate::window::next_frame),
+/// or at the start of the next frame for what the UI drew on top.
+/// The rows go from the bottom of the screen to the top, as the GPU reads them:
+/// [Image::export_png] expects them this way, [get_screen_image] has them top to bottom.
 pub fn get_screen_data() -> Image {
     unsafe {
         crate::window::get_internal_gl().flush();
@@ -628,6 +1307,47 @@ pub fn get_screen_data() -> Image {
     texture.get_texture_data()
 }
 
+/// [get_screen_data] with the rows from the top of the screen to the bottom,
+/// like the images loaded from files.
+pub fn get_screen_image() -> Image {
+    let mut image = get_screen_data();
+    image.flip_y();
+    image
+}
+
+/// [get_screen_image] of the whole frame, taken once everything is drawn, the UI and
+/// the cursor image included. Ends the frame like [next_frame](crate::window::next_frame).
+///
+/// On the web the browser clears the screen once the frame is shown, so a screenshot
+/// taken from within the next frame is blank: this is the way to get what the frame drew there.
+pub async fn get_screen_image_async() -> Image {
+    loop {
+        let context = get_context();
+        if let Some(image) = context.screenshot.take() {
+            return image;
+        }
+        context.screenshot_requested = true;
+        crate::window::next_frame().await;
+    }
+}
+
+/// Takes the screenshot of [get_screen_image_async], called once the frame is drawn.
+pub(crate) fn take_requested_screenshot() {
+    if !std::mem::take(&mut get_context().screenshot_requested) {
+        return;
+    }
+    let image = get_screen_image();
+    get_context().screenshot = Some(image);
+}
+
+/// Save a screenshot, see [get_screen_data], as a PNG file, the right way up.
+///
+/// Not available on the web, where there is no file system to write to.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn screenshot_to_png(path: &str) -> Result<(), Error> {
+    get_screen_data().save_png_flipped(path)
+}
+
 /// Texture, data stored in GPU memory
 #[derive(Clone, Debug, PartialEq)]
 pub struct Texture2D {
@@ -712,6 +1432,14 @@ impl Texture2D {
         Texture2D::from_rgba8(image.width, image.height, &image.bytes)
     }
 
+    /// Creates a Texture2D from an [Image] with the given filter.
+    ///
+    /// Use `FilterMode::Nearest` for pixel art, so the sprites stay sharp from the very
+    /// first frame, without a `set_filter` call after the texture is created.
+    pub fn from_image_with_filter(image: &Image, filter: FilterMode) -> Texture2D {
+        Texture2D::from_rgba8_with_filter(image.width, image.height, &image.bytes, filter)
+    }
+
     /// Creates a Texture2D from a miniquad
     /// [Texture](https://docs.rs/miniquad/0.3.0-alpha/miniquad/graphics/struct.Texture.html)
     pub const fn from_miniquad_texture(texture: miniquad::TextureId) -> Texture2D {
@@ -735,11 +1463,34 @@ impl Texture2D {
     /// # }
     /// ```
     pub fn from_rgba8(width: u16, height: u16, bytes: &[u8]) -> Texture2D {
-        let texture = get_quad_context().new_texture_from_rgba8(width, height, bytes);
+        let filter = get_context().default_filter_mode;
+
+        Texture2D::from_rgba8_with_filter(width, height, bytes, filter)
+    }
+
+    /// Same as [Texture2D::from_rgba8], but the texture is created with the given filter
+    /// instead of the one from [set_default_filter_mode].
+    pub fn from_rgba8_with_filter(
+        width: u16,
+        height: u16,
+        bytes: &[u8],
+        filter: FilterMode,
+    ) -> Texture2D {
+        let texture = get_quad_context().new_texture_from_data_and_format(
+            bytes,
+            miniquad::TextureParams {
+                width: width as _,
+                height: height as _,
+                format: miniquad::TextureFormat::RGBA8,
+                min_filter: filter,
+                mag_filter: filter,
+                mipmap_filter: miniquad::MipmapFilterMode::None,
+                ..Default::default()
+            },
+        );
         let ctx = get_context();
         let texture = ctx.textures.store_texture(texture);
         let texture = Texture2D { texture };
-        texture.set_filter(ctx.default_filter_mode);
 
         ctx.texture_batcher.add_unbatched(&texture);
 
@@ -769,6 +1520,13 @@ impl Texture2D {
     }
 
     /// Uploads [Image] data to part of this texture.
+    ///
+    /// Only the `[x_offset, y_offset, width, height]` region is sent to the GPU, much cheaper
+    /// than [Texture2D::update] when just a small part of a big texture changes.
+    ///
+    /// # Panics
+    /// If the region does not fit into the texture or the image is not exactly
+    /// `width` x `height` pixels.
     pub fn update_part(
         &self,
         image: &Image,
@@ -778,6 +1536,31 @@ impl Texture2D {
         height: i32,
     ) {
         let ctx = get_quad_context();
+        let (texture_width, texture_height) = ctx.texture_size(self.raw_miniquad_id());
+
+        assert!(
+            x_offset >= 0
+                && y_offset >= 0
+                && width >= 0
+                && height >= 0
+                && x_offset as u32 + width as u32 <= texture_width
+                && y_offset as u32 + height as u32 <= texture_height,
+            "update_part: region {}x{} at ({}, {}) does not fit into {}x{} texture",
+            width,
+            height,
+            x_offset,
+            y_offset,
+            texture_width,
+            texture_height
+        );
+        assert!(
+            image.width as i32 == width && image.height as i32 == height,
+            "update_part: image is {}x{}, but the region is {}x{}",
+            image.width,
+            image.height,
+            width,
+            height
+        );
 
         ctx.texture_update_part(
             self.raw_miniquad_id(),
@@ -880,18 +1663,51 @@ impl Texture2D {
     }
 
     /// Returns an [Image] from the pixel data in this texture.
+    /// RGB and alpha-only textures are expanded to RGBA.
     ///
-    /// This operation can be expensive.
+    /// All the pending draw calls are flushed first, so the image includes everything drawn
+    /// into a render target so far. The read back stalls the GPU pipeline, use it for
+    /// screenshots or building collision masks at load time, not every frame.
+    /// The read is synchronous on all the platforms, including web.
     pub fn get_texture_data(&self) -> Image {
+        unsafe {
+            crate::window::get_internal_gl().flush();
+        }
+
+        let id = self.raw_miniquad_id();
         let ctx = get_quad_context();
-        let (width, height) = ctx.texture_size(self.raw_miniquad_id());
-        let mut image = Image {
-            width: width as _,
-            height: height as _,
-            bytes: vec![0; width as usize * height as usize * 4],
+        let params = ctx.texture_params(id);
+        let pixels = params.width as usize * params.height as usize;
+
+        let bytes = match params.format {
+            miniquad::TextureFormat::RGBA8 => {
+                let mut bytes = vec![0; pixels * 4];
+                ctx.texture_read_pixels(id, &mut bytes);
+                bytes
+            }
+            miniquad::TextureFormat::RGB8 => {
+                let mut rgb = vec![0; pixels * 3];
+                ctx.texture_read_pixels(id, &mut rgb);
+                rgb.chunks_exact(3)
+                    .flat_map(|c| [c[0], c[1], c[2], 255])
+                    .collect()
+            }
+            miniquad::TextureFormat::Alpha => {
+                let mut alpha = vec![0; pixels];
+                ctx.texture_read_pixels(id, &mut alpha);
+                alpha.iter().flat_map(|&a| [255, 255, 255, a]).collect()
+            }
+            format => {
+                warn!("get_texture_data: {:?} textures can't be read back", format);
+                vec![0; pixels * 4]
+            }
         };
-        ctx.texture_read_pixels(self.raw_miniquad_id(), &mut image.bytes);
-        image
+
+        Image {
+            width: params.width as _,
+            height: params.height as _,
+            bytes,
+        }
     }
 }
 
@@ -901,10 +1717,10 @@ pub(crate) struct Batcher {
 }
 
 impl Batcher {
-    pub fn new(ctx: &mut dyn miniquad::RenderingBackend) -> Batcher {
+    pub fn new(ctx: &mut dyn miniquad::RenderingBackend, filter: FilterMode) -> Batcher {
         Batcher {
             unbatched: vec![],
-            atlas: crate::text::atlas::Atlas::new(ctx, miniquad::FilterMode::Linear),
+            atlas: crate::text::atlas::Atlas::new(ctx, filter),
         }
     }
 
@@ -946,9 +1762,25 @@ pub fn build_textures_atlas() {
 pub unsafe fn reset_textures_atlas() {
     let context = get_context();
     context.fonts_storage = crate::text::FontsStorage::new(&mut *context.quad_context);
-    context.texture_batcher = Batcher::new(&mut *context.quad_context);
+    context.texture_batcher = Batcher::new(&mut *context.quad_context, context.default_filter_mode);
 }
 
+/// Filter of the textures created from now on, [Texture2D::from_rgba8], [load_texture]
+/// and the fonts from [load_ttf_font](crate::text::load_ttf_font) included.
+/// The ones created before keep their filter.
+///
+/// Same as `Conf::default_filter_mode`, but can be changed at any point.
+/// The atlas of [build_textures_atlas] is a single texture, so it changes right away.
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # #[macroquad::main("test")]
+/// # async fn main() {
+/// // pixel art, no blurry sprites anywhere
+/// set_default_filter_mode(FilterMode::Nearest);
+/// let player = load_texture("examples/ferris.png").await.unwrap();
+/// # }
+/// ```
 pub fn set_default_filter_mode(filter: FilterMode) {
     let context = get_context();
 
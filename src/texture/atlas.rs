@@ -0,0 +1,259 @@
+//! Packing multiple images into a single texture, so they all can be drawn in one draw call.
+
+use crate::{
+    color::Color,
+    math::Rect,
+    texture::{draw_texture_ex, DrawTextureParams, FilterMode, Image, Texture2D},
+    Error,
+};
+
+use std::collections::HashMap;
+
+/// Collects images to pack into a [TextureAtlas].
+///
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # async fn f() -> Result<(), macroquad::Error> {
+/// let mut builder = TextureAtlasBuilder::new().padding(2);
+/// builder.add("player", &load_image("player.png").await?);
+/// builder.add("coin", &load_image("coin.png").await?);
+/// let atlas = builder.build()?;
+///
+/// loop {
+///     draw_sprite(&atlas, "player", 100., 100., WHITE, DrawTextureParams::default());
+///     next_frame().await
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextureAtlasBuilder {
+    sprites: Vec<(String, Image)>,
+    padding: u16,
+    max_size: u16,
+    filter: Option<FilterMode>,
+}
+
+impl Default for TextureAtlasBuilder {
+    fn default() -> TextureAtlasBuilder {
+        TextureAtlasBuilder {
+            sprites: vec![],
+            padding: 1,
+            max_size: 4096,
+            filter: None,
+        }
+    }
+}
+
+impl TextureAtlasBuilder {
+    pub fn new() -> TextureAtlasBuilder {
+        TextureAtlasBuilder::default()
+    }
+
+    /// Empty pixels between the sprites, 1 by default.
+    /// With [FilterMode::Linear] sprites need some padding to not bleed into each other.
+    pub fn padding(self, padding: u16) -> Self {
+        TextureAtlasBuilder { padding, ..self }
+    }
+
+    /// Largest width and height of the atlas texture, 4096 by default.
+    pub fn max_size(self, max_size: u16) -> Self {
+        TextureAtlasBuilder { max_size, ..self }
+    }
+
+    /// Filter of the atlas texture.
+    /// If not set, the one from [set_default_filter_mode](crate::texture::set_default_filter_mode) is used.
+    pub fn filter(self, filter: FilterMode) -> Self {
+        TextureAtlasBuilder {
+            filter: Some(filter),
+            ..self
+        }
+    }
+
+    /// Adds an image to the atlas. An image with the same name is replaced.
+    pub fn add(&mut self, name: &str, image: &Image) -> &mut Self {
+        if let Some(sprite) = self.sprites.iter_mut().find(|(n, _)| n == name) {
+            sprite.1 = image.clone();
+        } else {
+            self.sprites.push((name.to_owned(), image.clone()));
+        }
+        self
+    }
+
+    /// Packs all the images and uploads them into a single texture.
+    ///
+    /// Fails with [Error::TextureAtlasOverflow] when the images do not fit into `max_size`.
+    pub fn build(self) -> Result<TextureAtlas, Error> {
+        let sizes: Vec<(u16, u16)> = self
+            .sprites
+            .iter()
+            .map(|(_, image)| (image.width, image.height))
+            .collect();
+
+        let (size, positions) =
+            pack(&sizes, self.padding, self.max_size).ok_or(Error::TextureAtlasOverflow {
+                max_size: self.max_size,
+            })?;
+
+        let mut image = Image::gen_image_color(size, size, Color::new(0., 0., 0., 0.));
+        let mut sprites = HashMap::with_capacity(self.sprites.len());
+
+        for ((name, sprite), (x, y)) in self.sprites.into_iter().zip(positions) {
+            let row = sprite.width as usize * 4;
+            for sy in 0..sprite.height as usize {
+                let dst = ((y as usize + sy) * size as usize + x as usize) * 4;
+                image.bytes[dst..dst + row]
+                    .copy_from_slice(&sprite.bytes[sy * row..(sy + 1) * row]);
+            }
+
+            let rect = Rect::new(
+                x as f32,
+                y as f32,
+                sprite.width as f32,
+                sprite.height as f32,
+            );
+            sprites.insert(name, rect);
+        }
+
+        let texture = Texture2D::from_image(&image);
+        if let Some(filter) = self.filter {
+            texture.set_filter(filter);
+        }
+
+        Ok(TextureAtlas { texture, sprites })
+    }
+}
+
+/// A single texture with many named sprites in it.
+/// Created with [TextureAtlasBuilder].
+#[derive(Debug, Clone)]
+pub struct TextureAtlas {
+    texture: Texture2D,
+    sprites: HashMap<String, Rect>,
+}
+
+impl TextureAtlas {
+    /// The texture with all the sprites.
+    pub const fn texture(&self) -> &Texture2D {
+        &self.texture
+    }
+
+    /// Part of the atlas texture occupied by the sprite, in pixels.
+    pub fn sprite_rect(&self, name: &str) -> Option<Rect> {
+        self.sprites.get(name).copied()
+    }
+
+    /// The atlas texture and the sprite's rectangle in it, ready to be used as
+    /// [DrawTextureParams::source].
+    pub fn sprite(&self, name: &str) -> Option<(Texture2D, Rect)> {
+        self.sprite_rect(name)
+            .map(|rect| (self.texture.clone(), rect))
+    }
+
+    /// Names of all the sprites in the atlas.
+    pub fn sprite_names(&self) -> impl Iterator<Item = &str> {
+        self.sprites.keys().map(|name| name.as_str())
+    }
+}
+
+/// Draws a sprite from the atlas, same as [draw_texture_ex] with a texture of just that sprite.
+///
+/// `params.source`, if set, is relative to the sprite's top left corner.
+/// Nothing is drawn when there is no sprite with this name.
+pub fn draw_sprite(
+    atlas: &TextureAtlas,
+    name: &str,
+    x: f32,
+    y: f32,
+    color: Color,
+    params: DrawTextureParams,
+) {
+    let Some(rect) = atlas.sprite_rect(name) else {
+        return;
+    };
+
+    let source = match params.source {
+        Some(source) => Rect::new(rect.x + source.x, rect.y + source.y, source.w, source.h),
+        None => rect,
+    };
+
+    draw_texture_ex(
+        &atlas.texture,
+        x,
+        y,
+        color,
+        DrawTextureParams {
+            source: Some(source),
+            ..params
+        },
+    );
+}
+
+/// Shelf packing: sprites sorted by height are placed left to right in rows.
+/// Tries power of two square sizes until everything fits.
+/// Returns the atlas size and the position of each sprite.
+fn pack(sizes: &[(u16, u16)], padding: u16, max_size: u16) -> Option<(u16, Vec<(u16, u16)>)> {
+    let padding = padding as u32;
+    let max_size = max_size as u32;
+
+    if sizes
+        .iter()
+        .any(|&(w, h)| w as u32 > max_size || h as u32 > max_size)
+    {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(sizes[i].1));
+
+    let mut size = 64u32.min(max_size);
+    loop {
+        let mut positions = vec![(0, 0); sizes.len()];
+        let (mut x, mut y, mut shelf_height) = (0u32, 0u32, 0u32);
+        let mut fits = true;
+
+        for &i in &order {
+            let (w, h) = (sizes[i].0 as u32, sizes[i].1 as u32);
+            if x + w > size {
+                x = 0;
+                y += shelf_height + padding;
+                shelf_height = 0;
+            }
+            if x + w > size || y + h > size {
+                fits = false;
+                break;
+            }
+
+            positions[i] = (x as u16, y as u16);
+            x += w + padding;
+            shelf_height = shelf_height.max(h);
+        }
+
+        if fits {
+            return Some((size as u16, positions));
+        }
+        if size >= max_size {
+            return None;
+        }
+        size = (size * 2).min(max_size);
+    }
+}
+
+#[test]
+fn atlas_pack() {
+    let sizes = [(30, 10), (30, 20), (30, 10), (64, 5)];
+    let (size, positions) = pack(&sizes, 2, 1024).unwrap();
+
+    assert_eq!(size, 64);
+    // every sprite is inside the atlas and sprites, with padding, do not overlap
+    for (i, (&(x, y), &(w, h))) in positions.iter().zip(&sizes).enumerate() {
+        assert!(x + w <= size && y + h <= size);
+        for (&(ox, oy), &(ow, oh)) in positions[i + 1..].iter().zip(&sizes[i + 1..]) {
+            assert!(x + w + 2 <= ox || ox + ow + 2 <= x || y + h + 2 <= oy || oy + oh + 2 <= y);
+        }
+    }
+
+    assert_eq!(pack(&[(100, 100)], 0, 64), None);
+    assert_eq!(pack(&[(40, 40), (40, 40)], 1, 64), None);
+    assert_eq!(pack(&[(40, 40), (40, 40)], 1, 128).unwrap().0, 128);
+    assert_eq!(pack(&[], 1, 64), Some((64, vec![])));
+}
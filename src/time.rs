@@ -1,4 +1,10 @@
 //! Cross platform system time access and FPS counters.
+//!
+//! The game clock can be slowed down or paused with [set_time_scale].
+//! Scaled: [get_frame_time], [get_frame_time_capped], the coroutine timers like
+//! [wait_seconds](crate::experimental::coroutines::wait_seconds), the animations,
+//! and the fixed updates of the scene.
+//! Not scaled: [get_unscaled_frame_time], [get_time], [get_fps] and the ui.
 
 use crate::{get_context, text::draw_text};
 
@@ -14,18 +20,93 @@ pub fn get_fps() -> i32 {
     (1. / context.frame_time) as i32
 }
 
-/// Returns duration in seconds of the last frame drawn
+/// Returns duration in seconds of the last frame drawn, multiplied by the [time scale](set_time_scale).
+///
+/// Limited by [set_max_frame_time], without a limit the first frame after the window
+/// was minimized or the app was paused in a debugger may last seconds.
 pub fn get_frame_time() -> f32 {
     let context = get_context();
 
     if crate::experimental::scene::in_fixed_update() {
         crate::experimental::scene::fixed_frame_time()
     } else {
-        context.frame_time as f32
+        (context.frame_time.min(context.max_frame_time) * context.time_scale) as f32
+    }
+}
+
+/// [get_frame_time] without the [time scale](set_time_scale), for what keeps
+/// going while the game is paused, like the menus.
+///
+/// Still limited by [set_max_frame_time].
+pub fn get_unscaled_frame_time() -> f32 {
+    let context = get_context();
+
+    context.frame_time.min(context.max_frame_time) as f32
+}
+
+/// Speed of the game clock: 1 is the normal speed, 0.5 a slow motion
+/// and 0 pauses it. Negative scales are taken as 0.
+///
+/// The frames are still drawn while paused, [get_frame_time] is then 0 and
+/// coroutines waiting with [wait_seconds](crate::experimental::coroutines::wait_seconds)
+/// stay there. See the [module documentation](self) for what is scaled.
+pub fn set_time_scale(scale: f32) {
+    get_context().time_scale = scale.max(0.) as f64;
+}
+
+/// The scale set with [set_time_scale], 1 by default.
+pub fn get_time_scale() -> f32 {
+    get_context().time_scale as f32
+}
+
+/// [get_frame_time], but never longer than `max` seconds.
+///
+/// Handy for the physics steps, that go unstable with long frames:
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # let mut velocity = Vec2::ZERO;
+/// velocity.y += 9.8 * get_frame_time_capped(1. / 20.);
+/// ```
+pub fn get_frame_time_capped(max: f32) -> f32 {
+    get_frame_time().min(max)
+}
+
+/// Limits the frame time reported by [get_frame_time] to `max` seconds.
+///
+/// The game then slows down rather than jumping ahead on long frames.
+/// [get_fps] still gives the real frame rate.
+/// `f32::INFINITY`, the default, removes the limit.
+pub fn set_max_frame_time(max: f32) {
+    get_context().max_frame_time = max.max(0.) as f64;
+}
+
+pub(crate) const FRAME_TIMES_HISTORY: usize = 120;
+
+/// Refresh rate of the monitor in Hz, estimated from the recent frame times.
+///
+/// The windowing backend can't query the monitor, so this is only accurate while
+/// vsync is on and the game keeps up with the display. Without vsync it gives the FPS.
+/// None during the first frames, while there is not enough data.
+///
+/// Vsync can only be chosen at startup, with `swap_interval` in [miniquad::conf::Platform]:
+/// the backend has no way to change the swap interval of a running context,
+/// so there is no runtime setter for it.
+pub fn monitor_refresh_rate() -> Option<f32> {
+    let context = get_context();
+    if context.recent_frame_times.len() < FRAME_TIMES_HISTORY / 2 {
+        return None;
     }
+
+    // the median ignores the occasional hitches and loading frames
+    let mut times: Vec<f64> = context.recent_frame_times.iter().copied().collect();
+    times.sort_by(f64::total_cmp);
+    let median = times[times.len() / 2];
+
+    (median > 0.).then(|| (1. / median) as f32)
 }
 
-/// Returns elapsed wall-clock time in seconds since start
+/// Returns elapsed wall-clock time in seconds since start,
+/// not affected by the [time scale](set_time_scale).
 ///
 /// Note that as real world time progresses during computation,
 /// the value returned will change. Therefore if you want
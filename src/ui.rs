@@ -18,6 +18,8 @@ mod clipboard;
 mod hash;
 mod input_handler;
 mod render;
+#[cfg(feature = "serde")]
+mod skin_file;
 mod style;
 
 pub mod widgets;
@@ -73,7 +75,7 @@ use crate::{
     ui::{canvas::DrawCanvas, render::Painter},
 };
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 mod cursor;
 mod input;
 mod key_repeat;
@@ -377,7 +379,8 @@ pub struct Ui {
     child_window_stack: Vec<Id>,
 
     last_item_clicked: bool,
-    last_item_hovered: bool,
+    tooltips: widgets::Tooltips,
+    drag_and_drop: widgets::DragAndDrop,
 
     pub(crate) atlas: Arc<Mutex<Atlas>>,
     pub(crate) default_font: Arc<Mutex<Font>>,
@@ -389,6 +392,9 @@ pub struct Ui {
 
     tab_selector: TabSelector,
     input_focus: Option<Id>,
+    /// Widgets that only read the arrow keys while focused, like tab bars,
+    /// their focus does not count as capturing the keyboard.
+    pub(crate) arrow_keys_only: HashSet<Id>,
 }
 
 #[derive(Default)]
@@ -431,7 +437,6 @@ pub(crate) struct WindowContext<'a> {
     pub clipboard: &'a mut dyn crate::ui::ClipboardObject,
     pub focused: bool,
     pub last_item_clicked: &'a mut bool,
-    pub last_item_hovered: &'a mut bool,
     pub tab_selector: &'a mut TabSelector,
     pub input_focus: &'a mut Option<Id>,
 }
@@ -530,11 +535,12 @@ impl<'a> WindowContext<'a> {
     }
 
     pub fn register_click_intention(&mut self, rect: Rect) -> (bool, bool) {
-        *self.last_item_hovered =
-            self.input.window_active && rect.contains(self.input.mouse_position);
-        *self.last_item_clicked = *self.last_item_hovered && self.input.click_down();
+        self.window.cursor.last_item = Some(rect);
 
-        (*self.last_item_hovered, *self.last_item_clicked)
+        let hovered = self.input.window_active && rect.contains(self.input.mouse_position);
+        *self.last_item_clicked = hovered && self.input.click_down();
+
+        (hovered, *self.last_item_clicked)
     }
 
     pub fn input_focused(&self, id: Id) -> bool {
@@ -682,6 +688,8 @@ impl Ui {
             .cache_sprite(SpriteKey::Id(0), Image::gen_image_color(1, 1, crate::WHITE));
 
         let font = Arc::new(Mutex::new(font));
+        let tooltips = widgets::Tooltips::new(atlas.clone());
+        let drag_and_drop = widgets::DragAndDrop::new(atlas.clone());
         Ui {
             input: Input::default(),
             default_font: font.clone(),
@@ -725,9 +733,11 @@ impl Ui {
             time: 0.0,
             key_repeat: key_repeat::KeyRepeat::new(),
             last_item_clicked: false,
-            last_item_hovered: false,
+            tooltips,
+            drag_and_drop,
             tab_selector: TabSelector::new(),
             input_focus: None,
+            arrow_keys_only: HashSet::new(),
         }
     }
 
@@ -837,7 +847,6 @@ impl Ui {
             clipboard_selection: &mut self.clipboard_selection,
             clipboard: &mut *self.clipboard,
             last_item_clicked: &mut self.last_item_clicked,
-            last_item_hovered: &mut self.last_item_hovered,
             tab_selector: &mut self.tab_selector,
             input_focus: &mut self.input_focus,
         }
@@ -867,7 +876,10 @@ impl Ui {
         });
 
         window.parent = self.active_window;
-        window.size = size;
+        // the modal is reused by all the popups, its layout has to follow their sizes
+        if window.size != size {
+            window.resize(size);
+        }
         window.want_close = false;
         window.active = true;
         window.painter.clipping_zone = Some(Rect::new(position.x, position.y, size.x, size.y));
@@ -886,7 +898,6 @@ impl Ui {
             clipboard_selection: &mut self.clipboard_selection,
             clipboard: &mut *self.clipboard,
             last_item_clicked: &mut self.last_item_clicked,
-            last_item_hovered: &mut self.last_item_hovered,
             tab_selector: &mut self.tab_selector,
             input_focus: &mut self.input_focus,
         }
@@ -933,7 +944,6 @@ impl Ui {
             clipboard_selection: &mut self.clipboard_selection,
             clipboard: &mut *self.clipboard,
             last_item_clicked: &mut self.last_item_clicked,
-            last_item_hovered: &mut self.last_item_hovered,
             tab_selector: &mut self.tab_selector,
             input_focus: &mut self.input_focus,
         }
@@ -945,8 +955,23 @@ impl Ui {
     }
 
     /// Returns true if the mouse is over the last widget which had `.ui` called on it.
+    ///
+    /// Parts of the widget scrolled out of its group or window do not count.
     pub fn last_item_hovered(&mut self) -> bool {
-        self.last_item_hovered
+        let context = self.get_active_window_context();
+        let mouse_position = context.input.mouse_position;
+
+        context.input.window_active
+            && context
+                .window
+                .cursor
+                .last_item
+                .is_some_and(|rect| rect.contains(mouse_position))
+            && context
+                .window
+                .painter
+                .clipping_zone
+                .is_none_or(|clip| clip.contains(mouse_position))
     }
 
     /// Scrolls the middle of the active GUI window to its GUI cursor
@@ -995,6 +1020,14 @@ impl Ui {
         self.input.cursor_grabbed
     }
 
+    /// Whether a widget, like an editbox, has the keyboard focus.
+    ///
+    /// A focused [widgets::Tabbar] does not count, it only reads Left and Right.
+    pub fn is_keyboard_captured(&self) -> bool {
+        self.input_focus
+            .is_some_and(|id| !self.arrow_keys_only.contains(&id))
+    }
+
     pub fn is_mouse_over(&self, mouse_position: Vec2) -> bool {
         for window in self.windows_focus_order.iter() {
             let window = &self.windows[window];
@@ -1080,7 +1113,8 @@ impl Ui {
         self.time += delta;
 
         self.last_item_clicked = false;
-        self.last_item_hovered = false;
+        self.tooltips.new_frame();
+        self.drag_and_drop.new_frame(self.input.is_mouse_down);
 
         self.drag_hovered_previous_frame = self.drag_hovered;
         self.drag_hovered = None;
@@ -1135,6 +1169,14 @@ impl Ui {
 
             self.render_window(window, self.input.mouse_position - orig, draw_list);
         }
+
+        for cmd in &self.drag_and_drop.ghost.commands {
+            crate::ui::render::render_command(draw_list, cmd.clone());
+        }
+
+        for cmd in &self.tooltips.painter.commands {
+            crate::ui::render::render_command(draw_list, cmd.clone());
+        }
     }
 
     fn render_window(&self, window: &Window, offset: Vec2, draw_list: &mut Vec<DrawList>) {
@@ -1256,6 +1298,9 @@ pub(crate) mod ui_context {
             let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
             let ctrl = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
 
+            // the focus from the last frame decides who gets this frame's characters
+            crate::get_context().keyboard_captured_by_ui = ui.is_keyboard_captured();
+
             while let Some(c) = get_char_pressed_ui() {
                 if !ctrl {
                     ui.char_event(c, false, false);
@@ -1366,7 +1411,7 @@ pub(crate) mod ui_context {
             std::mem::swap(&mut ui_draw_list, &mut self.ui_draw_list);
 
             drop(atlas);
-            ui.new_frame(get_frame_time());
+            ui.new_frame(get_unscaled_frame_time());
         }
     }
 
@@ -1374,11 +1419,11 @@ pub(crate) mod ui_context {
 
     impl megaui::ClipboardObject for ClipboardObject {
         fn get(&self) -> Option<String> {
-            miniquad::window::clipboard_get()
+            crate::window::get_clipboard_text()
         }
 
         fn set(&mut self, data: &str) {
-            miniquad::window::clipboard_set(data);
+            crate::window::set_clipboard_text(data);
         }
     }
 
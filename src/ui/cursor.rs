@@ -51,6 +51,10 @@ pub struct Cursor {
     pub margin: f32,
     pub next_same_line: Option<f32>,
     pub max_row_y: f32,
+    /// Screen rect of the last widget placed with this cursor.
+    pub last_item: Option<Rect>,
+    /// Screen rect around the widgets placed since it was last taken.
+    pub bounds: Option<Rect>,
 }
 
 impl Cursor {
@@ -74,6 +78,8 @@ impl Cursor {
             area,
             next_same_line: None,
             max_row_y: 0.,
+            last_item: None,
+            bounds: None,
         }
     }
 
@@ -82,6 +88,8 @@ impl Cursor {
         self.y = self.start_y;
         self.max_row_y = 0.;
         self.ident = 0.;
+        self.last_item = None;
+        self.bounds = None;
         self.scroll.inner_rect_previous_frame = self.scroll.inner_rect;
         self.scroll.inner_rect = Rect::new(0., 0., self.area.w, self.area.h);
     }
@@ -135,6 +143,14 @@ impl Cursor {
             .inner_rect
             .combine_with(Rect::new(res.x, res.y, size.x, size.y));
 
-        res + Vec2::new(self.area.x, self.area.y) + self.scroll.scroll + Vec2::new(self.ident, 0.)
+        let pos = res
+            + Vec2::new(self.area.x, self.area.y)
+            + self.scroll.scroll
+            + Vec2::new(self.ident, 0.);
+        let item = Rect::new(pos.x, pos.y, size.x, size.y);
+        self.last_item = Some(item);
+        self.bounds = Some(self.bounds.map_or(item, |bounds| bounds.combine_with(item)));
+
+        pos
     }
 }
@@ -313,12 +313,13 @@ pub(crate) fn render_command(draw_lists: &mut Vec<DrawList>, command: DrawComman
         } => {
             active_draw_list.draw_rectangle(dest, source, color);
         }
-        DrawCommand::DrawRawTexture { rect, .. } => {
-            active_draw_list.draw_rectangle(
-                rect,
-                Rect::new(0., 0., 1., 1.),
-                Color::new(1., 1., 1., 1.),
-            );
+        DrawCommand::DrawRawTexture {
+            rect,
+            source,
+            color,
+            ..
+        } => {
+            active_draw_list.draw_rectangle(rect, source, color);
         }
         DrawCommand::DrawTriangle {
             p0,
@@ -59,6 +59,9 @@ pub(crate) enum DrawCommand {
     DrawRawTexture {
         rect: Rect,
         texture: Texture2D,
+        /// In texture coordinates, 0..1.
+        source: Rect,
+        color: Color,
     },
     Clip {
         rect: Option<Rect>,
@@ -77,9 +80,16 @@ impl DrawCommand {
                 source,
                 color,
             },
-            DrawCommand::DrawRawTexture { rect, texture } => DrawCommand::DrawRawTexture {
+            DrawCommand::DrawRawTexture {
+                rect,
+                texture,
+                source,
+                color,
+            } => DrawCommand::DrawRawTexture {
                 rect: rect.offset(offset),
                 texture,
+                source,
+                color,
             },
             DrawCommand::DrawRect {
                 rect,
@@ -399,13 +409,30 @@ impl Painter {
     }
 
     pub fn draw_raw_texture(&mut self, rect: Rect, texture: &Texture2D) {
+        self.draw_raw_texture_ex(rect, texture, None, Color::new(1., 1., 1., 1.));
+    }
+
+    /// `source` is in pixels of the texture, the whole texture when None.
+    pub fn draw_raw_texture_ex(
+        &mut self,
+        rect: Rect,
+        texture: &Texture2D,
+        source: Option<Rect>,
+        color: Color,
+    ) {
         if self.clipping_zone.is_some_and(|clip| !clip.overlaps(&rect)) {
             return;
         }
 
+        let source = source.map_or(Rect::new(0., 0., 1., 1.), |source| {
+            let (w, h) = (texture.width(), texture.height());
+            Rect::new(source.x / w, source.y / h, source.w / w, source.h / h)
+        });
         self.add_command(DrawCommand::DrawRawTexture {
             rect,
             texture: texture.clone(),
+            source,
+            color,
         });
     }
 
@@ -522,7 +549,7 @@ impl Painter {
         };
 
         let scaled_clipping_zone = self.clipping_zone.map(|rect| {
-            let dpi = miniquad::window::dpi_scale();
+            let dpi = crate::window::dpi_scale();
             Rect::new(rect.x * dpi, rect.y * dpi, rect.w * dpi, rect.h * dpi)
         });
         self.add_command(DrawCommand::Clip { rect: scaled_clipping_zone });
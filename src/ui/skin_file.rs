@@ -0,0 +1,296 @@
+//! Loading a [Skin] from a json description, see [Skin::from_file].
+
+use crate::{
+    color::Color,
+    file::load_file,
+    math::RectOffset,
+    text::{
+        atlas::{Atlas, SpriteKey},
+        Font,
+    },
+    texture::Image,
+    ui::{root_ui, Skin, Style},
+    Error,
+};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// Every field is optional, the missing ones keep the value of the base skin.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct StyleFile {
+    font: Option<String>,
+    font_size: Option<u16>,
+    text_color: Option<String>,
+    text_color_hovered: Option<String>,
+    text_color_clicked: Option<String>,
+    background: Option<String>,
+    background_hovered: Option<String>,
+    background_clicked: Option<String>,
+    // [left, right, top, bottom]
+    background_margin: Option<[f32; 4]>,
+    margin: Option<[f32; 4]>,
+    color: Option<String>,
+    color_inactive: Option<String>,
+    color_hovered: Option<String>,
+    color_clicked: Option<String>,
+    color_selected: Option<String>,
+    color_selected_hovered: Option<String>,
+    reverse_background_z: Option<bool>,
+    #[serde(flatten)]
+    unknown: HashMap<String, Value>,
+}
+
+fn style_mut<'a>(skin: &'a mut Skin, name: &str) -> Option<&'a mut Style> {
+    Some(match name {
+        "label_style" => &mut skin.label_style,
+        "button_style" => &mut skin.button_style,
+        "tabbar_style" => &mut skin.tabbar_style,
+        "tabbar_disabled_style" => &mut skin.tabbar_disabled_style,
+        "combobox_style" => &mut skin.combobox_style,
+        "combobox_popup_style" => &mut skin.combobox_popup_style,
+        "combobox_item_style" => &mut skin.combobox_item_style,
+        "window_style" => &mut skin.window_style,
+        "editbox_style" => &mut skin.editbox_style,
+        "window_titlebar_style" => &mut skin.window_titlebar_style,
+        "scrollbar_style" => &mut skin.scrollbar_style,
+        "scrollbar_handle_style" => &mut skin.scrollbar_handle_style,
+        "checkbox_style" => &mut skin.checkbox_style,
+        "group_style" => &mut skin.group_style,
+        "progress_bar_style" => &mut skin.progress_bar_style,
+        "tooltip_style" => &mut skin.tooltip_style,
+        _ => return None,
+    })
+}
+
+fn value_mut<'a>(skin: &'a mut Skin, name: &str) -> Option<&'a mut f32> {
+    Some(match name {
+        "margin" => &mut skin.margin,
+        "title_height" => &mut skin.title_height,
+        "scroll_width" => &mut skin.scroll_width,
+        "scroll_multiplier" => &mut skin.scroll_multiplier,
+        "tooltip_delay" => &mut skin.tooltip_delay,
+        _ => return None,
+    })
+}
+
+// loads the files the styles refer to, a font used by several styles is loaded once
+struct Loader<'a> {
+    base_path: &'a str,
+    atlas: Arc<Mutex<Atlas>>,
+    fonts: HashMap<String, Arc<Mutex<Font>>>,
+}
+
+impl Loader<'_> {
+    async fn load(&self, style: &str, path: &str) -> Result<(String, Vec<u8>), Error> {
+        let path = if self.base_path.is_empty() {
+            path.to_owned()
+        } else {
+            format!("{}/{path}", self.base_path.trim_end_matches('/'))
+        };
+
+        match load_file(&path).await {
+            Ok(bytes) => Ok((path, bytes)),
+            Err(source) => Err(Error::SkinFileError {
+                style: style.to_owned(),
+                path,
+                source: Box::new(source),
+            }),
+        }
+    }
+
+    async fn background(
+        &self,
+        style: &str,
+        path: Option<String>,
+    ) -> Result<Option<SpriteKey>, Error> {
+        let Some(path) = path else {
+            return Ok(None);
+        };
+        let (path, bytes) = self.load(style, &path).await?;
+        let image =
+            Image::from_file_with_format(&bytes, None).map_err(|source| Error::SkinFileError {
+                style: style.to_owned(),
+                path,
+                source: Box::new(source),
+            })?;
+
+        let mut atlas = self.atlas.lock().unwrap();
+        let id = atlas.new_unique_id();
+        atlas.cache_sprite(id, image);
+        Ok(Some(id))
+    }
+
+    async fn font(&mut self, style: &str, path: &str) -> Result<Arc<Mutex<Font>>, Error> {
+        if let Some(font) = self.fonts.get(path) {
+            return Ok(font.clone());
+        }
+
+        let (full_path, bytes) = self.load(style, path).await?;
+        let font = Font::load_from_bytes(self.atlas.clone(), &bytes).map_err(|source| {
+            Error::SkinFileError {
+                style: style.to_owned(),
+                path: full_path,
+                source: Box::new(source),
+            }
+        })?;
+        let font = Arc::new(Mutex::new(font));
+        self.fonts.insert(path.to_owned(), font.clone());
+        Ok(font)
+    }
+
+    async fn apply(&mut self, style: &mut Style, name: &str, file: StyleFile) -> Result<(), Error> {
+        for field in file.unknown.keys() {
+            crate::logging::warn!("skin: unknown field `{}` in `{}`", field, name);
+        }
+
+        let color = |field: &str, value: Option<String>| match value {
+            None => Ok(None),
+            Some(hex) => Color::from_hex_str(&hex).map(Some).ok_or_else(|| {
+                Error::SkinParseError(format!("`{name}.{field}`: `{hex}` is not a #RRGGBB color"))
+            }),
+        };
+        let margin = |margin: Option<[f32; 4]>| {
+            margin.map(|[left, right, top, bottom]| RectOffset::new(left, right, top, bottom))
+        };
+
+        if let Some(path) = &file.font {
+            style.font = self.font(name, path).await?;
+        }
+        style.font_size = file.font_size.unwrap_or(style.font_size);
+
+        if let Some(background) = self.background(name, file.background).await? {
+            style.background = Some(background);
+        }
+        if let Some(background) = self.background(name, file.background_hovered).await? {
+            style.background_hovered = Some(background);
+        }
+        if let Some(background) = self.background(name, file.background_clicked).await? {
+            style.background_clicked = Some(background);
+        }
+        style.background_margin = margin(file.background_margin).or(style.background_margin);
+        style.margin = margin(file.margin).or(style.margin);
+
+        let overrides = [
+            (&mut style.text_color, "text_color", file.text_color),
+            (
+                &mut style.text_color_hovered,
+                "text_color_hovered",
+                file.text_color_hovered,
+            ),
+            (
+                &mut style.text_color_clicked,
+                "text_color_clicked",
+                file.text_color_clicked,
+            ),
+            (&mut style.color, "color", file.color),
+            (
+                &mut style.color_hovered,
+                "color_hovered",
+                file.color_hovered,
+            ),
+            (
+                &mut style.color_clicked,
+                "color_clicked",
+                file.color_clicked,
+            ),
+            (
+                &mut style.color_selected,
+                "color_selected",
+                file.color_selected,
+            ),
+            (
+                &mut style.color_selected_hovered,
+                "color_selected_hovered",
+                file.color_selected_hovered,
+            ),
+        ];
+        for (target, field, value) in overrides {
+            if let Some(value) = color(field, value)? {
+                *target = value;
+            }
+        }
+        if let Some(value) = color("color_inactive", file.color_inactive)? {
+            style.color_inactive = Some(value);
+        }
+        style.reverse_background_z = file
+            .reverse_background_z
+            .unwrap_or(style.reverse_background_z);
+
+        Ok(())
+    }
+}
+
+impl Skin {
+    /// Load a skin from a json file, see [Skin::from_str] for the format.
+    ///
+    /// Images and fonts are looked up next to the file.
+    pub async fn from_file(path: &str) -> Result<Skin, Error> {
+        let data = load_file(path).await?;
+        let data = String::from_utf8_lossy(&data);
+        let base_path = path.rsplit_once('/').map_or("", |(dir, _)| dir);
+
+        Skin::from_str(&data, base_path).await
+    }
+
+    /// Build a skin from a json description, paths in it are relative to `base_path`.
+    ///
+    /// Every field is optional: the styles and values that are not there
+    /// are taken from the current skin of [root_ui]. A style has the fields of
+    /// [StyleBuilder](crate::ui::StyleBuilder), colors are `#RRGGBB` or `#RRGGBBAA`
+    /// and margins are `[left, right, top, bottom]`.
+    /// Unknown fields are skipped with a warning.
+    ///
+    /// ```json
+    /// {
+    ///     "margin": 4,
+    ///     "button_style": {
+    ///         "background": "button_background.png",
+    ///         "background_hovered": "button_hovered_background.png",
+    ///         "background_margin": [37, 37, 5, 5],
+    ///         "font": "HTOWERT.TTF",
+    ///         "font_size": 40,
+    ///         "text_color": "#B4B464"
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// The images and fonts are cached in the atlas of [root_ui],
+    /// so the skin is meant to be pushed to it.
+    pub async fn from_str(data: &str, base_path: &str) -> Result<Skin, Error> {
+        let fields: Map<String, Value> =
+            serde_json::from_str(data).map_err(|err| Error::SkinParseError(err.to_string()))?;
+
+        let (mut skin, atlas) = {
+            let ui = root_ui();
+            (ui.default_skin(), ui.atlas.clone())
+        };
+        let mut loader = Loader {
+            base_path,
+            atlas,
+            fonts: HashMap::new(),
+        };
+
+        for (name, value) in fields {
+            let parse_error =
+                |err: serde_json::Error| Error::SkinParseError(format!("`{name}`: {err}"));
+
+            if let Some(style) = style_mut(&mut skin, &name) {
+                let file = serde_json::from_value(value).map_err(parse_error)?;
+                loader.apply(style, &name, file).await?;
+            } else if let Some(target) = value_mut(&mut skin, &name) {
+                *target = serde_json::from_value(value).map_err(parse_error)?;
+            } else {
+                crate::logging::warn!("skin: unknown field `{}`", name);
+            }
+        }
+
+        Ok(skin)
+    }
+}
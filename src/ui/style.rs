@@ -371,7 +371,14 @@ pub struct Skin {
     pub label_style: Style,
     pub button_style: Style,
     pub tabbar_style: Style,
+    /// Tabs disabled with [Tabbar::disabled](crate::ui::widgets::Tabbar::disabled).
+    pub tabbar_disabled_style: Style,
     pub combobox_style: Style,
+    /// Background of the open combobox popup.
+    pub combobox_popup_style: Style,
+    /// Variants in the open combobox popup, `color_hovered` is for the highlighted one
+    /// and `color_selected` for the current one.
+    pub combobox_item_style: Style,
     pub window_style: Style,
     pub editbox_style: Style,
     pub window_titlebar_style: Style,
@@ -380,12 +387,16 @@ pub struct Skin {
     pub checkbox_style: Style,
     pub group_style: Style,
     pub progress_bar_style: Style,
+    /// Background, text color and margin of [Ui::tooltip](crate::ui::Ui::tooltip).
+    pub tooltip_style: Style,
 
     pub margin: f32,
     pub title_height: f32,
 
     pub scroll_width: f32,
     pub scroll_multiplier: f32,
+    /// Seconds the widget has to be hovered before its tooltip shows up.
+    pub tooltip_delay: f32,
 }
 
 impl Skin {
@@ -416,6 +427,20 @@ impl Skin {
                     bytes: include_bytes!("combobox.img").to_vec(),
                 })
                 .build(),
+            combobox_popup_style: Style {
+                color: Color::from_rgba(220, 220, 220, 255),
+                ..Style::default(default_font.clone())
+            },
+            combobox_item_style: Style {
+                margin: Some(RectOffset::new(5., 2., 2., 2.)),
+                color: Color::from_rgba(220, 220, 220, 255),
+                color_clicked: Color::from_rgba(170, 170, 170, 255),
+                color_hovered: Color::from_rgba(187, 187, 187, 255),
+                color_selected: Color::from_rgba(204, 204, 204, 255),
+                color_selected_hovered: Color::from_rgba(187, 187, 187, 255),
+                text_color: Color::from_rgba(0, 0, 0, 255),
+                ..Style::default(default_font.clone())
+            },
             tabbar_style: Style {
                 margin: Some(RectOffset::new(2., 2., 2., 2.)),
                 color: Color::from_rgba(220, 220, 220, 235),
@@ -426,6 +451,12 @@ impl Skin {
                 text_color: Color::from_rgba(0, 0, 0, 255),
                 ..Style::default(default_font.clone())
             },
+            tabbar_disabled_style: Style {
+                margin: Some(RectOffset::new(2., 2., 2., 2.)),
+                color: Color::from_rgba(235, 235, 235, 235),
+                text_color: Color::from_rgba(150, 150, 150, 255),
+                ..Style::default(default_font.clone())
+            },
             window_style: StyleBuilder::new(default_font.clone(), atlas.clone())
                 .background_margin(RectOffset::new(1., 1., 1., 1.))
                 .color_inactive(Color::from_rgba(238, 238, 238, 128))
@@ -487,11 +518,18 @@ impl Skin {
                 text_color: Color::from_rgba(0, 0, 0, 255),
                 ..Style::default(default_font.clone())
             },
+            tooltip_style: Style {
+                margin: Some(RectOffset::new(4., 4., 2., 2.)),
+                color: Color::from_rgba(255, 255, 225, 245),
+                text_color: Color::from_rgba(0, 0, 0, 255),
+                ..Style::default(default_font.clone())
+            },
 
             margin: 2.0,
             title_height: 14.0,
             scroll_width: 10.0,
             scroll_multiplier: 3.,
+            tooltip_delay: 0.5,
         }
     }
 }
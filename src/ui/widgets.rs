@@ -1,31 +1,40 @@
 mod button;
 mod checkbox;
+mod color_picker;
 mod combobox;
 mod drag;
+mod drag_and_drop;
 mod editbox;
 mod group;
 mod input;
 mod label;
+mod list;
 mod popup;
 mod progress_bar;
 mod separator;
 mod slider;
 mod tabbar;
 mod texture;
+mod tooltip;
 mod tree_node;
 mod window;
 
 pub use button::Button;
 pub use checkbox::Checkbox;
+pub use color_picker::ColorPicker;
 pub use combobox::ComboBox;
+pub(crate) use drag_and_drop::DragAndDrop;
 pub use editbox::Editbox;
 pub use group::{Group, GroupToken};
 pub use input::InputText;
 pub use label::Label;
+pub use list::List;
 pub use popup::Popup;
 pub use progress_bar::ProgressBar;
 pub use slider::Slider;
-pub use tabbar::Tabbar;
-pub use texture::Texture;
+pub use tabbar::{Tabbar, Tabs};
+pub use texture::{Texture, TextureButton};
+pub use tooltip::Tooltip;
+pub(crate) use tooltip::Tooltips;
 pub use tree_node::{TreeNode, TreeNodeToken};
 pub use window::{Window, WindowToken};
@@ -0,0 +1,251 @@
+use crate::{
+    color::{hsv_to_rgb, rgb_to_hsv, Color},
+    math::{vec2, Rect, Vec2},
+    text::atlas::{Atlas, SpriteKey},
+    texture::Image,
+    ui::{widgets::Editbox, Id, Layout, Ui},
+};
+
+// the gradients are drawn once into the UI atlas, next to the white pixel at Id(0)
+const HUE: SpriteKey = SpriteKey::Id(1);
+const SATURATION: SpriteKey = SpriteKey::Id(2);
+const VALUE: SpriteKey = SpriteKey::Id(3);
+const ALPHA: SpriteKey = SpriteKey::Id(4);
+
+const HUE_WIDTH: f32 = 16.;
+const ALPHA_HEIGHT: f32 = 12.;
+const ROW_HEIGHT: f32 = 20.;
+const GAP: f32 = 4.;
+
+#[derive(Default, Clone, Copy)]
+struct PickerState {
+    hue: f32,
+    saturation: f32,
+    value: f32,
+    alpha: f32,
+    // the color the hsv above were taken from, to notice the color changed elsewhere
+    color: Option<Color>,
+    dragging: Option<Area>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Area {
+    SaturationValue,
+    Hue,
+    Alpha,
+}
+
+/// Saturation/value square, hue strip and alpha strip, with a swatch
+/// and an editbox for the `#RRGGBB` or `#RRGGBBAA` value.
+pub struct ColorPicker {
+    id: Id,
+    position: Option<Vec2>,
+    size: Vec2,
+}
+
+impl ColorPicker {
+    pub const fn new(id: Id) -> ColorPicker {
+        ColorPicker {
+            id,
+            position: None,
+            size: vec2(200., 220.),
+        }
+    }
+
+    pub const fn position(self, position: Vec2) -> Self {
+        ColorPicker {
+            position: Some(position),
+            ..self
+        }
+    }
+
+    /// Default is 200x220.
+    pub const fn size(self, size: Vec2) -> Self {
+        ColorPicker { size, ..self }
+    }
+
+    /// Returns true if the color was changed this frame.
+    pub fn ui(self, ui: &mut Ui, color: &mut Color) -> bool {
+        cache_gradients(&mut ui.atlas.lock().unwrap());
+
+        let context = ui.get_active_window_context();
+
+        let pos = context.window.cursor.fit(
+            self.size,
+            self.position.map_or(Layout::Vertical, Layout::Free),
+        );
+
+        let square_size = vec2(
+            self.size.x - HUE_WIDTH - GAP,
+            self.size.y - ALPHA_HEIGHT - ROW_HEIGHT - GAP * 2.,
+        );
+        let square = Rect::new(pos.x, pos.y, square_size.x, square_size.y);
+        let hue_strip = Rect::new(square.right() + GAP, pos.y, HUE_WIDTH, square_size.y);
+        let alpha_strip = Rect::new(pos.x, square.bottom() + GAP, self.size.x, ALPHA_HEIGHT);
+        let row_y = alpha_strip.bottom() + GAP;
+        let swatch = Rect::new(pos.x, row_y, ROW_HEIGHT * 2., ROW_HEIGHT);
+
+        let state = context
+            .storage_any
+            .get_or_default::<PickerState>(hash!(self.id, "state"));
+        if state.color != Some(*color) {
+            let (hue, saturation, value) = rgb_to_hsv(*color);
+            // hue and saturation are lost on grays and black, keep the old ones then
+            if value > 0. {
+                if saturation > 0. {
+                    state.hue = hue;
+                }
+                state.saturation = saturation;
+            }
+            state.value = value;
+            state.alpha = color.a;
+        }
+
+        let mouse = context.input.mouse_position;
+        if state.dragging.is_none() && context.input.click_down() {
+            state.dragging = [
+                (square, Area::SaturationValue),
+                (hue_strip, Area::Hue),
+                (alpha_strip, Area::Alpha),
+            ]
+            .into_iter()
+            .find(|(rect, _)| rect.contains(mouse))
+            .map(|(_, area)| area);
+            context.input.cursor_grabbed |= state.dragging.is_some();
+        }
+        if state.dragging.is_some() && !context.input.is_mouse_down {
+            state.dragging = None;
+            context.input.cursor_grabbed = false;
+        }
+
+        let ratio = |value: f32, start: f32, len: f32| ((value - start) / len).clamp(0., 1.);
+        match state.dragging {
+            Some(Area::SaturationValue) => {
+                state.saturation = ratio(mouse.x, square.x, square.w);
+                state.value = 1. - ratio(mouse.y, square.y, square.h);
+            }
+            Some(Area::Hue) => state.hue = ratio(mouse.y, hue_strip.y, hue_strip.h),
+            Some(Area::Alpha) => state.alpha = ratio(mouse.x, alpha_strip.x, alpha_strip.w),
+            None => {}
+        }
+
+        let mut changed = false;
+        if state.dragging.is_some() {
+            let picked =
+                hsv_to_rgb(state.hue, state.saturation, state.value).with_alpha(state.alpha);
+            changed = picked != *color;
+            *color = picked;
+        }
+        state.color = Some(*color);
+        let PickerState {
+            hue,
+            saturation,
+            value,
+            alpha,
+            ..
+        } = *state;
+
+        let painter = &mut context.window.painter;
+        let white = Color::new(1., 1., 1., 1.);
+        let black = Color::new(0., 0., 0., 1.);
+
+        painter.draw_rect(square, None, hsv_to_rgb(hue, 1., 1.));
+        painter.draw_sprite(square, SATURATION, white, None);
+        painter.draw_sprite(square, VALUE, white, None);
+        let marker = vec2(
+            square.x + saturation * square.w,
+            square.y + (1. - value) * square.h,
+        );
+        painter.draw_rect(
+            Rect::new(marker.x - 3., marker.y - 3., 6., 6.),
+            if value > 0.5 { black } else { white },
+            None,
+        );
+
+        painter.draw_sprite(hue_strip, HUE, white, None);
+        let hue_y = hue_strip.y + hue * hue_strip.h;
+        painter.draw_rect(
+            Rect::new(hue_strip.x - 1., hue_y - 1., hue_strip.w + 2., 3.),
+            black,
+            None,
+        );
+
+        painter.draw_rect(alpha_strip, None, Color::new(0.8, 0.8, 0.8, 1.));
+        painter.draw_sprite(alpha_strip, ALPHA, color.with_alpha(1.), None);
+        let alpha_x = alpha_strip.x + alpha * alpha_strip.w;
+        painter.draw_rect(
+            Rect::new(alpha_x - 1., alpha_strip.y - 1., 3., alpha_strip.h + 2.),
+            black,
+            None,
+        );
+
+        painter.draw_rect(swatch, black, *color);
+
+        let hex_id = hash!(self.id, "hex");
+        let mut hex = std::mem::take(context.storage_any.get_or_default::<String>(hex_id));
+        if !context.input_focused(hex_id) {
+            hex = to_hex(*color);
+        }
+
+        Editbox::new(hex_id, vec2(self.size.x - swatch.w - GAP, ROW_HEIGHT))
+            .position(vec2(swatch.right() + GAP, row_y))
+            .multiline(false)
+            .filter(&|character| character.is_ascii_hexdigit() || character == '#')
+            .ui(ui, &mut hex);
+
+        let context = ui.get_active_window_context();
+        if context.input_focused(hex_id) {
+            if let Some(typed) = Color::from_hex_str(&hex) {
+                changed |= typed != *color;
+                *color = typed;
+            }
+        }
+        *context.storage_any.get_or_default::<String>(hex_id) = hex;
+
+        changed
+    }
+}
+
+fn to_hex(color: Color) -> String {
+    let hex = color.to_hex_rgba();
+
+    if hex & 0xFF == 0xFF {
+        format!("#{:06X}", hex >> 8)
+    } else {
+        format!("#{hex:08X}")
+    }
+}
+
+fn cache_gradients(atlas: &mut Atlas) {
+    if atlas.get(HUE).is_some() {
+        return;
+    }
+
+    let gradient = |width: u16, height: u16, f: &dyn Fn(f32) -> Color| {
+        let mut image = Image::gen_image_color(width, height, Color::new(0., 0., 0., 0.));
+        for n in 0..width.max(height) as u32 {
+            let color = f(n as f32 / 255.);
+            if width > height {
+                image.set_pixel(n, 0, color);
+            } else {
+                image.set_pixel(0, n, color);
+            }
+        }
+        image
+    };
+
+    atlas.cache_sprite(HUE, gradient(1, 256, &|t| hsv_to_rgb(t, 1., 1.)));
+    atlas.cache_sprite(
+        SATURATION,
+        gradient(256, 1, &|t| Color::new(1., 1., 1., 1. - t)),
+    );
+    atlas.cache_sprite(VALUE, gradient(1, 256, &|t| Color::new(0., 0., 0., t)));
+    atlas.cache_sprite(ALPHA, gradient(256, 1, &|t| Color::new(1., 1., 1., t)));
+}
+
+impl Ui {
+    /// Returns true if the color was changed this frame.
+    pub fn color_picker(&mut self, id: Id, color: &mut Color) -> bool {
+        ColorPicker::new(id).ui(self, color)
+    }
+}
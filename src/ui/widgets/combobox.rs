@@ -1,6 +1,6 @@
 use crate::{
     math::{vec2, Rect, Vec2},
-    ui::{ElementState, Id, Layout, Ui, UiContent},
+    ui::{ElementState, Id, Key, KeyCode, Layout, Ui, UiContent},
 };
 
 pub struct ComboBox<'a, 'b, 'c> {
@@ -8,6 +8,7 @@ pub struct ComboBox<'a, 'b, 'c> {
     label: &'a str,
     variants: &'b [&'c str],
     ratio: f32,
+    max_visible: usize,
 }
 
 impl<'a, 'b, 'c> ComboBox<'a, 'b, 'c> {
@@ -17,6 +18,7 @@ impl<'a, 'b, 'c> ComboBox<'a, 'b, 'c> {
             label: "",
             variants,
             ratio: 0.5,
+            max_visible: 8,
         }
     }
 
@@ -26,13 +28,30 @@ impl<'a, 'b, 'c> ComboBox<'a, 'b, 'c> {
             variants: self.variants,
             label,
             ratio: self.ratio,
+            max_visible: self.max_visible,
         }
     }
 
     pub const fn ratio(self, ratio: f32) -> Self {
         Self { ratio, ..self }
     }
+
+    /// How many variants the popup lists at once, it scrolls through the rest.
+    /// Default is 8.
+    pub const fn max_visible(self, max_visible: usize) -> Self {
+        Self {
+            max_visible,
+            ..self
+        }
+    }
+
+    /// Draws the closed box with the selected variant, and the popup to pick another one
+    /// while it is open. Up, Down and Enter pick a variant in the open popup, Escape closes it.
+    ///
+    /// `data` is clamped to the variants, if it got out of range since the last frame.
     pub fn ui(self, ui: &mut Ui, data: &mut usize) -> usize {
+        *data = (*data).min(self.variants.len().saturating_sub(1));
+
         let mut context = ui.get_active_window_context();
 
         let line_height = context.style.label_style.font_size;
@@ -48,16 +67,6 @@ impl<'a, 'b, 'c> ComboBox<'a, 'b, 'c> {
 
         let active_area_w = size.x * self.ratio;
 
-        let text_measures = {
-            let font = &mut *context.style.label_style.font.lock().unwrap();
-            let font_size = context.style.label_style.font_size;
-
-            context
-                .window
-                .painter
-                .label_size(self.label, None, font, font_size)
-        };
-
         let clickable_rect = Rect::new(pos.x, pos.y, active_area_w, size.y);
 
         let (hovered, _) = context.register_click_intention(clickable_rect);
@@ -86,7 +95,7 @@ impl<'a, 'b, 'c> ComboBox<'a, 'b, 'c> {
             &context.style.label_style,
             pos,
             vec2(combobox_area_w, size.y),
-            &UiContent::Label(self.variants[*data].into()),
+            &UiContent::Label(self.variants.get(*data).copied().unwrap_or("").into()),
             ElementState {
                 focused: context.focused,
                 hovered,
@@ -107,77 +116,134 @@ impl<'a, 'b, 'c> ComboBox<'a, 'b, 'c> {
             );
         }
 
-        let modal_size = Vec2::new(active_area_w, self.variants.len() as f32 * size.y);
+        let visible = self.variants.len().min(self.max_visible.max(1));
+        let modal_size = Vec2::new(active_area_w, visible as f32 * size.y);
         let modal_rect = Rect::new(pos.x, pos.y + size.y, modal_size.x, modal_size.y);
 
-        if !*state && context.focused && hovered && context.input.click_down {
+        let mut just_opened = false;
+        if !*state
+            && !self.variants.is_empty()
+            && context.focused
+            && hovered
+            && context.input.click_down
+        {
             *state = true;
+            just_opened = true;
         } else if *state
             && (context.input.escape
-                || context.input.enter
                 || (!modal_rect.contains(context.input.mouse_position) && context.input.click_down))
         {
             *state = false;
         }
 
-        if *state {
-            let context = ui.begin_modal(
-                hash!("combobox", self.id),
-                pos + Vec2::new(0., 20.),
-                modal_size,
-            );
+        if !*state || self.variants.is_empty() {
+            return *data;
+        }
 
-            let state = context
-                .storage_any
-                .get_or_default::<bool>(hash!(self.id, "combobox_state"));
-
-            for (i, variant) in self.variants.iter().enumerate() {
-                let rect = Rect::new(
-                    pos.x + 5.0,
-                    pos.y + i as f32 * size.y + size.y,
-                    active_area_w - 5.0,
-                    size.y,
-                );
-                let hovered = rect.contains(context.input.mouse_position);
-
-                let color = context.style.combobox_style.color(ElementState {
-                    focused: context.focused,
-                    hovered,
-                    clicked: hovered && context.input.is_mouse_down,
-                    selected: false,
-                });
-
-                context.window.painter.draw_rect(
-                    rect, //context.style.combobox_variant_border(hovered, *data == i),
-                    color,
-                    // context
-                    //     .style
-                    //     .combobox_variant_background(hovered, *data == i),
-                    color,
-                );
-
-                let font = &mut *context.style.label_style.font.lock().unwrap();
-                let font_size = context.style.label_style.font_size;
-
-                context.window.painter.draw_label(
-                    variant,
-                    Vec2::new(
-                        pos.x + 7.,
-                        pos.y + i as f32 * size.y + size.y + 2.0 + text_measures.offset_y,
-                    ),
-                    context.style.combobox_style.text_color,
-                    font,
-                    font_size,
-                );
-
-                if hovered && context.input.click_up {
-                    *data = i;
-                    *state = false;
+        // the variant under the mouse or picked with the arrows, to be selected with Enter
+        let highlighted = context
+            .storage_any
+            .get_or_default::<usize>(hash!(self.id, "combobox_highlighted"));
+        if just_opened {
+            *highlighted = *data;
+        }
+        let mut keyboard_moved = false;
+        for character in &context.input.input_buffer {
+            match character.key {
+                Key::KeyCode(KeyCode::Up) => {
+                    *highlighted = highlighted.saturating_sub(1);
+                    keyboard_moved = true;
                 }
+                Key::KeyCode(KeyCode::Down) => {
+                    *highlighted = (*highlighted + 1).min(self.variants.len() - 1);
+                    keyboard_moved = true;
+                }
+                _ => {}
+            }
+        }
+        let mut highlighted_variant = (*highlighted).min(self.variants.len() - 1);
+        let mut picked = context.input.enter.then_some(highlighted_variant);
+
+        let mut context =
+            ui.begin_modal(hash!("combobox", self.id), modal_rect.point(), modal_size);
+
+        // the popup is shared by all the comboboxes, start from the selected variant
+        let scroll = &mut context.window.cursor.scroll;
+        let highlighted_top = highlighted_variant as f32 * size.y;
+        if just_opened {
+            scroll.inner_rect_previous_frame =
+                Rect::new(0., 0., modal_size.x, self.variants.len() as f32 * size.y);
+            scroll.rect.y = (highlighted_top + size.y - modal_size.y).max(0.);
+        } else if keyboard_moved {
+            if highlighted_top < scroll.rect.y {
+                scroll.scroll_to(highlighted_top);
+            } else if highlighted_top + size.y > scroll.rect.y + modal_size.y {
+                scroll.scroll_to(highlighted_top + size.y - modal_size.y);
             }
-            ui.end_modal();
         }
 
+        context.window.painter.draw_element_background(
+            &context.style.combobox_popup_style,
+            modal_rect.point(),
+            modal_size,
+            ElementState {
+                focused: true,
+                ..Default::default()
+            },
+        );
+
+        context.scroll_area();
+
+        let item_w = context.window.content_rect().w;
+        for (i, variant) in self.variants.iter().enumerate() {
+            let item_pos = context
+                .window
+                .cursor
+                .fit(vec2(item_w, size.y), Layout::Vertical);
+            let rect = Rect::new(item_pos.x, item_pos.y, item_w, size.y);
+            let hovered = rect.contains(context.input.mouse_position)
+                && modal_rect.contains(context.input.mouse_position);
+
+            if hovered && !keyboard_moved {
+                highlighted_variant = i;
+            }
+            if hovered && context.input.click_up {
+                picked = Some(i);
+            }
+
+            let element_state = ElementState {
+                focused: true,
+                hovered: i == highlighted_variant,
+                clicked: hovered && context.input.is_mouse_down,
+                selected: i == *data,
+            };
+            context.window.painter.draw_element_background(
+                &context.style.combobox_item_style,
+                item_pos,
+                rect.size(),
+                element_state,
+            );
+            context.window.painter.draw_element_content(
+                &context.style.combobox_item_style,
+                item_pos,
+                rect.size(),
+                &UiContent::Label((*variant).into()),
+                element_state,
+            );
+        }
+
+        *context
+            .storage_any
+            .get_or_default::<usize>(hash!(self.id, "combobox_highlighted")) = highlighted_variant;
+        if let Some(picked) = picked {
+            *data = picked;
+            *context
+                .storage_any
+                .get_or_default::<bool>(hash!(self.id, "combobox_state")) = false;
+        }
+
+        ui.end_modal();
+
         *data
     }
 }
@@ -0,0 +1,198 @@
+use crate::{
+    math::Vec2,
+    text::atlas::Atlas,
+    ui::{
+        render::{DrawCommand, Painter},
+        Id, Ui, Window,
+    },
+};
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// The payload carried from a [Ui::drag_source] to a [Ui::drop_target].
+pub(crate) struct DragAndDrop {
+    /// A copy of the dragged source under the mouse, drawn on top of all the windows.
+    pub(crate) ghost: Painter,
+    drag: Option<Dragged>,
+    // a drag of a source that is not drawn anymore gets cancelled
+    source_drawn: bool,
+    hovered_target: Option<Id>,
+    hovered_target_previous_frame: Option<Id>,
+}
+
+#[derive(Clone, Copy)]
+struct Dragged {
+    source: Id,
+    payload: u64,
+    origin: Vec2,
+    // the mouse moved far enough from the origin to tell the drag from a click
+    started: bool,
+}
+
+impl DragAndDrop {
+    pub(crate) const fn new(atlas: Arc<Mutex<Atlas>>) -> DragAndDrop {
+        DragAndDrop {
+            ghost: Painter::new(atlas),
+            drag: None,
+            source_drawn: false,
+            hovered_target: None,
+            hovered_target_previous_frame: None,
+        }
+    }
+
+    pub(crate) fn new_frame(&mut self, is_mouse_down: bool) {
+        self.ghost.clear();
+
+        // the mouse was released over no target
+        if !is_mouse_down || !self.source_drawn {
+            self.drag = None;
+        }
+        self.source_drawn = false;
+
+        self.hovered_target_previous_frame = self.hovered_target.take();
+    }
+}
+
+fn window_commands(windows: &HashMap<Id, Window>, id: Id, commands: &mut Vec<DrawCommand>) {
+    let window = &windows[&id];
+
+    commands.extend_from_slice(&window.painter.commands);
+    for child in &window.childs {
+        window_commands(windows, *child, commands);
+    }
+}
+
+impl Ui {
+    /// Makes the widgets drawn by `f` draggable, dropping them on a [Ui::drop_target]
+    /// hands `payload` to it.
+    ///
+    /// While dragged, a copy of the widgets follows the mouse.
+    /// Releasing the mouse outside of any target, or the window losing focus,
+    /// cancels the drag.
+    ///
+    /// Returns true while this source is being dragged.
+    ///
+    /// ```ignore
+    /// ui.drag_source(hash!("item", n), n as u64, |ui| {
+    ///     ui.button(None, item_name);
+    /// });
+    /// ```
+    pub fn drag_source<F: FnOnce(&mut Ui)>(&mut self, id: Id, payload: u64, f: F) -> bool {
+        let context = self.get_active_window_context();
+        let first_command = context.window.painter.commands.len();
+        let first_child = context.window.childs.len();
+
+        let hovered = self.bounds_hovered(f);
+
+        let context = self.get_active_window_context();
+        let focused = context.focused;
+        let mouse_position = context.input.mouse_position;
+        let click_down = context.input.click_down();
+
+        let drag_and_drop = &mut self.drag_and_drop;
+        if drag_and_drop.drag.is_none() && focused && hovered && click_down {
+            drag_and_drop.drag = Some(Dragged {
+                source: id,
+                payload,
+                origin: mouse_position,
+                started: false,
+            });
+        }
+
+        let Some(mut dragged) = drag_and_drop.drag.filter(|dragged| dragged.source == id) else {
+            return false;
+        };
+        if !focused {
+            drag_and_drop.drag = None;
+            return false;
+        }
+        drag_and_drop.source_drawn = true;
+        dragged.started |= mouse_position.distance(dragged.origin) > 5.;
+        drag_and_drop.drag = Some(dragged);
+        if !dragged.started {
+            return false;
+        }
+
+        // everything `f` has drawn, with the groups it has opened
+        let context = self.get_active_window_context();
+        let mut commands = context.window.painter.commands[first_command..].to_vec();
+        let childs = context.window.childs[first_child..].to_vec();
+        for child in childs {
+            window_commands(&self.windows, child, &mut commands);
+        }
+
+        let offset = mouse_position - dragged.origin;
+        let ghost = &mut self.drag_and_drop.ghost;
+        ghost.clip(None);
+        ghost.commands.extend(
+            commands
+                .iter()
+                .filter(|command| !matches!(command, DrawCommand::Clip { .. }))
+                .map(|command| command.offset(offset)),
+        );
+
+        true
+    }
+
+    /// Widgets drawn by `f` accept the payloads dropped on them from a [Ui::drag_source].
+    ///
+    /// Returns the payload on the frame it was dropped.
+    pub fn drop_target<F: FnOnce(&mut Ui)>(&mut self, id: Id, f: F) -> Option<u64> {
+        let hovered = self.bounds_hovered(f);
+        let click_up = self.get_active_window_context().input.click_up();
+
+        let drag_and_drop = &mut self.drag_and_drop;
+        let dragged = drag_and_drop.drag.filter(|dragged| dragged.started)?;
+        if !hovered {
+            return None;
+        }
+
+        drag_and_drop.hovered_target = Some(id);
+        if click_up {
+            drag_and_drop.drag = None;
+            return Some(dragged.payload);
+        }
+        None
+    }
+
+    /// The payload being dragged, to draw something of its own under the mouse
+    /// or to highlight the targets.
+    pub fn drag_payload(&self) -> Option<u64> {
+        self.drag_and_drop
+            .drag
+            .filter(|dragged| dragged.started)
+            .map(|dragged| dragged.payload)
+    }
+
+    /// Returns true if a payload was dragged over the target with this id on the last frame.
+    pub fn drop_target_hovered(&self, id: Id) -> bool {
+        self.drag_payload().is_some()
+            && self.drag_and_drop.hovered_target_previous_frame == Some(id)
+    }
+
+    // runs `f` and tells if the mouse is over the widgets it placed
+    fn bounds_hovered<F: FnOnce(&mut Ui)>(&mut self, f: F) -> bool {
+        let outer = self.get_active_window_context().window.cursor.bounds.take();
+        f(self);
+
+        let context = self.get_active_window_context();
+        let cursor = &mut context.window.cursor;
+        let bounds = cursor.bounds;
+        cursor.bounds = match (outer, bounds) {
+            (Some(outer), Some(bounds)) => Some(outer.combine_with(bounds)),
+            (outer, bounds) => outer.or(bounds),
+        };
+
+        let mouse_position = context.input.mouse_position;
+        context.input.window_active
+            && bounds.is_some_and(|bounds| bounds.contains(mouse_position))
+            && context
+                .window
+                .painter
+                .clipping_zone
+                .is_none_or(|clip| clip.contains(mouse_position))
+    }
+}
@@ -14,6 +14,7 @@ pub struct Editbox<'a> {
     pos: Option<Vec2>,
     password: bool,
     margin: Option<Vec2>,
+    word_wrap: bool,
 }
 
 mod text_editor;
@@ -33,6 +34,7 @@ impl<'a> Editbox<'a> {
             pos: None,
             password: false,
             margin: None,
+            word_wrap: false,
         }
     }
 
@@ -68,6 +70,7 @@ impl<'a> Editbox<'a> {
             password: self.password,
             filter: Some(filter),
             margin: self.margin,
+            word_wrap: self.word_wrap,
         }
     }
 
@@ -78,6 +81,13 @@ impl<'a> Editbox<'a> {
         }
     }
 
+    /// Break the lines too long for the editbox width, between words when possible.
+    /// Only for multiline editboxes. Up and Down still move the cursor by the lines
+    /// of the text, as if it was not wrapped.
+    pub const fn word_wrap(self, word_wrap: bool) -> Self {
+        Editbox { word_wrap, ..self }
+    }
+
     fn apply_keyboard_input(
         &self,
         input_buffer: &mut Vec<InputCharacter>,
@@ -309,8 +319,8 @@ impl<'a> Editbox<'a> {
             .storage_any
             .get_or_default::<EditboxState>(hash!(self.id, "cursor"));
 
-        // if text changed outside, than the selection range should be clamped
-        state.clamp_selection(&text_vec);
+        // if text changed outside, than the cursor and the selection should be clamped
+        state.sync_text(&text_vec);
 
         if self.select_all {
             state.select_all(&text_vec);
@@ -319,10 +329,6 @@ impl<'a> Editbox<'a> {
         if let Some(selected) = state.selected_text(&text_vec) {
             *context.clipboard_selection = selected.iter().collect();
         }
-        // in case the string was updated outside of editbox
-        if state.cursor > text_vec.len() as u32 {
-            state.cursor = text_vec.len() as u32;
-        }
 
         let input_focused = context.input_focus.is_some_and(|id| id == self.id) && context.focused;
 
@@ -352,6 +358,7 @@ impl<'a> Editbox<'a> {
                 state,
             );
         }
+        let content_height = state.content_height;
         // draw rect in parent window
 
         let text_color = context.style.editbox_style.text_color;
@@ -387,10 +394,8 @@ impl<'a> Editbox<'a> {
 
         let line_height = context.style.editbox_style.font_size as f32;
 
-        let size = vec2(
-            150.,
-            line_height * text_vec.iter().filter(|c| **c == '\n').count() as f32,
-        );
+        // the text is laid out below, the height from the last frame is good enough
+        let size = vec2(150., content_height);
 
         let margin = self.margin.unwrap_or(vec2(2., 2.));
         let pos = context.window.cursor.fit(size, Layout::Free(margin));
@@ -399,6 +404,9 @@ impl<'a> Editbox<'a> {
 
         context.scroll_area();
 
+        let wrap_width = (self.word_wrap && self.multiline)
+            .then(|| context.window.content_rect().w - margin.x * 2.);
+
         context.window.painter.clip(context.window.content_rect());
 
         let state = context
@@ -407,6 +415,7 @@ impl<'a> Editbox<'a> {
 
         let mut x = LEFT_MARGIN;
         let mut y = 0.;
+        let mut caret_y = 0.;
         let mut clicked = false;
 
         for (n, character) in text_vec
@@ -421,18 +430,46 @@ impl<'a> Editbox<'a> {
                 character
             };
 
+            let mut font = context.style.editbox_style.font.lock().unwrap();
             let font_size = context.style.editbox_style.font_size;
-            if n == state.cursor as usize && input_focused {
-                // caret
-                context.window.painter.draw_rect(
-                    Rect::new(pos.x + x, pos.y + y + 2., 2., font_size as f32 - 5.),
-                    text_color,
-                    None,
-                );
+
+            if let Some(wrap_width) = wrap_width {
+                // a whole word goes to the next line, unless it is longer than a line by itself
+                let word_start = n == 0 || text_vec[n - 1].is_whitespace();
+                let width = if word_start {
+                    text_vec[n..]
+                        .iter()
+                        .take_while(|character| !character.is_whitespace())
+                        .map(|&character| {
+                            context
+                                .window
+                                .painter
+                                .character_advance(character, &font, font_size)
+                        })
+                        .sum()
+                } else {
+                    context
+                        .window
+                        .painter
+                        .character_advance(character, &font, font_size)
+                };
+                if !character.is_whitespace() && x > LEFT_MARGIN && x + width > wrap_width {
+                    y += line_height;
+                    x = LEFT_MARGIN;
+                }
             }
 
-            let mut font = context.style.editbox_style.font.lock().unwrap();
-            let font_size = context.style.editbox_style.font_size;
+            if n == state.cursor as usize {
+                caret_y = y;
+                if input_focused {
+                    // caret
+                    context.window.painter.draw_rect(
+                        Rect::new(pos.x + x, pos.y + y + 2., 2., font_size as f32 - 5.),
+                        text_color,
+                        None,
+                    );
+                }
+            }
 
             let mut advance = 1.5; // 1.5 - hack to make cursor on newlines visible
 
@@ -511,6 +548,21 @@ impl<'a> Editbox<'a> {
             state.click_up(&text_vec);
         }
 
+        state.content_height = y + line_height;
+        state.remember_text(&text_vec);
+
+        // keep the caret in view while typing or moving it with the keyboard
+        if edited {
+            let scroll = &mut context.window.cursor.scroll;
+            let caret_top = margin.y + caret_y;
+            let caret_bottom = caret_top + line_height;
+            if caret_top < scroll.rect.y {
+                scroll.scroll_to(caret_top);
+            } else if caret_bottom > scroll.rect.y + scroll.rect.h {
+                scroll.scroll_to(caret_bottom - scroll.rect.h);
+            }
+        }
+
         let context = ui.get_active_window_context();
 
         context.window.painter.clip(None);
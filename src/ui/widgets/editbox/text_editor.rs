@@ -162,11 +162,38 @@ pub struct EditboxState {
     pub last_click_time: f32,
     pub last_click: u32,
     pub selection: Option<(u32, u32)>,
+    /// Height of the laid out text on the last frame, for the scroll area.
+    pub content_height: f32,
     undo_stack: Vec<Box<dyn Command>>,
     redo_stack: Vec<Box<dyn Command>>,
+    text_hash: u64,
 }
 
 impl EditboxState {
+    fn hash_text(text: &[char]) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        text.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Clamps the cursor and the selection to the text, and forgets the undo history
+    /// if the text got changed outside of the editbox since [Self::remember_text]:
+    /// the recorded edits would apply to the wrong characters.
+    pub fn sync_text(&mut self, text: &[char]) {
+        if Self::hash_text(text) != self.text_hash {
+            self.undo_stack.clear();
+            self.redo_stack.clear();
+        }
+        self.clamp_selection(text);
+        self.cursor = self.cursor.min(text.len() as u32);
+    }
+
+    pub fn remember_text(&mut self, text: &[char]) {
+        self.text_hash = Self::hash_text(text);
+    }
+
     pub fn clamp_selection(&mut self, text: &[char]) {
         if let Some((ref mut start, ref mut end)) = &mut self.selection {
             if *start >= text.len() as u32 {
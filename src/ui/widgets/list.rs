@@ -0,0 +1,127 @@
+use crate::{
+    math::{Rect, Vec2},
+    ui::{ElementState, Id, Layout, Ui},
+};
+
+/// A scrollable list of rows that lays out and draws only the visible ones,
+/// for lists too long for a [Group](super::Group).
+///
+/// All the rows are `row_height` high, rows taller than that overlap the next one.
+#[derive(Debug, Clone)]
+pub struct List {
+    id: Id,
+    position: Option<Vec2>,
+    size: Vec2,
+    row_height: f32,
+    scroll_to: Option<usize>,
+}
+
+impl List {
+    pub const fn new(id: Id, size: Vec2) -> List {
+        List {
+            id,
+            position: None,
+            size,
+            row_height: 24.,
+            scroll_to: None,
+        }
+    }
+
+    pub const fn position(self, position: Vec2) -> List {
+        List {
+            position: Some(position),
+            ..self
+        }
+    }
+
+    /// Default is 24.
+    pub const fn row_height(self, row_height: f32) -> List {
+        List { row_height, ..self }
+    }
+
+    /// Scroll the list so the row is at the top, or as close as the list allows.
+    pub const fn scroll_to(self, row: usize) -> List {
+        List {
+            scroll_to: Some(row),
+            ..self
+        }
+    }
+
+    /// Calls `f` with the index of every row that is at least partially visible,
+    /// with the UI cursor at the top of the row.
+    pub fn ui<F: FnMut(&mut Ui, usize)>(self, ui: &mut Ui, row_count: usize, mut f: F) {
+        let parent = ui.get_active_window_context();
+
+        let parent_rect = parent.window.content_rect();
+
+        parent.window.childs.push(self.id);
+
+        let pos = parent.window.cursor.fit(
+            self.size,
+            self.position.map_or(Layout::Vertical, Layout::Free),
+        );
+        let rect = Rect::new(pos.x, pos.y, self.size.x, self.size.y);
+        let parent_id = Some(parent.window.id);
+
+        let mut context = ui.begin_window(self.id, parent_id, pos, self.size, false, false);
+
+        let row_height = self.row_height.max(1.);
+        let top = context.window.cursor.start_y;
+        let scroll = &mut context.window.cursor.scroll;
+
+        // the skipped rows are never laid out, so the scroll area is told about all of them
+        scroll.inner_rect_previous_frame = Rect::new(
+            0.,
+            0.,
+            scroll.inner_rect_previous_frame.w,
+            top * 2. + row_count as f32 * row_height,
+        );
+        if let Some(row) = self.scroll_to {
+            scroll.scroll_to(top + row as f32 * row_height);
+        }
+
+        context.window.painter.clip(parent_rect);
+
+        context.scroll_area();
+
+        let clip_rect = context.window.content_rect();
+        context.window.painter.clip(clip_rect);
+        context.window.painter.draw_rect(
+            rect,
+            context.style.group_style.color(ElementState {
+                focused: context.focused,
+                ..Default::default()
+            }),
+            None,
+        );
+
+        let scroll_y = context.window.cursor.scroll.rect.y;
+        let visible_height = context.window.cursor.scroll.rect.h;
+        let first = ((scroll_y - top) / row_height).floor().max(0.) as usize;
+        let last = (((scroll_y + visible_height - top) / row_height)
+            .ceil()
+            .max(0.) as usize)
+            .min(row_count);
+
+        for row in first..last {
+            let context = ui.get_active_window_context();
+            let cursor = &mut context.window.cursor;
+            cursor.x = cursor.start_x;
+            cursor.y = top + row as f32 * row_height;
+            cursor.max_row_y = 0.;
+
+            f(ui, row);
+        }
+
+        let context = ui.get_active_window_context();
+        context.window.painter.clip(None);
+
+        ui.end_window();
+    }
+}
+
+impl Ui {
+    pub fn list<F: FnMut(&mut Ui, usize)>(&mut self, id: Id, size: Vec2, row_count: usize, f: F) {
+        List::new(id, size).ui(self, row_count, f)
+    }
+}
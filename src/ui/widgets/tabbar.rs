@@ -1,13 +1,21 @@
 use crate::{
     math::{vec2, Rect, Vec2},
-    ui::{ElementState, Id, Layout, Ui, UiContent},
+    ui::{widgets::Group, ElementState, Id, Key, KeyCode, Layout, Ui, UiContent},
 };
 
+/// A row of tabs, the selected one is returned from [Tabbar::ui].
+///
+/// Clicking a tab focuses the bar, then Left and Right switch between the tabs.
+/// The focused bar does not make [Ui::is_keyboard_captured] true.
+/// Colors come from `Skin::tabbar_style`: `color_selected` for the selected tab,
+/// `color_hovered` for the hovered one and `color` for the rest,
+/// and from `Skin::tabbar_disabled_style` for the disabled tabs.
 pub struct Tabbar<'a, 'b> {
     id: Id,
     size: Vec2,
     selected_tab: Option<&'b mut u32>,
     tabs: &'a [&'a str],
+    disabled: &'a [u32],
 }
 
 impl<'a, 'b> Tabbar<'a, 'b> {
@@ -17,6 +25,7 @@ impl<'a, 'b> Tabbar<'a, 'b> {
             size,
             tabs,
             selected_tab: None,
+            disabled: &[],
         }
     }
 
@@ -26,20 +35,56 @@ impl<'a, 'b> Tabbar<'a, 'b> {
             selected_tab,
             size: self.size,
             tabs: self.tabs,
+            disabled: self.disabled,
         }
     }
 
+    /// Indices of the tabs that can't be selected.
+    pub fn disabled(self, disabled: &'a [u32]) -> Self {
+        Tabbar { disabled, ..self }
+    }
+
     pub fn ui(mut self, ui: &mut Ui) -> u32 {
+        ui.arrow_keys_only.insert(self.id);
         let context = ui.get_active_window_context();
 
         let pos = context.window.cursor.fit(self.size, Layout::Vertical);
+        let bar = Rect::new(pos.x, pos.y, self.size.x, self.size.y);
 
         let width = self.size.x / self.tabs.len() as f32;
 
-        let selected = *self
-            .selected_tab
-            .as_deref()
-            .unwrap_or_else(|| context.storage_u32.entry(self.id).or_insert(0));
+        let id = self.id;
+        if context.input_focused(id)
+            && context.input.click_down()
+            && !bar.contains(context.input.mouse_position)
+        {
+            *context.input_focus = None;
+        }
+        let input_focused = context.input_focused(id);
+
+        let selected_mut = match self.selected_tab.as_deref_mut() {
+            Some(selected) => selected,
+            None => context.storage_u32.entry(id).or_insert(0),
+        };
+
+        if context.focused && input_focused {
+            for character in &context.input.input_buffer {
+                let step: i64 = match character.key {
+                    Key::KeyCode(KeyCode::Left) => -1,
+                    Key::KeyCode(KeyCode::Right) => 1,
+                    _ => continue,
+                };
+                // skip over the disabled tabs, stay put if there is no other tab that way
+                let mut tab = *selected_mut as i64 + step;
+                while (0..self.tabs.len() as i64).contains(&tab) {
+                    if !self.disabled.contains(&(tab as u32)) {
+                        *selected_mut = tab as u32;
+                        break;
+                    }
+                    tab += step;
+                }
+            }
+        }
 
         for (n, label) in self.tabs.iter().enumerate() {
             let rect = Rect::new(
@@ -48,49 +93,94 @@ impl<'a, 'b> Tabbar<'a, 'b> {
                 width - 2.,
                 self.size.y,
             );
-            let hovered = rect.contains(context.input.mouse_position);
-            let selected = n as u32 == selected;
+            let disabled = self.disabled.contains(&(n as u32));
+            let hovered = !disabled && rect.contains(context.input.mouse_position);
 
             if context.focused && hovered && context.input.click_up {
-                let id = self.id;
-                let selected_mut = self
-                    .selected_tab
-                    .as_deref_mut()
-                    .unwrap_or_else(|| context.storage_u32.entry(id).or_insert(0));
-
                 *selected_mut = n as u32;
+                *context.input_focus = Some(id);
+
+                // the release that switched the tab must not click a widget of the new tab
+                context.input.click_up = false;
             }
 
+            let style = if disabled {
+                &context.style.tabbar_disabled_style
+            } else {
+                &context.style.tabbar_style
+            };
+            let element_state = ElementState {
+                focused: context.focused,
+                hovered,
+                clicked: hovered && context.input.is_mouse_down,
+                selected: n as u32 == *selected_mut,
+            };
+
             context.window.painter.draw_element_background(
-                &context.style.tabbar_style,
+                style,
                 rect.point(),
                 rect.size(),
-                ElementState {
-                    focused: context.focused,
-                    hovered,
-                    clicked: hovered && context.input.is_mouse_down,
-                    selected,
-                },
+                element_state,
             );
 
             context.window.painter.draw_element_content(
-                &context.style.tabbar_style,
+                style,
                 pos + vec2(width * n as f32, 0.0),
                 vec2(width, self.size.y),
                 &UiContent::Label((*label).into()),
-                ElementState {
-                    focused: context.focused,
-                    hovered,
-                    clicked: hovered && context.input.is_mouse_down,
-                    selected,
-                },
+                element_state,
             );
         }
 
-        *self
-            .selected_tab
-            .as_deref()
-            .unwrap_or_else(|| context.storage_u32.entry(self.id).or_insert(0))
+        *selected_mut
+    }
+}
+
+/// A [Tabbar] above a [Group] with the selected tab's content.
+///
+/// Every tab gets a group of its own, so each keeps its own scroll position.
+pub struct Tabs<'a> {
+    id: Id,
+    size: Vec2,
+    tabs: &'a [&'a str],
+    disabled: &'a [u32],
+    bar_height: f32,
+}
+
+impl<'a> Tabs<'a> {
+    pub const fn new(id: Id, size: Vec2, tabs: &'a [&'a str]) -> Tabs<'a> {
+        Tabs {
+            id,
+            size,
+            tabs,
+            disabled: &[],
+            bar_height: 22.,
+        }
+    }
+
+    /// Indices of the tabs that can't be selected.
+    pub const fn disabled(self, disabled: &'a [u32]) -> Self {
+        Tabs { disabled, ..self }
+    }
+
+    /// Default is 22.
+    pub const fn bar_height(self, bar_height: f32) -> Self {
+        Tabs { bar_height, ..self }
+    }
+
+    /// Calls `f` with the index of the selected tab, inside the tab's group.
+    pub fn ui<F: FnOnce(&mut Ui, u32)>(self, ui: &mut Ui, selected_tab: &mut u32, f: F) {
+        let selected = Tabbar::new(self.id, vec2(self.size.x, self.bar_height), self.tabs)
+            .selected_tab(Some(selected_tab))
+            .disabled(self.disabled)
+            .ui(ui);
+
+        Group::new(
+            hash!(self.id, "tab", selected),
+            vec2(self.size.x, self.size.y - self.bar_height),
+        )
+        .layout(Layout::Vertical)
+        .ui(ui, |ui| f(ui, selected));
     }
 }
 
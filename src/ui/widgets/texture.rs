@@ -1,7 +1,8 @@
 use crate::{
+    color::{Color, WHITE},
     math::{Rect, Vec2},
     texture::Texture2D,
-    ui::{Layout, Ui},
+    ui::{Id, Layout, Ui},
 };
 
 pub struct Texture {
@@ -9,6 +10,8 @@ pub struct Texture {
     w: f32,
     h: f32,
     texture: Texture2D,
+    source: Option<Rect>,
+    color: Color,
 }
 
 impl Texture {
@@ -18,6 +21,8 @@ impl Texture {
             w: 100.,
             h: 100.,
             texture,
+            source: None,
+            color: WHITE,
         }
     }
 
@@ -31,6 +36,19 @@ impl Texture {
         Texture { position, ..self }
     }
 
+    /// Part of the texture to draw, in pixels, like an icon from an atlas.
+    pub fn source(self, source: Rect) -> Self {
+        Texture {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// Tint multiplied with the texture colors.
+    pub fn color(self, color: Color) -> Self {
+        Texture { color, ..self }
+    }
+
     pub fn ui(self, ui: &mut Ui) -> bool {
         let context = ui.get_active_window_context();
 
@@ -40,10 +58,12 @@ impl Texture {
             .window
             .cursor
             .fit(size, self.position.map_or(Layout::Vertical, Layout::Free));
-        context
-            .window
-            .painter
-            .draw_raw_texture(Rect::new(pos.x, pos.y, self.w, self.h), &self.texture);
+        context.window.painter.draw_raw_texture_ex(
+            Rect::new(pos.x, pos.y, self.w, self.h),
+            &self.texture,
+            self.source,
+            self.color,
+        );
 
         let rect = Rect::new(pos.x, pos.y, size.x, size.y);
         let hovered = rect.contains(context.input.mouse_position);
@@ -52,8 +72,149 @@ impl Texture {
     }
 }
 
+/// A button drawn as a texture, or a part of one, for things like inventory slots.
+///
+/// The hovered and pressed looks are either another tint or another part of the texture.
+pub struct TextureButton {
+    id: Id,
+    position: Option<Vec2>,
+    size: Vec2,
+    texture: Texture2D,
+    source: Option<Rect>,
+    hovered_source: Option<Rect>,
+    pressed_source: Option<Rect>,
+    color: Color,
+    hovered_color: Color,
+    pressed_color: Color,
+}
+
+impl TextureButton {
+    pub fn new(id: Id, texture: Texture2D, size: Vec2) -> TextureButton {
+        TextureButton {
+            id,
+            position: None,
+            size,
+            texture,
+            source: None,
+            hovered_source: None,
+            pressed_source: None,
+            color: WHITE,
+            hovered_color: Color::new(0.85, 0.85, 0.85, 1.),
+            pressed_color: Color::new(0.7, 0.7, 0.7, 1.),
+        }
+    }
+
+    pub fn position<P: Into<Option<Vec2>>>(self, position: P) -> Self {
+        let position = position.into();
+
+        TextureButton { position, ..self }
+    }
+
+    /// Part of the texture to draw, in pixels, the whole texture by default.
+    pub fn source(self, source: Rect) -> Self {
+        TextureButton {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// Part of the texture to draw while hovered, `source` by default.
+    pub fn hovered_source(self, source: Rect) -> Self {
+        TextureButton {
+            hovered_source: Some(source),
+            ..self
+        }
+    }
+
+    /// Part of the texture to draw while pressed, `hovered_source` by default.
+    pub fn pressed_source(self, source: Rect) -> Self {
+        TextureButton {
+            pressed_source: Some(source),
+            ..self
+        }
+    }
+
+    /// Tint multiplied with the texture colors, white by default.
+    pub fn color(self, color: Color) -> Self {
+        TextureButton { color, ..self }
+    }
+
+    /// Tint while hovered, slightly darker than white by default.
+    pub fn hovered_color(self, hovered_color: Color) -> Self {
+        TextureButton {
+            hovered_color,
+            ..self
+        }
+    }
+
+    /// Tint while pressed, darker than `hovered_color` by default.
+    pub fn pressed_color(self, pressed_color: Color) -> Self {
+        TextureButton {
+            pressed_color,
+            ..self
+        }
+    }
+
+    /// True on the frame the mouse button got released over the button,
+    /// if it was pressed over it too: dragging something off the button does not click it.
+    pub fn ui(self, ui: &mut Ui) -> bool {
+        let mut context = ui.get_active_window_context();
+
+        let pos = context.window.cursor.fit(
+            self.size,
+            self.position.map_or(Layout::Vertical, Layout::Free),
+        );
+        let rect = Rect::new(pos.x, pos.y, self.size.x, self.size.y);
+        let (hovered, click_down) = context.register_click_intention(rect);
+
+        let pressed = context
+            .storage_any
+            .get_or_default::<bool>(hash!(self.id, "pressed"));
+        if click_down {
+            *pressed = true;
+        }
+        let clicked = *pressed && hovered && context.input.click_up();
+        if !context.input.is_mouse_down {
+            *pressed = false;
+        }
+
+        let (source, color) = if *pressed && hovered {
+            (
+                self.pressed_source.or(self.hovered_source).or(self.source),
+                self.pressed_color,
+            )
+        } else if hovered && context.focused {
+            (self.hovered_source.or(self.source), self.hovered_color)
+        } else {
+            (self.source, self.color)
+        };
+        context
+            .window
+            .painter
+            .draw_raw_texture_ex(rect, &self.texture, source, color);
+
+        clicked
+    }
+}
+
 impl Ui {
     pub fn texture(&mut self, texture: Texture2D, w: f32, h: f32) -> bool {
         Texture::new(texture).size(w, h).ui(self)
     }
+
+    /// A [TextureButton] showing `source` of the texture, the whole texture when None.
+    pub fn texture_button(
+        &mut self,
+        id: Id,
+        texture: Texture2D,
+        size: Vec2,
+        source: Option<Rect>,
+    ) -> bool {
+        let button = TextureButton::new(id, texture, size);
+        match source {
+            Some(source) => button.source(source),
+            None => button,
+        }
+        .ui(self)
+    }
 }
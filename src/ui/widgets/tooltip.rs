@@ -0,0 +1,154 @@
+use crate::{
+    math::{vec2, Rect, Vec2},
+    text::atlas::Atlas,
+    ui::{render::Painter, ElementState, Ui},
+};
+
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+};
+
+/// Tooltips are drawn on top of all the windows, so they get a painter of their own.
+pub(crate) struct Tooltips {
+    pub(crate) painter: Painter,
+    // the hovered item and the time its hover began
+    hovered: Option<(Rect, f32)>,
+    requested: bool,
+}
+
+impl Tooltips {
+    pub(crate) const fn new(atlas: Arc<Mutex<Atlas>>) -> Tooltips {
+        Tooltips {
+            painter: Painter::new(atlas),
+            hovered: None,
+            requested: false,
+        }
+    }
+
+    pub(crate) fn new_frame(&mut self) {
+        self.painter.clear();
+
+        // no tooltip was asked for the hovered item, the hover is over
+        if !self.requested {
+            self.hovered = None;
+        }
+        self.requested = false;
+    }
+}
+
+/// A tooltip for the last widget, shown after hovering it for `Skin::tooltip_delay` seconds.
+///
+/// Lines are separated with `\n`.
+pub struct Tooltip<'a> {
+    text: Cow<'a, str>,
+}
+
+impl<'a> Tooltip<'a> {
+    pub fn new<S>(text: S) -> Tooltip<'a>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        Tooltip { text: text.into() }
+    }
+
+    pub fn ui(self, ui: &mut Ui) {
+        if !ui.last_item_hovered() || ui.input.is_mouse_down {
+            return;
+        }
+        let item = ui
+            .get_active_window_context()
+            .window
+            .cursor
+            .last_item
+            .unwrap();
+
+        let time = ui.time;
+        let tooltips = &mut ui.tooltips;
+        tooltips.requested = true;
+        let since = match tooltips.hovered {
+            Some((rect, since)) if rect == item => since,
+            _ => {
+                tooltips.hovered = Some((item, time));
+                time
+            }
+        };
+
+        let style = &ui.skin_stack.top().tooltip_style;
+        if time - since < ui.skin_stack.top().tooltip_delay {
+            return;
+        }
+
+        let painter = &mut tooltips.painter;
+        let font = &mut *style.font.lock().unwrap();
+        let font_size = style.font_size;
+        let line_height = font_size as f32;
+
+        let lines: Vec<&str> = self.text.lines().collect();
+        let width = lines
+            .iter()
+            .map(|line| painter.label_size(line, None, font, font_size).width)
+            .fold(0., f32::max);
+        // measured all together, so all the lines share the baseline offset
+        let measures = painter.label_size(&lines.concat(), None, font, font_size);
+
+        let margin = style.margin.unwrap_or_default();
+        let background_margin = style.background_margin.unwrap_or_default();
+        let size = vec2(
+            width + margin.left + margin.right + background_margin.left + background_margin.right,
+            lines.len() as f32 * line_height
+                + margin.top
+                + margin.bottom
+                + background_margin.top
+                + background_margin.bottom,
+        );
+
+        // below the mouse cursor, or above it if there is no room left at the bottom
+        let screen = ui.root_window.size;
+        let mouse = ui.input.mouse_position;
+        let mut pos = mouse + vec2(12., 16.);
+        if pos.y + size.y > screen.y {
+            pos.y = mouse.y - size.y - 4.;
+        }
+        let pos = Vec2::new(
+            pos.x.min(screen.x - size.x).max(0.),
+            pos.y.min(screen.y - size.y).max(0.),
+        );
+
+        let element_state = ElementState {
+            focused: true,
+            ..Default::default()
+        };
+        painter.draw_element_background(style, pos, size, element_state);
+
+        let top = (line_height / 2. - (measures.height / 2.).trunc())
+            + margin.top
+            + background_margin.top
+            + measures.offset_y;
+        for (n, line) in lines.iter().enumerate() {
+            painter.draw_label(
+                line,
+                pos + vec2(
+                    margin.left + background_margin.left,
+                    top + n as f32 * line_height,
+                ),
+                Some(style.text_color(element_state)),
+                font,
+                font_size,
+            );
+        }
+    }
+}
+
+impl Ui {
+    /// Show a tooltip for the last widget once it has been hovered for a while,
+    /// see [Tooltip].
+    ///
+    /// ```ignore
+    /// ui.button(None, "Save");
+    /// ui.tooltip("Save the level\nCtrl+S");
+    /// ```
+    pub fn tooltip(&mut self, text: &str) {
+        Tooltip::new(text).ui(self)
+    }
+}
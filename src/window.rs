@@ -3,6 +3,7 @@
 use crate::{get_context, get_quad_context};
 
 use crate::color::Color;
+use crate::math::Rect;
 
 // miniquad is re-exported for the use in combination with `get_internal_gl`
 pub use miniquad;
@@ -33,24 +34,52 @@ pub fn gl_set_drawcall_buffer_capacity(max_vertices: usize, max_indices: usize)
         .update_drawcall_capacity(get_quad_context(), max_vertices, max_indices);
 }
 
+/// The rendering backend under macroquad, from [get_internal_gl].
 pub struct InternalGlContext<'a> {
     pub quad_context: &'a mut dyn miniquad::RenderingBackend,
     pub quad_gl: &'a mut crate::quad_gl::QuadGl,
 }
 
 impl<'a> InternalGlContext<'a> {
-    /// Draw all the batched stuff and reset the internal state cache.
-    /// May be helpful for combining macroquad's drawing with raw miniquad/opengl calls.
+    /// Draw all the batched stuff.
+    ///
+    /// macroquad only sends its draw calls to the GPU at the end of the frame,
+    /// so raw miniquad/opengl calls made without flushing first are drawn below
+    /// everything macroquad was asked to draw earlier in the frame.
     pub fn flush(&mut self) {
         get_context().perform_render_passes();
     }
 }
 
+/// Direct access to miniquad, to issue draw calls of its own in between macroquad's ones.
+///
+/// ```ignore
+/// draw_rectangle(10., 10., 100., 100., RED);
+/// {
+///     let mut gl = unsafe { get_internal_gl() };
+///     gl.flush();
+///
+///     gl.quad_context.begin_default_pass(miniquad::PassAction::Nothing);
+///     gl.quad_context.apply_pipeline(&pipeline);
+///     gl.quad_context.apply_bindings(&bindings);
+///     gl.quad_context.draw(0, 6, 1);
+///     gl.quad_context.end_render_pass();
+/// }
+/// draw_text("on top of the custom mesh", 10., 150., 30., WHITE);
+/// ```
+/// See the `raw_miniquad` example for the whole thing.
+///
 /// # Safety
 ///
-/// This function is unsafe because it provides direct access to the internal OpenGL context.
-/// The caller must ensure that OpenGL operations are performed safely and do not interfere
-/// with macroquad's internal state management.
+/// macroquad assumes nobody else touches the GPU while it batches, so
+/// - call [InternalGlContext::flush] before the first draw call of your own;
+/// - end every render pass you begin, macroquad begins its own passes;
+/// - leave any state miniquad does not track, like raw opengl calls made through
+///   `miniquad::gl`, as you found it. Pipelines, bindings, viewport and scissor are
+///   applied again by macroquad on each of its draw calls and need no restoring;
+/// - do not delete the textures, buffers or pipelines macroquad has created;
+/// - drop the returned context before calling macroquad's drawing functions again,
+///   it borrows the same state they do.
 pub unsafe fn get_internal_gl<'a>() -> InternalGlContext<'a> {
     let context = get_context();
 
@@ -60,37 +89,209 @@ pub unsafe fn get_internal_gl<'a>() -> InternalGlContext<'a> {
     }
 }
 
+/// Width of the window in screen coordinates, logical pixels unless
+/// [Conf::high_dpi](crate::conf::Conf::high_dpi) is set.
+///
+/// `draw_rectangle(0., 0., screen_width(), screen_height(), color)` fills the window.
 pub fn screen_width() -> f32 {
     let context = get_context();
-    context.screen_width / miniquad::window::dpi_scale()
+    context.screen_width / context.dpi_scale()
 }
 
 pub fn screen_height() -> f32 {
     let context = get_context();
 
-    context.screen_height / miniquad::window::dpi_scale()
+    context.screen_height / context.dpi_scale()
 }
 
+/// The display scale factor of the OS, 2 on a typical "retina" display.
+///
+/// Only more than 1 when the framebuffer is full-resolution, with
+/// `miniquad_conf.high_dpi` or [Conf::high_dpi](crate::conf::Conf::high_dpi).
+/// To go from screen coordinates to physical pixels use [dpi_scale].
 pub fn screen_dpi_scale() -> f32 {
     miniquad::window::dpi_scale()
 }
 
+/// Physical pixels per unit of the screen coordinates: [screen_dpi_scale],
+/// or 1 with [Conf::high_dpi](crate::conf::Conf::high_dpi).
+///
+/// Scissor rects and viewports are in physical pixels,
+/// multiply the coordinates by this to get there.
+pub fn dpi_scale() -> f32 {
+    get_context().dpi_scale()
+}
+
+/// Clip all the following drawing to the `[x, y, w, h]` rectangle.
+///
+/// Coordinates are in physical pixels of the current render target (or the window),
+/// with the origin in the top left corner. They are not affected by the camera,
+/// and keep working across `set_camera`/`set_default_camera` calls.
+/// Replaces all the rectangles pushed with [push_scissor].
+/// The scissor is reset at the beginning of every frame.
+pub fn set_scissor(x: i32, y: i32, w: i32, h: i32) {
+    let context = get_context();
+
+    context.scissor_stack.clear();
+    context.scissor_stack.push((x, y, w.max(0), h.max(0)));
+    context.apply_scissor();
+}
+
+/// Stop clipping the drawing, also clears the [push_scissor] stack.
+/// Drawing is still clipped to the camera's viewport, if it has one.
+pub fn reset_scissor() {
+    let context = get_context();
+
+    context.scissor_stack.clear();
+    context.apply_scissor();
+}
+
+/// Clip the drawing to the intersection of `[x, y, w, h]` and the current scissor rectangle.
+/// The previous rectangle is restored with [pop_scissor].
+/// See [set_scissor] for the coordinates.
+pub fn push_scissor(x: i32, y: i32, w: i32, h: i32) {
+    let context = get_context();
+
+    let mut clip = (x, y, w.max(0), h.max(0));
+    if let Some(&top) = context.scissor_stack.last() {
+        clip = intersect_scissor(clip, top);
+    }
+
+    context.scissor_stack.push(clip);
+    context.apply_scissor();
+}
+
+/// Clip the drawing to the intersection of `rect` and the current scissor rectangle,
+/// for a scrolling panel of the UI for example. The previous one is restored with [pop_clip_rect].
+///
+/// `rect` is in the pixels of [screen_width] and [screen_height], the ones of
+/// [mouse_position](crate::input::mouse_position), rounded outwards to whole physical pixels.
+/// While drawing into a render target, use [push_scissor] with the pixels of the target.
+pub fn push_clip_rect(rect: Rect) {
+    let dpi_scale = dpi_scale();
+    let (x0, y0) = ((rect.x * dpi_scale).floor(), (rect.y * dpi_scale).floor());
+    let (x1, y1) = (
+        (rect.right() * dpi_scale).ceil(),
+        (rect.bottom() * dpi_scale).ceil(),
+    );
+
+    push_scissor(x0 as i32, y0 as i32, (x1 - x0) as i32, (y1 - y0) as i32);
+}
+
+/// Restore the clipping before the last [push_clip_rect], same as [pop_scissor].
+pub fn pop_clip_rect() {
+    pop_scissor();
+}
+
+/// Restore the scissor rectangle active before the last [push_scissor] or [push_clip_rect].
+///
+/// Logs a warning and does nothing when there is nothing to pop.
+pub fn pop_scissor() {
+    let context = get_context();
+
+    if context.scissor_stack.pop().is_none() {
+        crate::logging::warn!("pop_scissor: no scissor rectangle was pushed");
+        return;
+    }
+    context.apply_scissor();
+}
+
+pub(crate) fn intersect_scissor(
+    (ax, ay, aw, ah): (i32, i32, i32, i32),
+    (bx, by, bw, bh): (i32, i32, i32, i32),
+) -> (i32, i32, i32, i32) {
+    let (x0, y0) = (ax.max(bx), ay.max(by));
+    let (x1, y1) = ((ax + aw).min(bx + bw), (ay + ah).min(by + bh));
+
+    // rectangles that do not overlap leave nothing to draw into
+    (x0, y0, (x1 - x0).max(0), (y1 - y0).max(0))
+}
+
 /// Request the window size to be the given value. This takes DPI into account.
 ///
 /// Note that the OS might decide to give a different size. Additionally, the size in macroquad won't be updated until the next `next_frame().await`.
 pub fn request_new_screen_size(width: f32, height: f32) {
-    miniquad::window::set_window_size(
-        (width * miniquad::window::dpi_scale()) as u32,
-        (height * miniquad::window::dpi_scale()) as u32,
-    );
+    let dpi_scale = dpi_scale();
+    miniquad::window::set_window_size((width * dpi_scale) as u32, (height * dpi_scale) as u32);
     // We do not set the context.screen_width and context.screen_height here.
     // After `set_window_size` is called, EventHandlerFree::resize will be invoked, setting the size correctly.
     // Because the OS might decide to give a different screen dimension, setting the context.screen_* here would be confusing.
 }
 
 /// Toggle whether the window is fullscreen.
+///
+/// Going back to windowed mode restores the window size and position from before
+/// going fullscreen. On the web this uses the browser fullscreen API.
+/// `screen_width()` and `screen_height()` follow the new size on the next frame.
 pub fn set_fullscreen(fullscreen: bool) {
-    miniquad::window::set_fullscreen(fullscreen);
+    let context = get_context();
+    // no early return on an unchanged state, the window may have left fullscreen
+    // without macroquad noticing it
+    let was_fullscreen = context.fullscreen;
+    context.fullscreen = fullscreen;
+
+    if fullscreen {
+        if !was_fullscreen {
+            let size = (context.screen_width as u32, context.screen_height as u32);
+            #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+            let position = miniquad::window::get_window_position();
+            #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+            let position = (0, 0);
+            context.windowed_geometry = Some((size, position));
+        }
+
+        miniquad::window::set_fullscreen(true);
+    } else {
+        miniquad::window::set_fullscreen(false);
+
+        if let Some(((width, height), (_x, _y))) = context.windowed_geometry.take() {
+            miniquad::window::set_window_size(width, height);
+            #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+            miniquad::window::set_window_position(_x, _y);
+        }
+    }
+}
+
+/// Whether the window is fullscreen, either from [Conf::fullscreen] or [set_fullscreen].
+///
+/// Leaving fullscreen through the OS or with Esc in the browser is noticed when
+/// the window gets back the size it had before [set_fullscreen].
+pub fn is_fullscreen() -> bool {
+    get_context().fullscreen
+}
+
+/// Converts an image into a window icon for [Conf::icon].
+/// The image is scaled to all the sizes the platforms want, a square image looks best.
+///
+/// The backend only sets the icon at startup, from `Conf::icon`. It has no way to change
+/// the icon of an open window, so there is no runtime `set_window_icon`.
+pub fn window_icon_from_image(image: &crate::texture::Image) -> miniquad::conf::Icon {
+    let scaled = |size| image.resize(size, size, crate::texture::FilterMode::Linear);
+
+    let mut icon = miniquad::conf::Icon {
+        small: [0; 16 * 16 * 4],
+        medium: [0; 32 * 32 * 4],
+        big: [0; 64 * 64 * 4],
+    };
+    icon.small.copy_from_slice(&scaled(16).bytes);
+    icon.medium.copy_from_slice(&scaled(32).bytes);
+    icon.big.copy_from_slice(&scaled(64).bytes);
+    icon
+}
+
+/// Text from the system clipboard, `None` if it is empty or holds something else than text.
+///
+/// On the web the browser only lets the page read the clipboard from a paste
+/// event, so this returns the text of the last ctrl+v, or `None` before the first one.
+pub fn get_clipboard_text() -> Option<String> {
+    miniquad::window::clipboard_get().filter(|text| !text.is_empty())
+}
+
+/// Put text into the system clipboard.
+///
+/// On the web it is best-effort: the browser may refuse it outside of a key press or a click.
+pub fn set_clipboard_text(text: &str) {
+    miniquad::window::clipboard_set(text);
 }
 
 /// With `set_panic_handler` set to a handler code, macroquad will use
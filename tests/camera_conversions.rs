@@ -0,0 +1,34 @@
+use macroquad::prelude::*;
+
+#[macroquad::test]
+async fn camera2d_screen_world_round_trip() {
+    let (w, h) = (screen_width(), screen_height());
+    let viewports = [None, Some((10, 20, (w / 2.) as i32, (h / 3.) as i32))];
+
+    for viewport in viewports {
+        for (zoom, rotation) in [(1., 0.), (0.37, 30.), (4.2, -135.)] {
+            let camera = Camera2D {
+                zoom: vec2(zoom * 2. / w, zoom * 3. / h),
+                target: vec2(-123., 456.),
+                offset: vec2(0.1, -0.2),
+                rotation,
+                viewport,
+                ..Default::default()
+            };
+
+            for point in [vec2(0., 0.), vec2(17.5, -300.), vec2(-1000., 2000.)] {
+                let round_trip = camera.screen_to_world(camera.world_to_screen(point));
+                assert!(
+                    round_trip.distance(point) < 0.01,
+                    "{point} -> {round_trip} with zoom {zoom}, rotation {rotation}, viewport {viewport:?}",
+                );
+            }
+
+            // and the other way around, from the screen corners to the world
+            for point in [vec2(0., 0.), vec2(w, h), vec2(w / 3., h / 5.)] {
+                let round_trip = camera.world_to_screen(camera.screen_to_world(point));
+                assert!(round_trip.distance(point) < 0.01);
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use macroquad::{
+    experimental::coroutines::{
+        start_coroutine, timeout, wait_for_all, wait_for_any, wait_frames, wait_seconds, TimedOut,
+    },
+    telemetry,
+    window::next_frame,
+};
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+struct DropFlag(Arc<AtomicBool>);
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+#[macroquad::test]
+async fn cancel_drops_the_future() {
+    let dropped = Arc::new(AtomicBool::new(false));
+    let coroutine = start_coroutine({
+        let flag = DropFlag(dropped.clone());
+        async move {
+            let _flag = flag;
+            loop {
+                next_frame().await;
+            }
+        }
+    });
+    next_frame().await;
+
+    coroutine.cancel();
+    assert!(dropped.load(Ordering::Relaxed));
+    assert!(coroutine.is_done());
+
+    // cancelling itself, the future lives until its next await
+    let dropped = Arc::new(AtomicBool::new(false));
+    let handle = Arc::new(Mutex::new(None));
+    let coroutine = start_coroutine({
+        let (flag, handle, dropped) = (DropFlag(dropped.clone()), handle.clone(), dropped.clone());
+        async move {
+            let _flag = flag;
+            loop {
+                if let Some(coroutine) = *handle.lock().unwrap() {
+                    coroutine.cancel();
+                    assert!(!dropped.load(Ordering::Relaxed));
+                }
+                next_frame().await;
+            }
+        }
+    });
+    *handle.lock().unwrap() = Some(coroutine);
+    next_frame().await;
+    assert!(dropped.load(Ordering::Relaxed));
+    assert!(coroutine.is_done());
+
+    assert_eq!(telemetry::active_coroutines_count(), 0);
+}
+
+#[macroquad::test]
+async fn cancel_done_coroutine() {
+    let coroutine = start_coroutine(async move { 7 });
+    next_frame().await;
+    assert!(coroutine.is_done());
+
+    // no-op, the value is still there
+    coroutine.cancel();
+    assert_eq!(coroutine.retrieve(), Some(7));
+    coroutine.cancel();
+
+    // cancel wins over the manual poll
+    let mut coroutine = start_coroutine(async move {
+        next_frame().await;
+        1
+    });
+    coroutine.set_manual_poll();
+    coroutine.poll(0.);
+    coroutine.cancel();
+    coroutine.poll(0.);
+    assert!(coroutine.is_done());
+    assert_eq!(coroutine.retrieve(), None);
+}
+
+#[macroquad::test]
+async fn coroutine_timeout() {
+    let mut timed_out = start_coroutine(async move { timeout(1., wait_seconds(10.)).await });
+    let mut in_time = start_coroutine(async move { timeout(1., wait_seconds(0.5)).await });
+    timed_out.set_manual_poll();
+    in_time.set_manual_poll();
+
+    for _ in 0..3 {
+        timed_out.poll(0.4);
+        in_time.poll(0.4);
+    }
+    assert_eq!(timed_out.retrieve(), Some(Err(TimedOut)));
+    assert_eq!(in_time.retrieve(), Some(Ok(Some(()))));
+}
+
+#[macroquad::test]
+async fn coroutine_wait_for() {
+    let short = start_coroutine(wait_frames(1));
+    let long = start_coroutine(wait_frames(5));
+
+    let any = start_coroutine(async move { wait_for_any(&[long, short]).await });
+    let all = start_coroutine(async move { wait_for_all(&[short, long]).await });
+
+    while !any.is_done() {
+        next_frame().await;
+    }
+    assert_eq!(any.retrieve(), Some(1));
+    assert!(!long.is_done());
+    assert!(!all.is_done());
+
+    while !all.is_done() {
+        next_frame().await;
+    }
+    assert!(short.is_done() && long.is_done());
+}
@@ -1,6 +1,6 @@
 use macroquad::{
     experimental::{
-        coroutines::{start_coroutine, wait_seconds},
+        coroutines::{start_coroutine, wait_frames, wait_seconds},
         scene,
     },
     window::next_frame,
@@ -85,3 +85,22 @@ async fn coroutine_manual_poll_delay() {
 
     assert_eq!(scene::get_node(player).state, 1);
 }
+
+#[macroquad::test]
+async fn coroutine_wait_frames() {
+    let mut coroutine = start_coroutine(async move {
+        wait_seconds(0.).await;
+        wait_frames(2).await;
+        1
+    });
+
+    coroutine.set_manual_poll();
+
+    // wait_seconds(0.) takes one poll, wait_frames(2) two more
+    for _ in 0..3 {
+        coroutine.poll(0.);
+        assert_eq!(coroutine.retrieve(), None);
+    }
+    coroutine.poll(0.);
+    assert_eq!(coroutine.retrieve(), Some(1));
+}
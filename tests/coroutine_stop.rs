@@ -0,0 +1,70 @@
+use macroquad::{
+    experimental::coroutines::{start_coroutine, stop_coroutine},
+    telemetry,
+    window::next_frame,
+};
+
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+#[macroquad::test]
+async fn coroutine_stop() {
+    let polls = Arc::new(AtomicU32::new(0));
+
+    let coroutine = start_coroutine({
+        let polls = polls.clone();
+        async move {
+            loop {
+                polls.fetch_add(1, Ordering::Relaxed);
+                next_frame().await;
+            }
+        }
+    });
+    next_frame().await;
+    next_frame().await;
+    assert!(!coroutine.is_done());
+
+    coroutine.stop();
+    let stopped_at = polls.load(Ordering::Relaxed);
+    next_frame().await;
+    next_frame().await;
+    assert!(coroutine.is_done());
+    assert_eq!(polls.load(Ordering::Relaxed), stopped_at);
+
+    // a no-op once it is stopped or finished
+    stop_coroutine(coroutine);
+    let finished = start_coroutine(async move {});
+    next_frame().await;
+    assert!(finished.is_done());
+    finished.stop();
+    stop_coroutine(coroutine);
+
+    assert_eq!(telemetry::active_coroutines_count(), 0);
+}
+
+#[macroquad::test]
+async fn coroutine_stops_itself() {
+    let polls = Arc::new(AtomicU32::new(0));
+
+    let coroutine = Arc::new(std::sync::Mutex::new(None));
+    *coroutine.lock().unwrap() = Some(start_coroutine({
+        let (polls, coroutine) = (polls.clone(), coroutine.clone());
+        async move {
+            loop {
+                polls.fetch_add(1, Ordering::Relaxed);
+                if let Some(coroutine) = *coroutine.lock().unwrap() {
+                    stop_coroutine(coroutine);
+                }
+                next_frame().await;
+            }
+        }
+    }));
+
+    for _ in 0..3 {
+        next_frame().await;
+    }
+    assert!(coroutine.lock().unwrap().unwrap().is_done());
+    assert_eq!(polls.load(Ordering::Relaxed), 1);
+}
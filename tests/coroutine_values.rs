@@ -34,3 +34,34 @@ async fn coroutine_memory() {
 
     assert_eq!(telemetry::active_coroutines_count(), 0);
 }
+
+#[macroquad::test]
+async fn coroutine_value_dropped_on_clear() {
+    use macroquad::experimental::scene;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    struct DropFlag(Arc<AtomicBool>);
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let coroutine = start_coroutine({
+        let flag = DropFlag(dropped.clone());
+        async move { flag }
+    });
+    next_frame().await;
+    assert!(coroutine.is_done());
+    assert!(!dropped.load(Ordering::Relaxed));
+
+    // never retrieved
+    scene::clear();
+    assert!(dropped.load(Ordering::Relaxed));
+    assert!(coroutine.retrieve().is_none());
+    assert_eq!(telemetry::active_coroutines_count(), 0);
+}
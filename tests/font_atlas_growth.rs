@@ -0,0 +1,53 @@
+use macroquad::prelude::*;
+
+/// Draws "Hello" into an offscreen target and reads the pixels back.
+fn draw_hello(font: &Font) -> Vec<u8> {
+    let target = render_target(128, 64);
+    target.texture.set_filter(FilterMode::Nearest);
+
+    set_camera(&Camera2D {
+        render_target: Some(target.clone()),
+        ..Camera2D::from_display_rect(Rect::new(0., 0., 128., 64.))
+    });
+    clear_background(BLACK);
+    draw_text_ex(
+        "Hello",
+        4.,
+        40.,
+        TextParams {
+            font: Some(font),
+            font_size: 32,
+            color: WHITE,
+            ..Default::default()
+        },
+    );
+    set_default_camera();
+
+    target.texture.get_texture_data().bytes
+}
+
+#[macroquad::test]
+async fn font_atlas_growth() {
+    let mut font = get_default_font();
+    // nearest sampling, so the moved glyphs have to land on exactly the same texels
+    font.set_filter(FilterMode::Nearest);
+
+    let before = draw_hello(&font);
+    assert!(
+        before.chunks_exact(4).any(|p| p[0] > 0),
+        "nothing was drawn"
+    );
+
+    // ASCII at 11 sizes is a few megapixels of glyphs,
+    // the 512x512 atlas has to grow and re-pack a couple of times
+    let ascii = Font::ascii_character_list();
+    for size in (16..=96).step_by(8) {
+        font.populate_font_cache(&ascii, size);
+    }
+
+    // glyphs cached before the growth still point at their own pixels
+    let after = draw_hello(&font);
+    assert!(before == after, "glyphs changed after the atlas growth");
+
+    next_frame().await;
+}
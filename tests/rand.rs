@@ -0,0 +1,81 @@
+use macroquad::rand::{self, ChooseRandom, Rng};
+
+#[test]
+fn same_seed_same_sequence() {
+    let mut a = Rng::with_seed(7);
+    let mut b = Rng::with_seed(7);
+
+    for _ in 0..1000 {
+        assert_eq!(a.gen_range(-100, 100), b.gen_range(-100, 100));
+        assert_eq!(a.gen::<f32>(), b.gen::<f32>());
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+
+    let mut c = Rng::with_seed(8);
+    assert!((0..10).any(|_| a.next_u64() != c.next_u64()));
+}
+
+#[test]
+fn clone_forks_the_stream() {
+    let mut rng = Rng::with_seed(1);
+    rng.gen_range(0., 1.);
+
+    let mut fork = rng.clone();
+    let original: Vec<u32> = (0..100).map(|_| rng.gen_range(0, 10)).collect();
+    let forked: Vec<u32> = (0..100).map(|_| fork.gen_range(0, 10)).collect();
+    assert_eq!(original, forked);
+}
+
+#[test]
+fn ranges() {
+    let mut rng = Rng::with_seed(3);
+
+    for _ in 0..1000 {
+        let x = rng.gen_range(-2.5f32, 4.);
+        assert!((-2.5..4.).contains(&x));
+        let n = rng.gen_range(u64::MAX - 3, u64::MAX);
+        assert!(n >= u64::MAX - 3 && n < u64::MAX);
+        let f = rng.gen::<f64>();
+        assert!((0. ..1.).contains(&f));
+    }
+    assert_eq!(rng.gen_range(5, 5), 5);
+}
+
+#[test]
+fn global_snapshot() {
+    rand::srand(99);
+    let snapshot = rand::get_rng();
+    let first: Vec<i32> = (0..10).map(|_| rand::gen_range(0, 1000)).collect();
+
+    rand::set_rng(snapshot);
+    let replayed: Vec<i32> = (0..10).map(|_| rand::gen_range(0, 1000)).collect();
+    assert_eq!(first, replayed);
+}
+
+#[test]
+fn choose_replays() {
+    rand::srand(5);
+    let snapshot = rand::get_rng();
+    let mut deck: Vec<u32> = (0..52).collect();
+    deck.shuffle();
+    let picked: Vec<u32> = deck.choose_multiple(5).copied().collect();
+
+    rand::set_rng(snapshot);
+    let mut replayed: Vec<u32> = (0..52).collect();
+    replayed.shuffle();
+    assert_eq!(deck, replayed);
+    assert_eq!(
+        replayed.choose_multiple(5).copied().collect::<Vec<_>>(),
+        picked
+    );
+
+    let mut sorted = deck.clone();
+    sorted.sort();
+    assert_eq!(sorted, (0..52).collect::<Vec<_>>());
+
+    let mut picked = picked;
+    picked.sort();
+    picked.dedup();
+    assert_eq!(picked.len(), 5);
+    assert_eq!(Rng::with_seed(1).choose::<u32>(&[]), None);
+}
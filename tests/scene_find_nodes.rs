@@ -0,0 +1,41 @@
+use macroquad::experimental::scene::{self, Node};
+
+struct Enemy {
+    hp: i32,
+}
+impl Node for Enemy {}
+
+struct Player;
+impl Node for Player {}
+
+#[macroquad::test]
+async fn find_nodes_by_type() {
+    scene::clear();
+
+    scene::add_node(Enemy { hp: 1 });
+    let deleted = scene::add_node(Enemy { hp: 2 });
+    scene::add_node(Player);
+    scene::add_node(Enemy { hp: 3 });
+
+    assert_eq!(scene::find_nodes_by_type::<Enemy>().count(), 3);
+    assert_eq!(scene::find_nodes_by_type::<Player>().count(), 1);
+
+    // reading all of them at once is fine
+    let enemies: Vec<_> = scene::find_nodes_by_type::<Enemy>().collect();
+    assert_eq!(enemies.iter().map(|enemy| enemy.hp).sum::<i32>(), 6);
+    drop(enemies);
+
+    // a borrowed node is skipped
+    let borrowed = scene::get_node(deleted);
+    assert_eq!(scene::find_nodes_by_type::<Enemy>().count(), 2);
+    borrowed.delete();
+
+    // the deleted node is skipped right away, before it is freed at the end of the frame
+    scene::add_node(Enemy { hp: 4 });
+    let hps: Vec<_> = scene::find_nodes_by_type::<Enemy>()
+        .map(|enemy| enemy.hp)
+        .collect();
+    assert_eq!(hps, [1, 3, 4]);
+
+    scene::clear();
+}
@@ -0,0 +1,64 @@
+use macroquad::{
+    experimental::scene::{self, Node, OnDelete, RefMut, Transform},
+    math::vec2,
+    window::next_frame,
+};
+
+use std::cell::RefCell;
+
+std::thread_local! {
+    static LOG: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+}
+
+struct Named {
+    name: &'static str,
+}
+impl Node for Named {
+    fn update(node: RefMut<Self>) {
+        LOG.with(|log| log.borrow_mut().push(node.name));
+    }
+}
+
+#[macroquad::test]
+async fn parents_before_children() {
+    scene::clear();
+
+    let gun = scene::add_node(Named { name: "gun" });
+    let player = scene::add_node(Named { name: "player" });
+    let scope = scene::add_node(Named { name: "scope" });
+    scene::set_parent(scope, gun);
+    scene::set_parent(gun, player);
+    assert_eq!(scene::children(player), [gun.untyped()]);
+
+    next_frame().await;
+    assert_eq!(LOG.with(|log| log.take()), ["player", "gun", "scope"]);
+
+    scene::set_transform(
+        player,
+        Transform {
+            position: vec2(100., 0.),
+            rotation: std::f32::consts::PI,
+            ..Transform::IDENTITY
+        },
+    );
+    scene::set_transform(gun, Transform::from_position(vec2(10., 0.)));
+    let gun_position = scene::world_transform(gun).position;
+    assert!(gun_position.distance(vec2(90., 0.)) < 1e-4);
+
+    // the scope goes away with the player, the gun it holds is dropped where it is
+    scene::set_parent(scope, player);
+    scene::set_parent(gun, scope);
+    scene::set_on_delete(scope, OnDelete::DetachChildren);
+    assert!(scene::world_transform(gun).position.distance(gun_position) < 1e-4);
+
+    scene::get_node(player).delete();
+    assert!(!scope.is_valid());
+    assert!(gun.is_valid());
+    assert!(scene::parent(gun).is_none());
+    assert!(scene::world_transform(gun).position.distance(gun_position) < 1e-4);
+
+    next_frame().await;
+    assert_eq!(LOG.with(|log| log.take()), ["gun"]);
+
+    scene::clear();
+}
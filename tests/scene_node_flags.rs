@@ -0,0 +1,98 @@
+use macroquad::{
+    experimental::scene::{self, Handle, Node, RefMut},
+    window::next_frame,
+};
+
+use std::cell::RefCell;
+
+std::thread_local! {
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+}
+
+fn log(entry: String) {
+    LOG.with(|log| log.borrow_mut().push(entry));
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|log| log.take())
+}
+
+struct Named {
+    name: &'static str,
+}
+impl Node for Named {
+    fn update(node: RefMut<Self>) {
+        log(format!("update {}", node.name));
+    }
+
+    fn draw(node: RefMut<Self>) {
+        log(format!("draw {}", node.name));
+    }
+}
+
+struct Killer {
+    victims: Vec<Handle<Named>>,
+}
+impl Node for Killer {
+    fn update(mut node: RefMut<Self>) {
+        for victim in node.victims.drain(..) {
+            scene::get_node(victim).delete();
+            assert!(!victim.is_valid());
+
+            // still in memory until the end of the frame
+            assert!(scene::try_get_node(victim).is_some());
+        }
+    }
+}
+
+#[macroquad::test]
+async fn delete_during_update() {
+    scene::clear();
+
+    let a = scene::add_node(Named { name: "a" });
+    let killer = scene::add_node(Killer { victims: vec![] });
+    let c = scene::add_node(Named { name: "c" });
+    scene::add_node(Named { name: "d" });
+
+    // one node before the killer, one after it
+    scene::get_node(killer).victims = vec![a, c];
+    next_frame().await;
+
+    // "a" was updated before being deleted, it is still not drawn
+    assert_eq!(
+        take_log(),
+        ["update a", "update d", "draw d"].map(String::from)
+    );
+    assert!(!a.is_valid() && !c.is_valid());
+    assert!(scene::try_get_node(a).is_none());
+
+    next_frame().await;
+    assert_eq!(take_log(), ["update d", "draw d"].map(String::from));
+
+    scene::clear();
+}
+
+#[macroquad::test]
+async fn inactive_and_invisible_nodes() {
+    scene::clear();
+
+    let paused = scene::add_node(Named { name: "paused" });
+    let hidden = scene::add_node(Named { name: "hidden" });
+    scene::set_node_active(paused, false);
+    scene::set_node_visible(hidden, false);
+    next_frame().await;
+
+    assert_eq!(
+        take_log(),
+        ["update hidden", "draw paused"].map(String::from)
+    );
+
+    scene::set_node_active(paused, true);
+    next_frame().await;
+    assert_eq!(
+        take_log(),
+        ["update paused", "update hidden", "draw paused"].map(String::from)
+    );
+
+    scene::clear();
+}
@@ -0,0 +1,58 @@
+use macroquad::{
+    experimental::scene::{self, Node},
+    window::next_frame,
+};
+
+struct Enemy {
+    hp: i32,
+}
+impl Node for Enemy {}
+
+struct Turret;
+impl Node for Turret {}
+
+struct Coin;
+impl Node for Coin {}
+
+#[macroquad::test]
+async fn find_nodes_by_tag() {
+    scene::clear();
+
+    let weak = scene::add_node(Enemy { hp: 1 });
+    let strong = scene::add_node(Enemy { hp: 5 });
+    let turret = scene::add_node(Turret);
+    let coin = scene::add_node(Coin);
+    for enemy in [weak, strong] {
+        scene::add_tag(enemy, "enemy");
+    }
+    scene::add_tag(turret, "enemy");
+    scene::add_tag(coin, "pickup");
+    // adding a tag twice does nothing
+    scene::add_tag(coin, "pickup");
+
+    assert_eq!(scene::find_nodes_by_tag("enemy").count(), 3);
+    assert_eq!(scene::find_nodes_by_tag("pickup").count(), 1);
+    assert_eq!(scene::find_nodes_by_tag("boss").count(), 0);
+
+    // the turret is an enemy, but not an Enemy
+    let hps: Vec<_> = scene::find_nodes_by_tag_as::<Enemy>("enemy")
+        .map(|enemy| enemy.hp)
+        .collect();
+    assert_eq!(hps, [1, 5]);
+
+    scene::remove_tag(turret, "enemy");
+    assert!(!scene::has_tag(turret, "enemy"));
+    assert_eq!(scene::find_nodes_by_tag("enemy").count(), 2);
+
+    // a deleted node is gone from the tag right away, and a new node
+    // taking its memory does not get its tags
+    scene::get_node(weak).delete();
+    assert_eq!(scene::find_nodes_by_tag("enemy").count(), 1);
+    next_frame().await;
+
+    let reused = scene::add_node(Enemy { hp: 2 });
+    assert!(!scene::has_tag(reused, "enemy"));
+    assert_eq!(scene::find_nodes_by_tag("enemy").count(), 1);
+
+    scene::clear();
+}